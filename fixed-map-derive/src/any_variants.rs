@@ -1,14 +1,15 @@
 use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, quote, quote_spanned, ToTokens};
 use syn::spanned::Spanned;
-use syn::{DataEnum, Ident, Pat};
+use syn::{DataEnum, GenericParam, Ident, Pat};
 
 const MAP_STORAGE: &str = "__MapStorage";
 const SET_STORAGE: &str = "__SetStorage";
 
+use crate::attrs;
 use crate::context::Ctxt;
 
-///
+/// Variants may be unit variants or carry a single field which is a `Key`.
 pub(crate) fn implement(cx: &Ctxt<'_>, en: &DataEnum) -> Result<TokenStream, ()> {
     let ident = &cx.ast.ident;
 
@@ -16,19 +17,27 @@ pub(crate) fn implement(cx: &Ctxt<'_>, en: &DataEnum) -> Result<TokenStream, ()>
     let map_storage_t = cx.toks.map_storage_t();
     let set_storage_t = cx.toks.set_storage_t();
 
-    let const_wrapper = Ident::new(
-        &format!("__IMPL_KEY_FOR_{}", cx.ast.ident),
-        Span::call_site(),
-    );
+    let generics = generics(cx)?;
+    let args = &generics.args;
 
-    let mut fields = Fields::default();
+    let mut fields = Fields {
+        key: quote!(#ident<#args>),
+        generics,
+        ..Fields::default()
+    };
 
     for (index, variant) in en.variants.iter().enumerate() {
         let var = &variant.ident;
         let name = format_ident!("_{}", index);
+        let opts = attrs::variant(cx, variant)?;
 
         let kind = match &variant.fields {
             syn::Fields::Unit => {
+                if let Some(span) = opts.ordered {
+                    cx.error(span, "`ordered` is only supported on variants with a field");
+                    continue;
+                }
+
                 fields
                     .patterns
                     .push(cx.fallible(|| syn::parse2(quote!(#ident::#var)))?);
@@ -44,21 +53,55 @@ pub(crate) fn implement(cx: &Ctxt<'_>, en: &DataEnum) -> Result<TokenStream, ()>
                 }
 
                 let element = unnamed.unnamed.first().expect("Expected one element");
-                let map_storage = quote!(<#element as #key_t>::MapStorage::<V>);
+
+                let (map_storage, set_storage) = if opts.ordered.is_some() {
+                    let btree_map_storage = cx.toks.btree_map_storage();
+                    let btree_set_storage = cx.toks.btree_set_storage();
+
+                    (
+                        quote!(#btree_map_storage::<#element, V>),
+                        quote!(#btree_set_storage::<#element>),
+                    )
+                } else {
+                    (
+                        quote!(<#element as #key_t>::MapStorage::<V>),
+                        quote!(<#element as #key_t>::SetStorage),
+                    )
+                };
+
+                if !cx.ast.generics.params.is_empty() {
+                    let ty = &element.ty;
+
+                    let bound = if opts.ordered.is_some() {
+                        let clone_t = cx.toks.clone_t();
+                        let ord_t = cx.toks.ord_t();
+                        quote!(#ty: #clone_t + #ord_t)
+                    } else {
+                        quote!(#ty: #key_t)
+                    };
+
+                    fields
+                        .generics
+                        .predicates
+                        .push(cx.fallible(|| syn::parse2(bound))?);
+                }
+
                 let as_map_storage = quote!(<#map_storage as #map_storage_t<#element, V>>);
-                let set_storage = quote!(<#element as #key_t>::SetStorage);
                 let as_set_storage = quote!(<#set_storage as #set_storage_t<#element>>);
 
                 fields
                     .patterns
                     .push(cx.fallible(|| syn::parse2(quote!(#ident::#var(v))))?);
 
+                let generic = mentions_any(element.ty.to_token_stream(), &fields.generics.types);
+
                 Kind::Complex(Complex {
                     element,
                     map_storage,
                     as_map_storage,
                     set_storage,
                     as_set_storage,
+                    generic,
                 })
             }
             syn::Fields::Named(_) => {
@@ -79,26 +122,82 @@ pub(crate) fn implement(cx: &Ctxt<'_>, en: &DataEnum) -> Result<TokenStream, ()>
     let (map_storage_type_name, map_storage_impl) = impl_map_storage(cx, &fields)?;
     let (set_storage_type_name, set_storage_impl) = impl_set_storage(cx, &fields)?;
 
+    let key = &fields.key;
+    let Generics { params, args, .. } = &fields.generics;
+    let predicates = fields.generics.predicates();
+
     Ok(quote! {
-        const #const_wrapper: () = {
+        const _: () = {
             #map_storage_impl
             #set_storage_impl
 
             #[automatically_derived]
-            impl #key_t for #ident {
-                type MapStorage<V> = #map_storage_type_name<V>;
-                type SetStorage = #set_storage_type_name;
+            impl<#params> #key_t for #key where #predicates {
+                type MapStorage<V> = #map_storage_type_name<V, #args>;
+                type SetStorage = #set_storage_type_name<#args>;
             }
         };
     })
 }
 
+/// Collect the generics of the key, which are carried over to all generated
+/// items.
+fn generics(cx: &Ctxt<'_>) -> Result<Generics, ()> {
+    let mut generics = Generics::default();
+
+    for param in &cx.ast.generics.params {
+        match param {
+            GenericParam::Type(param) => {
+                let ident = &param.ident;
+
+                if ident == "V" {
+                    cx.error(
+                        ident.span(),
+                        "`V` is reserved for the value type of generated storage",
+                    );
+                    return Err(());
+                }
+
+                if !param.bounds.is_empty() {
+                    let bounds = &param.bounds;
+                    let predicate = cx.fallible(|| syn::parse2(quote!(#ident: #bounds)))?;
+                    generics.predicates.push(predicate);
+                }
+
+                generics.params.extend(quote!(#ident,));
+                generics.args.extend(quote!(#ident,));
+                generics.types.push(ident.clone());
+            }
+            GenericParam::Const(param) => {
+                let ident = &param.ident;
+                let ty = &param.ty;
+                generics.params.extend(quote!(const #ident: #ty,));
+                generics.args.extend(quote!(#ident,));
+            }
+            GenericParam::Lifetime(param) => {
+                cx.error(param.span(), "lifetime parameters are not supported");
+                return Err(());
+            }
+        }
+    }
+
+    if let Some(where_clause) = &cx.ast.generics.where_clause {
+        generics
+            .predicates
+            .extend(where_clause.predicates.iter().cloned());
+    }
+
+    Ok(generics)
+}
+
 /// Implement `MapStorage` implementation.
 fn impl_map_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(Ident, TokenStream), ()> {
     let vis = &cx.ast.vis;
+    let key = &fields.key;
+    let Generics { params, args, .. } = &fields.generics;
+    let predicates = fields.generics.predicates();
     let ident = &cx.ast.ident;
 
-    let mem = cx.toks.mem();
     let option = cx.toks.option();
     let map_storage_t = cx.toks.map_storage_t();
 
@@ -118,10 +217,14 @@ fn impl_map_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(Ident, TokenS
         let partial_eq_t = cx.toks.partial_eq_t();
         let eq_t = cx.toks.eq_t();
         let names = fields.names();
+        let bounds = fields
+            .generic()
+            .map(|Complex { map_storage, .. }| map_storage)
+            .collect::<Vec<_>>();
 
         output.impls.extend(quote! {
             #[automatically_derived]
-            impl<V> #partial_eq_t for #type_name<V> where V: #partial_eq_t {
+            impl<V, #params> #partial_eq_t for #type_name<V, #args> where #predicates V: #partial_eq_t, #(#bounds: #partial_eq_t,)* {
                 #[inline]
                 fn eq(&self, other: &Self) -> bool {
                     #(if #partial_eq_t::ne(&self.#names, &other.#names) {
@@ -133,7 +236,7 @@ fn impl_map_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(Ident, TokenS
             }
 
             #[automatically_derived]
-            impl<V> #eq_t for #type_name<V> where V: #eq_t {}
+            impl<V, #params> #eq_t for #type_name<V, #args> where #predicates V: #eq_t, #(#bounds: #eq_t,)* {}
         });
     }
 
@@ -143,21 +246,29 @@ fn impl_map_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(Ident, TokenS
         let bounds = fields
             .complex()
             .map(|Complex { map_storage, .. }| map_storage);
-        let names = fields.names();
+        let clone_bounds = fields
+            .generic()
+            .map(|Complex { map_storage, .. }| map_storage);
+        let names = fields.names().collect::<Vec<_>>();
 
         output.impls.extend(quote! {
             #[automatically_derived]
-            impl<V> #clone_t for #type_name<V> where V: #clone_t {
+            impl<V, #params> #clone_t for #type_name<V, #args> where #predicates V: #clone_t, #(#clone_bounds: #clone_t,)* {
                 #[inline]
                 fn clone(&self) -> Self {
                     Self {
                         #(#names: #clone_t::clone(&self.#names),)*
                     }
                 }
+
+                #[inline]
+                fn clone_from(&mut self, source: &Self) {
+                    #(#clone_t::clone_from(&mut self.#names, &source.#names);)*
+                }
             }
 
             #[automatically_derived]
-            impl<V> #copy_t for #type_name<V> where V: #copy_t, #(#bounds: #copy_t,)* {}
+            impl<V, #params> #copy_t for #type_name<V, #args> where #predicates V: #copy_t, #(#bounds: #copy_t,)* {}
         });
     }
 
@@ -179,6 +290,38 @@ fn impl_map_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(Ident, TokenS
         });
     }
 
+    {
+        let const_map_storage_t = cx.toks.const_map_storage_t();
+
+        let bounds = fields.complex().map(
+            |Complex {
+                 element,
+                 map_storage,
+                 ..
+             }| quote!(#map_storage: #const_map_storage_t<#element, V>),
+        );
+
+        let inits = fields.iter().map(|f| match &f.kind {
+            Kind::Complex(Complex {
+                element,
+                map_storage,
+                ..
+            }) => quote!(<#map_storage as #const_map_storage_t<#element, V>>::EMPTY),
+            Kind::Simple => quote!(#option::None),
+        });
+
+        let names = fields.names();
+
+        output.impls.extend(quote! {
+            #[automatically_derived]
+            impl<V, #params> #const_map_storage_t<#key, V> for #type_name<V, #args> where #predicates #(#bounds,)* {
+                const EMPTY: Self = Self {
+                    #(#names: #inits,)*
+                };
+            }
+        });
+    }
+
     {
         let patterns = &fields.patterns;
 
@@ -186,12 +329,12 @@ fn impl_map_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(Ident, TokenS
             Kind::Complex(Complex { as_map_storage, .. }) => {
                 quote!(#as_map_storage::insert(&mut self.#name, v, value))
             }
-            Kind::Simple => quote!(#mem::replace(&mut self.#name, #option::Some(value))),
+            Kind::Simple => quote!(#option::replace(&mut self.#name, value)),
         });
 
         output.items.extend(quote! {
             #[inline]
-            fn insert(&mut self, key: #ident, value: V) -> #option<V> {
+            fn insert(&mut self, key: #key, value: V) -> #option<V> {
                 match key {
                     #(#patterns => #insert,)*
                 }
@@ -243,7 +386,7 @@ fn impl_map_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(Ident, TokenS
 
         output.items.extend(quote! {
             #[inline]
-            fn contains_key(&self, value: #ident) -> bool {
+            fn contains_key(&self, value: #key) -> bool {
                 match value {
                     #(#patterns => #contains_key,)*
                 }
@@ -263,7 +406,7 @@ fn impl_map_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(Ident, TokenS
 
         output.items.extend(quote! {
             #[inline]
-            fn get(&self, value: #ident) -> #option<&V> {
+            fn get(&self, value: #key) -> #option<&V> {
                 match value {
                     #(#patterns => #get,)*
                 }
@@ -283,7 +426,7 @@ fn impl_map_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(Ident, TokenS
 
         output.items.extend(quote! {
             #[inline]
-            fn get_mut(&mut self, value: #ident) -> #option<&mut V> {
+            fn get_mut(&mut self, value: #key) -> #option<&mut V> {
                 match value {
                     #(#patterns => #get_mut,)*
                 }
@@ -296,14 +439,14 @@ fn impl_map_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(Ident, TokenS
             Kind::Complex(Complex { as_map_storage, .. }) => {
                 quote!(#as_map_storage::remove(&mut self.#name, v))
             }
-            Kind::Simple => quote!(#mem::replace(&mut self.#name, #option::None)),
+            Kind::Simple => quote!(#option::take(&mut self.#name)),
         });
 
         let patterns = &fields.patterns;
 
         output.items.extend(quote! {
             #[inline]
-            fn remove(&mut self, value: #ident) -> #option<V> {
+            fn remove(&mut self, value: #key) -> #option<V> {
                 match value {
                     #(#patterns => #remove,)*
                 }
@@ -333,13 +476,46 @@ fn impl_map_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(Ident, TokenS
             #[inline]
             fn retain<F>(&mut self, mut func: F)
             where
-                F: FnMut(#ident, &mut V) -> bool
+                F: FnMut(#key, &mut V) -> bool
             {
                 #(#retain;)*
             }
         });
     }
 
+    {
+        let option_bucket_option = cx.toks.option_bucket_option();
+        let option_bucket_some = cx.toks.option_bucket_some();
+
+        let retain_with_removed = fields.iter().map(
+            |Field {
+                 var, name, kind, ..
+             }| match kind {
+                Kind::Complex(Complex { as_map_storage, .. }) => quote! {
+                    #as_map_storage::retain_with_removed(&mut self.#name, |k, v| keep(#ident::#var(k), v), |k, v| on_remove(#ident::#var(k), v));
+                },
+                Kind::Simple => quote! {
+                    if let #option_bucket_option::Some(mut bucket) = #option_bucket_option::new(&mut self.#name) {
+                        if !keep(#ident::#var, #option_bucket_some::as_mut(&mut bucket)) {
+                            on_remove(#ident::#var, #option_bucket_some::take(bucket));
+                        }
+                    }
+                },
+            },
+        );
+
+        output.items.extend(quote! {
+            #[inline]
+            fn retain_with_removed<F, R>(&mut self, mut keep: F, mut on_remove: R)
+            where
+                F: FnMut(#key, &mut V) -> bool,
+                R: FnMut(#key, V)
+            {
+                #(#retain_with_removed;)*
+            }
+        });
+    }
+
     {
         let clear = fields.iter().map(|Field { name, kind, .. }| match kind {
             Kind::Complex(Complex { as_map_storage, .. }) => quote! {
@@ -358,6 +534,66 @@ fn impl_map_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(Ident, TokenS
         });
     }
 
+    {
+        let reserve = fields
+            .iter()
+            .filter_map(|Field { name, kind, .. }| match kind {
+                Kind::Complex(Complex { as_map_storage, .. }) => Some(quote! {
+                    #as_map_storage::reserve(&mut self.#name, additional)
+                }),
+                Kind::Simple => None,
+            });
+
+        let shrink_to_fit = fields
+            .iter()
+            .filter_map(|Field { name, kind, .. }| match kind {
+                Kind::Complex(Complex { as_map_storage, .. }) => Some(quote! {
+                    #as_map_storage::shrink_to_fit(&mut self.#name)
+                }),
+                Kind::Simple => None,
+            });
+
+        let heap_size = fields
+            .iter()
+            .filter_map(|Field { name, kind, .. }| match kind {
+                Kind::Complex(Complex { as_map_storage, .. }) => Some(quote! {
+                    #as_map_storage::heap_size(&self.#name)
+                }),
+                Kind::Simple => None,
+            });
+
+        let capacity = fields.iter().map(|Field { name, kind, .. }| match kind {
+            Kind::Complex(Complex { as_map_storage, .. }) => quote! {
+                #as_map_storage::capacity(&self.#name)
+            },
+            Kind::Simple => quote! {
+                usize::from(#option::is_some(&self.#name))
+            },
+        });
+
+        output.items.extend(quote! {
+            #[inline]
+            fn reserve(&mut self, additional: usize) {
+                #(#reserve;)*
+            }
+
+            #[inline]
+            fn shrink_to_fit(&mut self) {
+                #(#shrink_to_fit;)*
+            }
+
+            #[inline]
+            fn heap_size(&self) -> usize {
+                0 #(+ #heap_size)*
+            }
+
+            #[inline]
+            fn capacity(&self) -> usize {
+                0 #(+ #capacity)*
+            }
+        });
+    }
+
     let field_decls = fields.iter().map(|Field { name, kind, .. }| match kind {
         Kind::Complex(Complex { map_storage, .. }) => quote!(#name: #map_storage),
         Kind::Simple => quote!(#name: #option<V>),
@@ -366,12 +602,12 @@ fn impl_map_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(Ident, TokenS
     let Output { impls, items } = output;
 
     let map_storage_impl = quote! {
-        #vis struct #type_name<V> {
+        #vis struct #type_name<V, #params> where #predicates {
             #(#field_decls,)*
         }
 
         #[automatically_derived]
-        impl<V> #map_storage_t<#ident, V> for #type_name<V> {
+        impl<V, #params> #map_storage_t<#key, V> for #type_name<V, #args> where #predicates {
             #items
         }
 
@@ -384,6 +620,9 @@ fn impl_map_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(Ident, TokenS
 /// Implement `SetStorage` implementation.
 fn impl_set_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(Ident, TokenStream), ()> {
     let vis = &cx.ast.vis;
+    let key = &fields.key;
+    let Generics { params, args, .. } = &fields.generics;
+    let predicates = fields.generics.predicates();
     let ident = &cx.ast.ident;
 
     let mem = cx.toks.mem();
@@ -400,10 +639,14 @@ fn impl_set_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(Ident, TokenS
         let partial_eq_t = cx.toks.partial_eq_t();
         let eq_t = cx.toks.eq_t();
         let names = fields.names();
+        let bounds = fields
+            .generic()
+            .map(|Complex { set_storage, .. }| set_storage)
+            .collect::<Vec<_>>();
 
         output.impls.extend(quote! {
             #[automatically_derived]
-            impl #partial_eq_t for #type_name {
+            impl<#params> #partial_eq_t for #type_name<#args> where #predicates #(#bounds: #partial_eq_t,)* {
                 #[inline]
                 fn eq(&self, other: &Self) -> bool {
                     #(if #partial_eq_t::ne(&self.#names, &other.#names) {
@@ -415,7 +658,7 @@ fn impl_set_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(Ident, TokenS
             }
 
             #[automatically_derived]
-            impl #eq_t for #type_name  {}
+            impl<#params> #eq_t for #type_name<#args> where #predicates #(#bounds: #eq_t,)* {}
         });
     }
 
@@ -426,21 +669,26 @@ fn impl_set_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(Ident, TokenS
             .complex()
             .map(|Complex { set_storage, .. }| set_storage)
             .collect::<Vec<_>>();
-        let names = fields.names();
+        let names = fields.names().collect::<Vec<_>>();
 
         output.impls.extend(quote! {
             #[automatically_derived]
-            impl #clone_t for #type_name where #(for<'trivial_bounds> #bounds: #clone_t,)* {
+            impl<#params> #clone_t for #type_name<#args> where #predicates #(for<'trivial_bounds> #bounds: #clone_t,)* {
                 #[inline]
                 fn clone(&self) -> Self {
                     Self {
                         #(#names: #clone_t::clone(&self.#names),)*
                     }
                 }
+
+                #[inline]
+                fn clone_from(&mut self, source: &Self) {
+                    #(#clone_t::clone_from(&mut self.#names, &source.#names);)*
+                }
             }
 
             #[automatically_derived]
-            impl #copy_t for #type_name where #(for<'trivial_bounds> #bounds: #copy_t,)* {}
+            impl<#params> #copy_t for #type_name<#args> where #predicates #(for<'trivial_bounds> #bounds: #copy_t,)* {}
         });
     }
 
@@ -474,7 +722,7 @@ fn impl_set_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(Ident, TokenS
 
         output.items.extend(quote! {
             #[inline]
-            fn insert(&mut self, key: #ident) -> bool {
+            fn insert(&mut self, key: #key) -> bool {
                 match key {
                     #(#patterns => #insert,)*
                 }
@@ -526,7 +774,7 @@ fn impl_set_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(Ident, TokenS
 
         output.items.extend(quote! {
             #[inline]
-            fn contains(&self, value: #ident) -> bool {
+            fn contains(&self, value: #key) -> bool {
                 match value {
                     #(#patterns => #contains,)*
                 }
@@ -546,7 +794,7 @@ fn impl_set_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(Ident, TokenS
 
         output.items.extend(quote! {
             #[inline]
-            fn remove(&mut self, value: #ident) -> bool {
+            fn remove(&mut self, value: #key) -> bool {
                 match value {
                     #(#patterns => #remove,)*
                 }
@@ -574,7 +822,7 @@ fn impl_set_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(Ident, TokenS
             #[inline]
             fn retain<F>(&mut self, mut func: F)
             where
-                F: FnMut(#ident) -> bool
+                F: FnMut(#key) -> bool
             {
                 #(#retain;)*
             }
@@ -607,12 +855,12 @@ fn impl_set_storage(cx: &Ctxt<'_>, fields: &Fields<'_>) -> Result<(Ident, TokenS
     let Output { impls, items } = output;
 
     let map_storage_impl = quote! {
-        #vis struct #type_name {
+        #vis struct #type_name<#params> where #predicates {
             #(#field_decls,)*
         }
 
         #[automatically_derived]
-        impl #set_storage_t<#ident> for #type_name {
+        impl<#params> #set_storage_t<#key> for #type_name<#args> where #predicates {
             #items
         }
 
@@ -663,7 +911,11 @@ fn build_iter_next(
                     }
                 });
             }
-            Kind::Complex(Complex { as_map_storage, .. }) => {
+            Kind::Complex(Complex {
+                as_map_storage,
+                generic,
+                ..
+            }) => {
                 step_forward.next.push(quote! {
                     #index => {
                         if let #option::Some((key, value)) = #iterator_t::next(&mut self.#name) {
@@ -680,8 +932,6 @@ fn build_iter_next(
                     }
                 });
 
-                // NB: The `Item = ..` component of the bound is technically
-                // superflous but currently necessary to satisfy rustc.
                 let where_clause = step_backward.make_where_clause();
 
                 let assoc_type = if let Some(lt) = lt {
@@ -690,9 +940,13 @@ fn build_iter_next(
                     quote!(#as_map_storage::#assoc_type)
                 };
 
-                where_clause.predicates.push(cx.fallible(|| syn::parse2(quote_spanned! {
-                    *span => #assoc_type: #double_ended_iterator_t<Item = <#assoc_type as #iterator_t>::Item>
-                }))?);
+                let double_ended_bound = double_ended_bound(cx, &assoc_type, *generic);
+
+                where_clause.predicates.push(cx.fallible(|| {
+                    syn::parse2(quote_spanned! {
+                        *span => #assoc_type: #double_ended_bound
+                    })
+                })?);
             }
         }
     }
@@ -700,6 +954,32 @@ fn build_iter_next(
     Ok(())
 }
 
+/// Construct the `DoubleEndedIterator` bound for the iterator of a field.
+///
+/// The `Item = ..` component of the bound is technically superflous but
+/// currently necessary to satisfy rustc when the iterator is a concrete type.
+/// It can't be used for iterators which depend on a generic parameter, since
+/// rustc overflows trying to normalize it.
+fn double_ended_bound(cx: &Ctxt<'_>, assoc_type: &TokenStream, generic: bool) -> TokenStream {
+    let double_ended_iterator_t = cx.toks.double_ended_iterator_t();
+
+    if generic {
+        return quote!(#double_ended_iterator_t);
+    }
+
+    let iterator_t = cx.toks.iterator_t();
+    quote!(#double_ended_iterator_t<Item = <#assoc_type as #iterator_t>::Item>)
+}
+
+/// Test if the given token stream mentions any of the given identifiers.
+fn mentions_any(tokens: TokenStream, idents: &[Ident]) -> bool {
+    tokens.into_iter().any(|tt| match tt {
+        proc_macro2::TokenTree::Ident(ident) => idents.contains(&ident),
+        proc_macro2::TokenTree::Group(group) => mentions_any(group.stream(), idents),
+        _ => false,
+    })
+}
+
 /// Construct an iterator implementation.
 fn map_storage_iter(
     cx: &Ctxt<'_>,
@@ -711,8 +991,10 @@ fn map_storage_iter(
     let assoc_type = Ident::new(assoc_type, Span::call_site());
 
     let lt = cx.lt;
-    let ident = &cx.ast.ident;
     let vis = &cx.ast.vis;
+    let key = &fields.key;
+    let Generics { params, args, .. } = &fields.generics;
+    let lt_predicates = fields.generics.lt_predicates(lt);
 
     let option = cx.toks.option();
     let iterator_t = cx.toks.iterator_t();
@@ -752,18 +1034,25 @@ fn map_storage_iter(
         .predicates
         .push(cx.fallible(|| syn::parse2(quote!(V: #lt)))?);
 
+    fields
+        .generics
+        .extend_where_clause(cx, step_backward.make_where_clause(), Some(lt))?;
+
     let double_ended_where_clause = &step_backward.where_clause;
     let names = fields.names();
+    let clone_bounds = fields
+        .generic()
+        .map(|Complex { as_map_storage, .. }| quote!(#as_map_storage::#assoc_type<#lt>: #clone_t));
 
     output.impls.extend(quote! {
-        #vis struct #type_name<#lt, V> where V: #lt {
+        #vis struct #type_name<#lt, V, #params> where V: #lt, #lt_predicates {
             start: usize,
             end: usize,
             #(#field_decls,)*
         }
 
         #[automatically_derived]
-        impl<#lt, V> #clone_t for #type_name<#lt, V> where V: #lt {
+        impl<#lt, V, #params> #clone_t for #type_name<#lt, V, #args> where V: #lt, #lt_predicates #(#clone_bounds,)* {
             #[inline]
             fn clone(&self) -> Self {
                 Self {
@@ -775,8 +1064,8 @@ fn map_storage_iter(
         }
 
         #[automatically_derived]
-        impl<#lt, V> #iterator_t for #type_name<#lt, V> where V: #lt {
-            type Item = (#ident, &#lt V);
+        impl<#lt, V, #params> #iterator_t for #type_name<#lt, V, #args> where V: #lt, #lt_predicates {
+            type Item = (#key, &#lt V);
 
             #[inline]
             fn next(&mut self) -> #option<Self::Item> {
@@ -786,7 +1075,7 @@ fn map_storage_iter(
         }
 
         #[automatically_derived]
-        impl<#lt, V> #double_ended_iterator_t for #type_name<#lt, V> #double_ended_where_clause {
+        impl<#lt, V, #params> #double_ended_iterator_t for #type_name<#lt, V, #args> #double_ended_where_clause {
             #[inline]
             fn next_back(&mut self) -> #option<Self::Item> {
                 #step_backward
@@ -797,12 +1086,17 @@ fn map_storage_iter(
 
     let end = fields.len();
 
+    let exact_size = cx.toks.exact_size();
+    let map_storage_t = cx.toks.map_storage_t();
+    let key = &fields.key;
+
     output.items.extend(quote! {
-        type #assoc_type<#lt> = #type_name<#lt, V> where V: #lt;
+        type #assoc_type<#lt> = #exact_size<#type_name<#lt, V, #args>> where Self: #lt;
 
         #[inline]
         fn iter(&self) -> Self::#assoc_type<'_> {
-            #type_name { start: 0, end: #end, #(#init,)* }
+            let len = <Self as #map_storage_t<#key, V>>::len(self);
+            #exact_size::new(#type_name { start: 0, end: #end, #(#init,)* }, len)
         }
     });
 
@@ -822,6 +1116,9 @@ fn map_storage_keys(
     let lt = cx.lt;
     let ident = &cx.ast.ident;
     let vis = &cx.ast.vis;
+    let key = &fields.key;
+    let Generics { params, args, .. } = &fields.generics;
+    let lt_predicates = fields.generics.lt_predicates(lt);
 
     let bool_type = cx.toks.bool_type();
     let clone_t = cx.toks.clone_t();
@@ -866,7 +1163,11 @@ fn map_storage_keys(
                     }
                 });
             }
-            Kind::Complex(Complex { as_map_storage, .. }) => {
+            Kind::Complex(Complex {
+                as_map_storage,
+                generic,
+                ..
+            }) => {
                 field_decls.push(quote!(#name: #as_map_storage::#assoc_type<#lt>));
                 init.push(quote!(#name: #as_map_storage::keys(&self.#name)));
 
@@ -890,9 +1191,13 @@ fn map_storage_keys(
 
                 let assoc_type = quote!(#as_map_storage::#assoc_type<#lt>);
 
-                where_clause.predicates.push(cx.fallible(|| syn::parse2(quote_spanned! {
-                    *span => #assoc_type: #double_ended_iterator_t<Item = <#assoc_type as #iterator_t>::Item>
-                }))?);
+                let double_ended_bound = double_ended_bound(cx, &assoc_type, *generic);
+
+                where_clause.predicates.push(cx.fallible(|| {
+                    syn::parse2(quote_spanned! {
+                        *span => #assoc_type: #double_ended_bound
+                    })
+                })?);
             }
         }
     }
@@ -902,18 +1207,25 @@ fn map_storage_keys(
         .predicates
         .push(cx.fallible(|| syn::parse2(quote!(V: #lt)))?);
 
+    fields
+        .generics
+        .extend_where_clause(cx, step_backward.make_where_clause(), Some(lt))?;
+
     let double_ended_where_clause = &step_backward.where_clause;
     let names = fields.names();
+    let clone_bounds = fields
+        .generic()
+        .map(|Complex { as_map_storage, .. }| quote!(#as_map_storage::#assoc_type<#lt>: #clone_t));
 
     output.impls.extend(quote! {
-        #vis struct #type_name<#lt, V> where V: #lt {
+        #vis struct #type_name<#lt, V, #params> where V: #lt, #lt_predicates {
             start: usize,
             end: usize,
             #(#field_decls,)*
         }
 
         #[automatically_derived]
-        impl<#lt, V> #clone_t for #type_name<#lt, V> where V: #lt {
+        impl<#lt, V, #params> #clone_t for #type_name<#lt, V, #args> where V: #lt, #lt_predicates #(#clone_bounds,)* {
             #[inline]
             fn clone(&self) -> Self {
                 Self {
@@ -925,8 +1237,8 @@ fn map_storage_keys(
         }
 
         #[automatically_derived]
-        impl<#lt, V> #iterator_t for #type_name<#lt, V> where V: #lt {
-            type Item = #ident;
+        impl<#lt, V, #params> #iterator_t for #type_name<#lt, V, #args> where V: #lt, #lt_predicates {
+            type Item = #key;
 
             #[inline]
             fn next(&mut self) -> #option<Self::Item> {
@@ -936,7 +1248,7 @@ fn map_storage_keys(
         }
 
         #[automatically_derived]
-        impl<#lt, V> #double_ended_iterator_t for #type_name<#lt, V> #double_ended_where_clause {
+        impl<#lt, V, #params> #double_ended_iterator_t for #type_name<#lt, V, #args> #double_ended_where_clause {
             #[inline]
             fn next_back(&mut self) -> #option<Self::Item> {
                 #step_backward
@@ -947,12 +1259,17 @@ fn map_storage_keys(
 
     let end = fields.len();
 
+    let exact_size = cx.toks.exact_size();
+    let map_storage_t = cx.toks.map_storage_t();
+    let key = &fields.key;
+
     output.items.extend(quote! {
-        type #assoc_type<#lt> = #type_name<#lt, V> where V: #lt;
+        type #assoc_type<#lt> = #exact_size<#type_name<#lt, V, #args>> where Self: #lt;
 
         #[inline]
         fn keys(&self) -> Self::#assoc_type<'_> {
-            #type_name { start: 0, end: #end, #(#init,)* }
+            let len = <Self as #map_storage_t<#key, V>>::len(self);
+            #exact_size::new(#type_name { start: 0, end: #end, #(#init,)* }, len)
         }
     });
 
@@ -971,6 +1288,8 @@ fn map_storage_values(
 
     let lt = cx.lt;
     let vis = &cx.ast.vis;
+    let Generics { params, args, .. } = &fields.generics;
+    let lt_predicates = fields.generics.lt_predicates(lt);
 
     let clone_t = cx.toks.clone_t();
     let double_ended_iterator_t = cx.toks.double_ended_iterator_t();
@@ -1012,7 +1331,11 @@ fn map_storage_values(
                     }
                 });
             }
-            Kind::Complex(Complex { as_map_storage, .. }) => {
+            Kind::Complex(Complex {
+                as_map_storage,
+                generic,
+                ..
+            }) => {
                 field_decls.push(quote!(#name: #as_map_storage::#assoc_type<#lt>));
                 init.push(quote!(#name: #as_map_storage::values(&self.#name)));
 
@@ -1036,9 +1359,13 @@ fn map_storage_values(
 
                 let assoc_type = quote!(#as_map_storage::#assoc_type<#lt>);
 
-                where_clause.predicates.push(cx.fallible(|| syn::parse2(quote_spanned! {
-                    *span => #assoc_type: #double_ended_iterator_t<Item = <#assoc_type as #iterator_t>::Item>
-                }))?);
+                let double_ended_bound = double_ended_bound(cx, &assoc_type, *generic);
+
+                where_clause.predicates.push(cx.fallible(|| {
+                    syn::parse2(quote_spanned! {
+                        *span => #assoc_type: #double_ended_bound
+                    })
+                })?);
             }
         }
     }
@@ -1048,18 +1375,25 @@ fn map_storage_values(
         .predicates
         .push(cx.fallible(|| syn::parse2(quote!(V: #lt)))?);
 
+    fields
+        .generics
+        .extend_where_clause(cx, step_backward.make_where_clause(), Some(lt))?;
+
     let double_ended_where_clause = &step_backward.where_clause;
     let names = fields.names();
+    let clone_bounds = fields
+        .generic()
+        .map(|Complex { as_map_storage, .. }| quote!(#as_map_storage::#assoc_type<#lt>: #clone_t));
 
     output.impls.extend(quote! {
-        #vis struct #type_name<#lt, V> where V: #lt {
+        #vis struct #type_name<#lt, V, #params> where V: #lt, #lt_predicates {
             start: usize,
             end: usize,
             #(#field_decls,)*
         }
 
         #[automatically_derived]
-        impl<#lt, V> #clone_t for #type_name<#lt, V> where V: #lt {
+        impl<#lt, V, #params> #clone_t for #type_name<#lt, V, #args> where V: #lt, #lt_predicates #(#clone_bounds,)* {
             #[inline]
             fn clone(&self) -> Self {
                 Self {
@@ -1071,7 +1405,7 @@ fn map_storage_values(
         }
 
         #[automatically_derived]
-        impl<#lt, V> #iterator_t for #type_name<#lt, V> where V: #lt {
+        impl<#lt, V, #params> #iterator_t for #type_name<#lt, V, #args> where V: #lt, #lt_predicates {
             type Item = &#lt V;
 
             #[inline]
@@ -1082,7 +1416,7 @@ fn map_storage_values(
         }
 
         #[automatically_derived]
-        impl<#lt, V> #double_ended_iterator_t for #type_name<#lt, V> #double_ended_where_clause {
+        impl<#lt, V, #params> #double_ended_iterator_t for #type_name<#lt, V, #args> #double_ended_where_clause {
             #[inline]
             fn next_back(&mut self) -> #option<Self::Item> {
                 #step_backward
@@ -1093,12 +1427,17 @@ fn map_storage_values(
 
     let end = fields.len();
 
+    let exact_size = cx.toks.exact_size();
+    let map_storage_t = cx.toks.map_storage_t();
+    let key = &fields.key;
+
     output.items.extend(quote! {
-        type #assoc_type<#lt> = #type_name<#lt, V> where V: #lt;
+        type #assoc_type<#lt> = #exact_size<#type_name<#lt, V, #args>> where Self: #lt;
 
         #[inline]
         fn values(&self) -> Self::#assoc_type<'_> {
-            #type_name { start: 0, end: #end, #(#init,)* }
+            let len = <Self as #map_storage_t<#key, V>>::len(self);
+            #exact_size::new(#type_name { start: 0, end: #end, #(#init,)* }, len)
         }
     });
 
@@ -1115,9 +1454,11 @@ fn map_storage_iter_mut(
     let type_name = format_ident!("{MAP_STORAGE}{assoc_type}");
     let assoc_type = Ident::new(assoc_type, Span::call_site());
 
-    let ident = &cx.ast.ident;
     let lt = cx.lt;
     let vis = &cx.ast.vis;
+    let key = &fields.key;
+    let Generics { params, args, .. } = &fields.generics;
+    let lt_predicates = fields.generics.lt_predicates(lt);
 
     let double_ended_iterator_t = cx.toks.double_ended_iterator_t();
     let iterator_t = cx.toks.iterator_t();
@@ -1160,18 +1501,22 @@ fn map_storage_iter_mut(
         .predicates
         .push(cx.fallible(|| syn::parse2(quote!(V: #lt)))?);
 
+    fields
+        .generics
+        .extend_where_clause(cx, step_backward.make_where_clause(), Some(lt))?;
+
     let double_ended_where = &step_backward.where_clause;
 
     output.impls.extend(quote! {
-        #vis struct #type_name<#lt, V> where V: #lt {
+        #vis struct #type_name<#lt, V, #params> where V: #lt, #lt_predicates {
             start: usize,
             end: usize,
             #(#field_decls,)*
         }
 
         #[automatically_derived]
-        impl<#lt, V> #iterator_t for #type_name<#lt, V> where V: #lt {
-            type Item = (#ident, &#lt mut V);
+        impl<#lt, V, #params> #iterator_t for #type_name<#lt, V, #args> where V: #lt, #lt_predicates {
+            type Item = (#key, &#lt mut V);
 
             #[inline]
             fn next(&mut self) -> #option<Self::Item> {
@@ -1181,7 +1526,7 @@ fn map_storage_iter_mut(
         }
 
         #[automatically_derived]
-        impl<#lt, V> #double_ended_iterator_t for #type_name<#lt, V> #double_ended_where {
+        impl<#lt, V, #params> #double_ended_iterator_t for #type_name<#lt, V, #args> #double_ended_where {
             #[inline]
             fn next_back(&mut self) -> #option<Self::Item> {
                 #step_backward
@@ -1192,12 +1537,17 @@ fn map_storage_iter_mut(
 
     let end = fields.len();
 
+    let exact_size = cx.toks.exact_size();
+    let map_storage_t = cx.toks.map_storage_t();
+    let key = &fields.key;
+
     output.items.extend(quote! {
-        type #assoc_type<#lt> = #type_name<#lt, V> where V: #lt;
+        type #assoc_type<#lt> = #exact_size<#type_name<#lt, V, #args>> where Self: #lt;
 
         #[inline]
         fn iter_mut(&mut self) -> Self::#assoc_type<'_> {
-            #type_name { start: 0, end: #end, #(#init,)* }
+            let len = <Self as #map_storage_t<#key, V>>::len(self);
+            #exact_size::new(#type_name { start: 0, end: #end, #(#init,)* }, len)
         }
     });
 
@@ -1216,6 +1566,8 @@ fn map_storage_values_mut(
 
     let lt = cx.lt;
     let vis = &cx.ast.vis;
+    let Generics { params, args, .. } = &fields.generics;
+    let lt_predicates = fields.generics.lt_predicates(lt);
 
     let option = cx.toks.option();
     let iterator_t = cx.toks.iterator_t();
@@ -1256,7 +1608,11 @@ fn map_storage_values_mut(
                     }
                 });
             }
-            Kind::Complex(Complex { as_map_storage, .. }) => {
+            Kind::Complex(Complex {
+                as_map_storage,
+                generic,
+                ..
+            }) => {
                 field_decls.push(quote!(#name: #as_map_storage::#assoc_type<#lt>));
                 init.push(quote!(#name: #as_map_storage::values_mut(&mut self.#name)));
 
@@ -1280,9 +1636,13 @@ fn map_storage_values_mut(
 
                 let assoc_type = quote!(#as_map_storage::#assoc_type<#lt>);
 
-                where_clause.predicates.push(cx.fallible(|| syn::parse2(quote_spanned! {
-                    *span => #assoc_type: #double_ended_iterator_t<Item = <#assoc_type as #iterator_t>::Item>
-                }))?);
+                let double_ended_bound = double_ended_bound(cx, &assoc_type, *generic);
+
+                where_clause.predicates.push(cx.fallible(|| {
+                    syn::parse2(quote_spanned! {
+                        *span => #assoc_type: #double_ended_bound
+                    })
+                })?);
             }
         }
     }
@@ -1292,17 +1652,21 @@ fn map_storage_values_mut(
         .predicates
         .push(cx.fallible(|| syn::parse2(quote!(V: #lt)))?);
 
+    fields
+        .generics
+        .extend_where_clause(cx, step_backward.make_where_clause(), Some(lt))?;
+
     let double_ended_where_clause = &step_backward.where_clause;
 
     output.impls.extend(quote! {
-        #vis struct #type_name<#lt, V> where V: #lt {
+        #vis struct #type_name<#lt, V, #params> where V: #lt, #lt_predicates {
             start: usize,
             end: usize,
             #(#field_decls,)*
         }
 
         #[automatically_derived]
-        impl<#lt, V> #iterator_t for #type_name<#lt, V> where V: #lt {
+        impl<#lt, V, #params> #iterator_t for #type_name<#lt, V, #args> where V: #lt, #lt_predicates {
             type Item = &#lt mut V;
 
             #[inline]
@@ -1313,7 +1677,7 @@ fn map_storage_values_mut(
         }
 
         #[automatically_derived]
-        impl<#lt, V> #double_ended_iterator_t for #type_name<#lt, V> #double_ended_where_clause {
+        impl<#lt, V, #params> #double_ended_iterator_t for #type_name<#lt, V, #args> #double_ended_where_clause {
             #[inline]
             fn next_back(&mut self) -> #option<Self::Item> {
                 #step_backward
@@ -1324,12 +1688,17 @@ fn map_storage_values_mut(
 
     let end = fields.len();
 
+    let exact_size = cx.toks.exact_size();
+    let map_storage_t = cx.toks.map_storage_t();
+    let key = &fields.key;
+
     output.items.extend(quote! {
-        type #assoc_type<#lt> = #type_name<#lt, V> where V: #lt;
+        type #assoc_type<#lt> = #exact_size<#type_name<#lt, V, #args>> where Self: #lt;
 
         #[inline]
         fn values_mut(&mut self) -> Self::#assoc_type<'_> {
-            #type_name { start: 0, end: #end, #(#init,)* }
+            let len = <Self as #map_storage_t<#key, V>>::len(self);
+            #exact_size::new(#type_name { start: 0, end: #end, #(#init,)* }, len)
         }
     });
 
@@ -1346,8 +1715,10 @@ fn map_storage_into_iter(
     let type_name = format_ident!("{MAP_STORAGE}{assoc_type}");
     let assoc_type = Ident::new(assoc_type, Span::call_site());
 
-    let ident = &cx.ast.ident;
     let vis = &cx.ast.vis;
+    let key = &fields.key;
+    let Generics { params, args, .. } = &fields.generics;
+    let predicates = fields.generics.predicates();
 
     let option = cx.toks.option();
     let clone_t = cx.toks.clone_t();
@@ -1386,6 +1757,10 @@ fn map_storage_into_iter(
         }
     }
 
+    fields
+        .generics
+        .extend_where_clause(cx, step_backward.make_where_clause(), None)?;
+
     let double_ended_where = &step_backward.where_clause;
     let names = fields.names();
     let clone_bounds = fields
@@ -1393,14 +1768,14 @@ fn map_storage_into_iter(
         .map(|Complex { as_map_storage, .. }| quote!(#as_map_storage::#assoc_type: #clone_t));
 
     output.impls.extend(quote! {
-        #vis struct #type_name<V> {
+        #vis struct #type_name<V, #params> where #predicates {
             start: usize,
             end: usize,
             #(#field_decls,)*
         }
 
         #[automatically_derived]
-        impl<V> #clone_t for #type_name<V> where V: Clone, #(#clone_bounds,)* {
+        impl<V, #params> #clone_t for #type_name<V, #args> where #predicates V: Clone, #(#clone_bounds,)* {
             #[inline]
             fn clone(&self) -> Self {
                 Self {
@@ -1412,8 +1787,8 @@ fn map_storage_into_iter(
         }
 
         #[automatically_derived]
-        impl<V> #iterator_t for #type_name<V> {
-            type Item = (#ident, V);
+        impl<V, #params> #iterator_t for #type_name<V, #args> where #predicates {
+            type Item = (#key, V);
 
             #[inline]
             fn next(&mut self) -> Option<Self::Item> {
@@ -1423,7 +1798,7 @@ fn map_storage_into_iter(
         }
 
         #[automatically_derived]
-        impl<V> #double_ended_iterator_t for #type_name<V> #double_ended_where {
+        impl<V, #params> #double_ended_iterator_t for #type_name<V, #args> #double_ended_where {
             #[inline]
             fn next_back(&mut self) -> #option<Self::Item> {
                 #step_backward
@@ -1434,12 +1809,17 @@ fn map_storage_into_iter(
 
     let end = fields.len();
 
+    let exact_size = cx.toks.exact_size();
+    let map_storage_t = cx.toks.map_storage_t();
+    let key = &fields.key;
+
     output.items.extend(quote! {
-        type #assoc_type = #type_name<V>;
+        type #assoc_type = #exact_size<#type_name<V, #args>>;
 
         #[inline]
         fn into_iter(self) -> Self::#assoc_type {
-            #type_name { start: 0, end: #end, #(#init,)* }
+            let len = <Self as #map_storage_t<#key, V>>::len(&self);
+            #exact_size::new(#type_name { start: 0, end: #end, #(#init,)* }, len)
         }
     });
 
@@ -1459,6 +1839,9 @@ fn set_storage_iter(
     let lt = cx.lt;
     let ident = &cx.ast.ident;
     let vis = &cx.ast.vis;
+    let key = &fields.key;
+    let Generics { params, args, .. } = &fields.generics;
+    let lt_predicates = fields.generics.lt_predicates(lt);
 
     let bool_type = cx.toks.bool_type();
     let clone_t = cx.toks.clone_t();
@@ -1503,7 +1886,11 @@ fn set_storage_iter(
                     }
                 });
             }
-            Kind::Complex(Complex { as_set_storage, .. }) => {
+            Kind::Complex(Complex {
+                as_set_storage,
+                generic,
+                ..
+            }) => {
                 field_decls.push(quote!(#name: #as_set_storage::#assoc_type<#lt>));
                 init.push(quote!(#name: #as_set_storage::iter(&self.#name)));
 
@@ -1527,25 +1914,36 @@ fn set_storage_iter(
 
                 let assoc_type = quote!(#as_set_storage::#assoc_type<#lt>);
 
-                where_clause.predicates.push(cx.fallible(|| syn::parse2(quote_spanned! {
-                    *span => #assoc_type: #double_ended_iterator_t<Item = <#assoc_type as #iterator_t>::Item>
-                }))?);
+                let double_ended_bound = double_ended_bound(cx, &assoc_type, *generic);
+
+                where_clause.predicates.push(cx.fallible(|| {
+                    syn::parse2(quote_spanned! {
+                        *span => #assoc_type: #double_ended_bound
+                    })
+                })?);
             }
         }
     }
 
+    fields
+        .generics
+        .extend_where_clause(cx, step_backward.make_where_clause(), Some(lt))?;
+
     let double_ended_where_clause = &step_backward.where_clause;
     let names = fields.names();
+    let clone_bounds = fields
+        .generic()
+        .map(|Complex { as_set_storage, .. }| quote!(#as_set_storage::#assoc_type<#lt>: #clone_t));
 
     output.impls.extend(quote! {
-        #vis struct #type_name<#lt> {
+        #vis struct #type_name<#lt, #params> where #lt_predicates {
             start: usize,
             end: usize,
             #(#field_decls,)*
         }
 
         #[automatically_derived]
-        impl<#lt> #clone_t for #type_name<#lt> {
+        impl<#lt, #params> #clone_t for #type_name<#lt, #args> where #lt_predicates #(#clone_bounds,)* {
             #[inline]
             fn clone(&self) -> Self {
                 Self {
@@ -1557,8 +1955,8 @@ fn set_storage_iter(
         }
 
         #[automatically_derived]
-        impl<#lt> #iterator_t for #type_name<#lt> {
-            type Item = #ident;
+        impl<#lt, #params> #iterator_t for #type_name<#lt, #args> where #lt_predicates {
+            type Item = #key;
 
             #[inline]
             fn next(&mut self) -> #option<Self::Item> {
@@ -1568,7 +1966,7 @@ fn set_storage_iter(
         }
 
         #[automatically_derived]
-        impl<#lt> #double_ended_iterator_t for #type_name<#lt> #double_ended_where_clause {
+        impl<#lt, #params> #double_ended_iterator_t for #type_name<#lt, #args> #double_ended_where_clause {
             #[inline]
             fn next_back(&mut self) -> #option<Self::Item> {
                 #step_backward
@@ -1579,12 +1977,17 @@ fn set_storage_iter(
 
     let end = fields.len();
 
+    let exact_size = cx.toks.exact_size();
+    let set_storage_t = cx.toks.set_storage_t();
+    let key = &fields.key;
+
     output.items.extend(quote! {
-        type #assoc_type<#lt> = #type_name<#lt>;
+        type #assoc_type<#lt> = #exact_size<#type_name<#lt, #args>> where Self: #lt;
 
         #[inline]
         fn iter(&self) -> Self::#assoc_type<'_> {
-            #type_name { start: 0, end: #end, #(#init,)* }
+            let len = <Self as #set_storage_t<#key>>::len(self);
+            #exact_size::new(#type_name { start: 0, end: #end, #(#init,)* }, len)
         }
     });
 
@@ -1603,6 +2006,9 @@ fn set_storage_into_iter(
 
     let ident = &cx.ast.ident;
     let vis = &cx.ast.vis;
+    let key = &fields.key;
+    let Generics { params, args, .. } = &fields.generics;
+    let predicates = fields.generics.predicates();
 
     let bool_type = cx.toks.bool_type();
     let clone_t = cx.toks.clone_t();
@@ -1647,7 +2053,11 @@ fn set_storage_into_iter(
                     }
                 });
             }
-            Kind::Complex(Complex { as_set_storage, .. }) => {
+            Kind::Complex(Complex {
+                as_set_storage,
+                generic,
+                ..
+            }) => {
                 field_decls.push(quote!(#name: #as_set_storage::#assoc_type));
                 init.push(quote!(#name: #as_set_storage::into_iter(self.#name)));
 
@@ -1671,9 +2081,13 @@ fn set_storage_into_iter(
 
                 let assoc_type = quote!(#as_set_storage::#assoc_type);
 
-                where_clause.predicates.push(cx.fallible(|| syn::parse2(quote_spanned! {
-                    *span => for<'trivial_bounds> #assoc_type: #double_ended_iterator_t<Item = <#assoc_type as #iterator_t>::Item>
-                }))?);
+                let double_ended_bound = double_ended_bound(cx, &assoc_type, *generic);
+
+                where_clause.predicates.push(cx.fallible(|| {
+                    syn::parse2(quote_spanned! {
+                        *span => for<'trivial_bounds> #assoc_type: #double_ended_bound
+                    })
+                })?);
             }
         }
     }
@@ -1681,7 +2095,7 @@ fn set_storage_into_iter(
     let names = fields.names();
 
     output.impls.extend(quote! {
-        #vis struct #type_name {
+        #vis struct #type_name<#params> where #predicates {
             start: usize,
             end: usize,
             #(#field_decls,)*
@@ -1695,7 +2109,7 @@ fn set_storage_into_iter(
 
         output.impls.extend(quote! {
             #[automatically_derived]
-            impl #clone_t for #type_name where #(for<'trivial_bounds> #bounds: #clone_t,)* {
+            impl<#params> #clone_t for #type_name<#args> where #predicates #(for<'trivial_bounds> #bounds: #clone_t,)* {
                 #[inline]
                 fn clone(&self) -> Self {
                     Self {
@@ -1710,8 +2124,8 @@ fn set_storage_into_iter(
 
     output.impls.extend(quote! {
         #[automatically_derived]
-        impl #iterator_t for #type_name {
-            type Item = #ident;
+        impl<#params> #iterator_t for #type_name<#args> where #predicates {
+            type Item = #key;
 
             #[inline]
             fn next(&mut self) -> #option<Self::Item> {
@@ -1721,11 +2135,15 @@ fn set_storage_into_iter(
         }
     });
 
+    fields
+        .generics
+        .extend_where_clause(cx, step_backward.make_where_clause(), None)?;
+
     let double_ended_where_clause = &step_backward.where_clause;
 
     output.impls.extend(quote! {
         #[automatically_derived]
-        impl #double_ended_iterator_t for #type_name #double_ended_where_clause {
+        impl<#params> #double_ended_iterator_t for #type_name<#args> #double_ended_where_clause {
             #[inline]
             fn next_back(&mut self) -> #option<Self::Item> {
                 #step_backward
@@ -1736,12 +2154,17 @@ fn set_storage_into_iter(
 
     let end = fields.len();
 
+    let exact_size = cx.toks.exact_size();
+    let set_storage_t = cx.toks.set_storage_t();
+    let key = &fields.key;
+
     output.items.extend(quote! {
-        type #assoc_type = #type_name;
+        type #assoc_type = #exact_size<#type_name<#args>>;
 
         #[inline]
         fn into_iter(self) -> Self::#assoc_type {
-            #type_name { start: 0, end: #end, #(#init,)* }
+            let len = <Self as #set_storage_t<#key>>::len(&self);
+            #exact_size::new(#type_name { start: 0, end: #end, #(#init,)* }, len)
         }
     });
 
@@ -1815,7 +2238,12 @@ fn map_storage_entry(
     let ident = &cx.ast.ident;
     let vis = &cx.ast.vis;
     let lt = cx.lt;
+    let key = &fields.key;
+    let Generics { params, args, .. } = &fields.generics;
+    let predicates = fields.generics.predicates();
+    let lt_predicates = fields.generics.lt_predicates(lt);
 
+    let clone_t = cx.toks.clone_t();
     let entry_enum = cx.toks.entry_enum();
     let occupied_entry_t = cx.toks.occupied_entry_t();
     let option = cx.toks.option();
@@ -1830,6 +2258,7 @@ fn map_storage_entry(
     let mut vacant_variant = Vec::new();
 
     let mut vacant_key = Vec::new();
+    let mut vacant_into_key = Vec::new();
     let mut vacant_insert = Vec::new();
 
     let mut occupied_key = Vec::new();
@@ -1838,6 +2267,7 @@ fn map_storage_entry(
     let mut occupied_into_mut = Vec::new();
     let mut occupied_insert = Vec::new();
     let mut occupied_remove = Vec::new();
+    let mut occupied_replace_key = Vec::new();
 
     for Field {
         name, kind, var, ..
@@ -1872,6 +2302,9 @@ fn map_storage_entry(
                 vacant_key.push(
                     quote!( VacantEntry::#name(entry) => #pattern(#as_vacant_entry::key(entry)) ),
                 );
+                vacant_into_key.push(
+                    quote!( VacantEntry::#name(entry) => #pattern(#as_vacant_entry::into_key(entry)) ),
+                );
                 vacant_insert.push(
                     quote!( VacantEntry::#name(entry) => #as_vacant_entry::insert(entry, value) ),
                 );
@@ -1892,29 +2325,30 @@ fn map_storage_entry(
                 occupied_remove.push(
                     quote!( OccupiedEntry::#name(entry) => #as_occupied_entry::remove(entry) ),
                 );
+                occupied_replace_key.push(quote!( (OccupiedEntry::#name(entry), #pattern(key)) => #pattern(#as_occupied_entry::replace_key(entry, key)) ));
             }
         }
     }
 
     output.impls.extend(quote! {
-        #vis struct SimpleVacantEntry<#lt, V> {
-            key: #ident,
+        #vis struct SimpleVacantEntry<#lt, V, #params> where #predicates {
+            key: #key,
             inner: #option_bucket_none<#lt, V>,
         }
 
-        impl<#lt, V> SimpleVacantEntry<#lt, V> {
+        impl<#lt, V, #params> SimpleVacantEntry<#lt, V, #args> where #predicates {
             #[inline]
             fn insert(self, value: V) -> &#lt mut V {
                 #option_bucket_none::insert(self.inner, value)
             }
         }
 
-        #vis struct SimpleOccupiedEntry<#lt, V> {
-            key: #ident,
+        #vis struct SimpleOccupiedEntry<#lt, V, #params> where #predicates {
+            key: #key,
             inner: #option_bucket_some<#lt, V>,
         }
 
-        impl<#lt, V> SimpleOccupiedEntry<#lt, V> {
+        impl<#lt, V, #params> SimpleOccupiedEntry<#lt, V, #args> where #predicates {
             #[inline]
             fn get(&self) -> &V {
                 #option_bucket_some::as_ref(&self.inner)
@@ -1941,26 +2375,34 @@ fn map_storage_entry(
             }
         }
 
-        #vis enum VacantEntry<#lt, V> {
-            Simple(SimpleVacantEntry<#lt, V>),
+        #vis enum VacantEntry<#lt, V, #params> where V: #lt, #lt_predicates {
+            Simple(SimpleVacantEntry<#lt, V, #args>),
             #(#vacant_variant,)*
         }
 
-        #vis enum OccupiedEntry<#lt, V> {
-            Simple(SimpleOccupiedEntry<#lt, V>),
+        #vis enum OccupiedEntry<#lt, V, #params> where V: #lt, #lt_predicates {
+            Simple(SimpleOccupiedEntry<#lt, V, #args>),
             #(#occupied_variant,)*
         }
 
         #[automatically_derived]
-        impl<#lt, V> #vacant_entry_t<#lt, #ident, V> for VacantEntry<#lt, V> {
+        impl<#lt, V, #params> #vacant_entry_t<#lt, #key, V> for VacantEntry<#lt, V, #args> where V: #lt, #lt_predicates {
             #[inline]
-            fn key(&self) -> #ident {
+            fn key(&self) -> #key {
                 match self {
-                    VacantEntry::Simple(entry) => entry.key,
+                    VacantEntry::Simple(entry) => #clone_t::clone(&entry.key),
                     #(#vacant_key,)*
                 }
             }
 
+            #[inline]
+            fn into_key(self) -> #key {
+                match self {
+                    VacantEntry::Simple(entry) => entry.key,
+                    #(#vacant_into_key,)*
+                }
+            }
+
             #[inline]
             fn insert(self, value: V) -> &#lt mut V {
                 match self {
@@ -1971,11 +2413,11 @@ fn map_storage_entry(
         }
 
         #[automatically_derived]
-        impl<#lt, V> #occupied_entry_t<#lt, #ident, V> for OccupiedEntry<#lt, V> {
+        impl<#lt, V, #params> #occupied_entry_t<#lt, #key, V> for OccupiedEntry<#lt, V, #args> where V: #lt, #lt_predicates {
             #[inline]
-            fn key(&self) -> #ident {
+            fn key(&self) -> #key {
                 match self {
-                    OccupiedEntry::Simple(entry) => entry.key,
+                    OccupiedEntry::Simple(entry) => #clone_t::clone(&entry.key),
                     #(#occupied_key,)*
                 }
             }
@@ -2019,10 +2461,18 @@ fn map_storage_entry(
                     #(#occupied_remove,)*
                 }
             }
+
+            #[inline]
+            fn replace_key(self, key: #key) -> #key {
+                match (self, key) {
+                    #(#occupied_replace_key,)*
+                    (_, key) => key,
+                }
+            }
         }
 
         #[inline]
-        fn option_to_entry<V>(opt: &mut #option<V>, key: #ident) -> #entry_enum<'_, #map_storage<V>, #ident, V> {
+        fn option_to_entry<V, #params>(opt: &mut #option<V>, key: #key) -> #entry_enum<'_, #map_storage<V, #args>, #key, V> where #predicates {
             match #option_bucket_option::new(opt) {
                 #option_bucket_option::Some(inner) => #entry_enum::Occupied(OccupiedEntry::Simple(SimpleOccupiedEntry { key, inner })),
                 #option_bucket_option::None(inner) => #entry_enum::Vacant(VacantEntry::Simple(SimpleVacantEntry { key, inner })),
@@ -2031,11 +2481,11 @@ fn map_storage_entry(
     });
 
     output.items.extend(quote! {
-        type Occupied<#lt> = OccupiedEntry<#lt, V> where V: #lt;
-        type Vacant<#lt> = VacantEntry<#lt, V> where V: #lt;
+        type Occupied<#lt> = OccupiedEntry<#lt, V, #args> where Self: #lt;
+        type Vacant<#lt> = VacantEntry<#lt, V, #args> where Self: #lt;
 
         #[inline]
-        fn entry(&mut self, key: #ident) -> #entry_enum<'_, Self, #ident, V> {
+        fn entry(&mut self, key: #key) -> #entry_enum<'_, Self, #key, V> {
             match key {
                 #(#init,)*
             }
@@ -2073,20 +2523,78 @@ pub(crate) enum Kind<'a> {
 pub(crate) struct Complex<'a> {
     /// Type of variant field
     pub(crate) element: &'a syn::Field,
-    /// <E as Key>::MapStorage::<V> (E = type of variant field)
+    /// `<E as Key>::MapStorage::<V>` (E = type of variant field), or
+    /// `BTreeMapStorage::<E, V>` if the variant is `#[key(ordered)]`.
     pub(crate) map_storage: TokenStream,
-    /// <<E as Key>::MapStorage::<V> as MapStorage<E, V>> (E = type of variant field)
+    /// `<<E as Key>::MapStorage::<V> as MapStorage<E, V>>` (E = type of variant field)
     pub(crate) as_map_storage: TokenStream,
-    /// <E as Key>::SetStorage (E = type of variant field)
+    /// `<E as Key>::SetStorage` (E = type of variant field), or
+    /// `BTreeSetStorage::<E>` if the variant is `#[key(ordered)]`.
     pub(crate) set_storage: TokenStream,
-    /// <<E as Key>::SetStorage as SetStorage<E>> (E = type of variant field)
+    /// `<<E as Key>::SetStorage as SetStorage<E>>` (E = type of variant field)
     pub(crate) as_set_storage: TokenStream,
+    /// If the type of the variant field mentions a generic parameter of the
+    /// key.
+    pub(crate) generic: bool,
 }
 
+/// Generics of the key.
 #[derive(Default)]
+pub(crate) struct Generics {
+    /// Generic parameters to declare, like `T, const N: usize,`.
+    params: TokenStream,
+    /// Generic arguments to use, like `T, N,`.
+    args: TokenStream,
+    /// Type parameters.
+    types: Vec<Ident>,
+    /// Where predicates, including the bounds required by variant fields.
+    predicates: Vec<syn::WherePredicate>,
+}
+
+impl Generics {
+    /// Predicates to put in a `where` clause, like `T: Key,`.
+    fn predicates(&self) -> TokenStream {
+        let predicates = &self.predicates;
+        quote!(#(#predicates,)*)
+    }
+
+    /// Predicates for an item which is parameterized over the given lifetime.
+    fn lt_predicates(&self, lt: &syn::Lifetime) -> TokenStream {
+        let predicates = &self.predicates;
+        let types = &self.types;
+        quote!(#(#predicates,)* #(#types: #lt,)*)
+    }
+
+    /// Extend a `where` clause with the predicates of the key.
+    fn extend_where_clause(
+        &self,
+        cx: &Ctxt<'_>,
+        where_clause: &mut syn::WhereClause,
+        lt: Option<&syn::Lifetime>,
+    ) -> Result<(), ()> {
+        where_clause
+            .predicates
+            .extend(self.predicates.iter().cloned());
+
+        if let Some(lt) = lt {
+            for ty in &self.types {
+                let predicate = cx.fallible(|| syn::parse2(quote!(#ty: #lt)))?;
+                where_clause.predicates.push(predicate);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+#[allow(clippy::struct_field_names)]
 pub(crate) struct Fields<'a> {
     fields: Vec<Field<'a>>,
     patterns: Vec<Pat>,
+    /// The key type, like `Key<T>`.
+    key: TokenStream,
+    generics: Generics,
 }
 
 impl<'a> Fields<'a> {
@@ -2103,6 +2611,11 @@ impl<'a> Fields<'a> {
         })
     }
 
+    /// Get complex fields whose type mentions a generic parameter.
+    fn generic(&self) -> impl Iterator<Item = &'_ Complex<'a>> {
+        self.complex().filter(|c| c.generic)
+    }
+
     /// Iterate over fields.
     fn iter(&self) -> ::core::slice::Iter<'_, Field<'a>> {
         self.fields.iter()
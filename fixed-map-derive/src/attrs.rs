@@ -1,7 +1,7 @@
 use syn::spanned::Spanned;
-use syn::{Meta, NestedMeta};
+use syn::{Lit, Meta, NestedMeta, Variant};
 
-use crate::context::{Ctxt, Opts};
+use crate::context::{Ctxt, Opts, Storage, VariantOpts};
 use crate::symbol;
 
 /// Parse attributes.
@@ -25,9 +25,88 @@ pub(crate) fn parse(cx: &Ctxt<'_>) -> Result<Opts, ()> {
 
         for meta in nested {
             match meta {
+                NestedMeta::Meta(Meta::Path(p)) if p == symbol::ALIASES => {
+                    opts.aliases = Some(p.span());
+                }
                 NestedMeta::Meta(Meta::Path(p)) if p == symbol::BITSET => {
                     opts.bitset = Some(p.span());
                 }
+                NestedMeta::Meta(Meta::Path(p)) if p == symbol::ORD => {
+                    opts.ord = Some(p.span());
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path == symbol::STORAGE => {
+                    let Lit::Str(lit) = &nv.lit else {
+                        cx.error(nv.lit.span(), "expected a string, like `\"dense\"`");
+                        return Err(());
+                    };
+
+                    let storage = match lit.value().as_str() {
+                        "dense" => Storage::Dense,
+                        "btree" => Storage::BTree,
+                        other => {
+                            cx.error(
+                                lit.span(),
+                                format_args!(
+                                    "unsupported storage `{other}`, expected `dense` or `btree`"
+                                ),
+                            );
+                            return Err(());
+                        }
+                    };
+
+                    opts.storage = Some((nv.span(), storage));
+                }
+                other => {
+                    cx.error(other.span(), "unsupported attribute");
+                    return Err(());
+                }
+            }
+        }
+    }
+
+    Ok(opts)
+}
+
+/// Test if the enum is marked with `#[repr(u8)]`.
+pub(crate) fn is_repr_u8(cx: &Ctxt<'_>) -> bool {
+    cx.ast
+        .attrs
+        .iter()
+        .filter(|attr| attr.path == symbol::REPR)
+        .filter_map(|attr| attr.parse_meta().ok())
+        .any(|meta| match meta {
+            Meta::List(list) => list
+                .nested
+                .iter()
+                .any(|meta| matches!(meta, NestedMeta::Meta(Meta::Path(p)) if p == symbol::U8)),
+            _ => false,
+        })
+}
+
+/// Parse attributes on a variant.
+pub(crate) fn variant(cx: &Ctxt<'_>, variant: &Variant) -> Result<VariantOpts, ()> {
+    let mut opts = VariantOpts::default();
+
+    for attr in &variant.attrs {
+        if attr.path != symbol::KEY {
+            continue;
+        }
+
+        let meta = cx.fallible(|| attr.parse_meta())?;
+
+        let nested = match meta {
+            Meta::List(meta) => meta.nested.into_iter(),
+            other => {
+                cx.error(other.span(), "unsupported attribute");
+                return Err(());
+            }
+        };
+
+        for meta in nested {
+            match meta {
+                NestedMeta::Meta(Meta::Path(p)) if p == symbol::ORDERED => {
+                    opts.ordered = Some(p.span());
+                }
                 other => {
                     cx.error(other.span(), "unsupported attribute");
                     return Err(());
@@ -0,0 +1,50 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::DataEnum;
+
+use crate::attrs;
+use crate::context::{Ctxt, Opts};
+use crate::unit_variants;
+
+/// The whole key is stored in a `BTreeMap`, as selected with
+/// `#[key(storage = "btree")]`.
+pub(crate) fn implement(cx: &Ctxt<'_>, opts: &Opts, en: &DataEnum) -> Result<TokenStream, ()> {
+    if let Some(span) = opts.bitset {
+        cx.error(span, "`bitset` can't be used with `storage = \"btree\"`");
+        return Err(());
+    }
+
+    for variant in &en.variants {
+        if let Some(span) = attrs::variant(cx, variant)?.ordered {
+            cx.error(
+                span,
+                "`ordered` is redundant since the key uses `storage = \"btree\"`",
+            );
+            return Err(());
+        }
+    }
+
+    let ident = &cx.ast.ident;
+    let key_t = cx.toks.key_t();
+    let btree_map_storage = cx.toks.btree_map_storage();
+    let btree_set_storage = cx.toks.btree_set_storage();
+    let (impl_generics, ty_generics, where_clause) = cx.ast.generics.split_for_impl();
+
+    let fixed_key_impl = if crate::is_all_unit_variants(en) {
+        Some(unit_variants::impl_fixed_key(cx, en, None))
+    } else {
+        None
+    };
+
+    Ok(quote! {
+        const _: () = {
+            #[automatically_derived]
+            impl #impl_generics #key_t for #ident #ty_generics #where_clause {
+                type MapStorage<V> = #btree_map_storage<Self, V>;
+                type SetStorage = #btree_set_storage<Self>;
+            }
+
+            #fixed_key_impl
+        };
+    })
+}
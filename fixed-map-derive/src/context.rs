@@ -51,23 +51,29 @@ macro_rules! toks {
 toks! {
     pub(crate) struct Toks<'a> {
         array_into_iter = [::core::array::IntoIter],
+        bitset_set_storage = [crate::set::storage::BitsetSetStorage],
         bool_type = [::core::primitive::bool],
+        btree_map_storage = [crate::map::storage::BTreeMapStorage],
+        btree_set_storage = [crate::set::storage::BTreeSetStorage],
         clone_t = [::core::clone::Clone],
+        const_map_storage_t = [crate::map::ConstMapStorage],
         copy_t = [::core::marker::Copy],
         double_ended_iterator_t = [::core::iter::DoubleEndedIterator],
         entry_enum = [crate::map::Entry],
         eq_t = [::core::cmp::Eq],
+        exact_size = [crate::iter::ExactSize],
+        fixed_key_t = [crate::key::FixedKey],
         hash_t = [::core::hash::Hash],
         hasher_t = [::core::hash::Hasher],
         into_iterator_t = [::core::iter::IntoIterator],
         iterator_cmp = [crate::macro_support::__storage_iterator_cmp],
-        iterator_cmp_bool = [crate::macro_support::__storage_iterator_cmp_bool],
+        iterator_copied = [::core::iter::Copied],
         iterator_flat_map = [::core::iter::FlatMap],
         iterator_flatten = [::core::iter::Flatten],
         iterator_partial_cmp = [crate::macro_support::__storage_iterator_partial_cmp],
-        iterator_partial_cmp_bool = [crate::macro_support::__storage_iterator_partial_cmp_bool],
         iterator_t = [::core::iter::Iterator],
         key_t = [crate::key::Key],
+        map = [crate::Map],
         mem = [::core::mem],
         occupied_entry_t = [crate::map::OccupiedEntry],
         option = [::core::option::Option],
@@ -78,9 +84,11 @@ toks! {
         ordering = [::core::cmp::Ordering],
         partial_eq_t = [::core::cmp::PartialEq],
         partial_ord_t = [::core::cmp::PartialOrd],
+        par_map_storage = [crate::__par_map_storage],
         slice_iter = [::core::slice::Iter],
         slice_iter_mut = [::core::slice::IterMut],
         map_storage_t = [crate::map::MapStorage],
+        set = [crate::Set],
         set_storage_t = [crate::set::SetStorage],
         vacant_entry_t = [crate::map::VacantEntry],
     }
@@ -116,8 +124,31 @@ fn suffixed<const N: usize>(prefix: &Path, parts: [&'static str; N]) -> Path {
 /// Options for derive.
 #[derive(Default)]
 pub(crate) struct Opts {
+    /// Emit `<Name>Map` and `<Name>Set` type aliases for the key.
+    pub(crate) aliases: Option<Span>,
     /// Implements sets as bitsets when possible.
     pub(crate) bitset: Option<Span>,
+    /// Implement `PartialOrd` and `Ord` for the key by `FixedKey::index`.
+    pub(crate) ord: Option<Span>,
+    /// Storage explicitly selected with `#[key(storage = "..")]`.
+    pub(crate) storage: Option<(Span, Storage)>,
+}
+
+/// Storage which can be selected with `#[key(storage = "..")]`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Storage {
+    /// The default storage for keys with only unit variants, which are
+    /// stored in an array.
+    Dense,
+    /// Store the whole key in a `BTreeMap`.
+    BTree,
+}
+
+/// Options for a single variant.
+#[derive(Default)]
+pub(crate) struct VariantOpts {
+    /// Use ordered storage for the field of the variant.
+    pub(crate) ordered: Option<Span>,
 }
 
 pub(crate) struct Ctxt<'a> {
@@ -23,7 +23,6 @@
     missing_docs,
     non_ascii_idents,
     noop_method_call,
-    pointer_structural_match,
     single_use_lifetimes,
     trivial_casts,
     trivial_numeric_casts,
@@ -33,7 +32,6 @@
     unused_lifetimes,
     unused_macro_rules,
     unused_qualifications,
-    unused_tuple_struct_fields,
     variant_size_differences,
     // Enable pedantic clippy lints
     clippy::pedantic,
@@ -58,13 +56,14 @@
     clippy::unnecessary_wraps
 )]
 
-use proc_macro2::TokenStream;
-use quote::quote;
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
 use syn::spanned::Spanned;
 use syn::{Data, DataEnum, DeriveInput, Fields};
 
 mod any_variants;
 mod attrs;
+mod btree;
 mod context;
 mod symbol;
 mod unit_variants;
@@ -103,43 +102,369 @@ mod unit_variants;
 ///     Third,
 /// }
 ///
-/// /// Build a storage struct containing an item for each key:
-/// pub struct KeyStorage<V> {
-///     /// Storage for `Key::First`.
-///     f1: Option<V>,
-///     /// Storage for `Key::Second`.
-///     f2: Option<V>,
-///     /// Storage for `Key::Third`.
-///     f3: Option<V>,
+/// /// Build a storage struct containing an item for each key, laid out as an
+/// /// array indexed in declaration order:
+/// pub struct KeyMapStorage<V> {
+///     data: [Option<V>; 3],
 /// }
 ///
-/// /// Implement storage for `KeyStorage`.
-/// impl<V> fixed_map::storage::Storage<Key, V> for KeyStorage<V> {
+/// /// Implement map storage for `KeyMapStorage`.
+/// impl<V> fixed_map::map::MapStorage<Key, V> for KeyMapStorage<V> {
 ///     fn get(&self, key: Key) -> Option<&V> {
+///         let [first, second, third] = &self.data;
+///
 ///         match key {
-///             Key::First => self.f1.as_ref(),
-///             Key::Second => self.f2.as_ref(),
-///             Key::Third => self.f3.as_ref(),
+///             Key::First => first.as_ref(),
+///             Key::Second => second.as_ref(),
+///             Key::Third => third.as_ref(),
 ///         }
 ///     }
 ///
 ///     /* skipped */
 /// }
 ///
-/// impl<V> Default for KeyStorage<V> {
-///     fn default() -> Self {
-///         Self {
-///             f1: None,
-///             f2: None,
-///             f3: None,
+/// /// Implement the `Key` trait to point out storage. Sets use a bitset with
+/// /// one bit for each key, packed into as many `u64` words as needed.
+/// impl fixed_map::Key for Key {
+///     type MapStorage<V> = KeyMapStorage<V>;
+///     type SetStorage = fixed_map::set::storage::BitsetSetStorage<1>;
+/// }
+///
+/// /// Since every variant is a unit variant, the number of keys is known.
+/// impl fixed_map::key::FixedKey for Key {
+///     const COUNT: usize = 3;
+///
+///     fn keys() -> std::iter::Copied<std::slice::Iter<'static, Key>> {
+///         static KEYS: [Key; 3] = [Key::First, Key::Second, Key::Third];
+///         KEYS.iter().copied()
+///     }
+///
+///     fn index(self) -> usize {
+///         match self {
+///             Key::First => 0,
+///             Key::Second => 1,
+///             Key::Third => 2,
+///         }
+///     }
+///
+///     fn from_index(index: usize) -> Option<Key> {
+///         match index {
+///             0 => Some(Key::First),
+///             1 => Some(Key::Second),
+///             2 => Some(Key::Third),
+///             _ => None,
 ///         }
 ///     }
 /// }
+/// ```
+///
+/// ## Explicit discriminants
+///
+/// Slots in the generated storage are assigned by the position a variant is
+/// declared in, not by its discriminant, unless the key opts into [indexing by
+/// discriminant](#indexing-by-discriminant). Unit-only enums may therefore use
+/// any explicit discriminants, including sparse or out of order ones, and
+/// entries are still iterated over in declaration order.
+///
+/// ```rust
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// pub enum Status {
+///     NotFound = 404,
+///     Ok = 200,
+///     Teapot = 418,
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(Status::Teapot, "teapot");
+/// map.insert(Status::NotFound, "not found");
+///
+/// assert!(map.keys().eq([Status::NotFound, Status::Teapot]));
+/// ```
+///
+/// Two variants can't share a slot, since the compiler already rejects
+/// duplicate discriminants:
+///
+/// ```rust,compile_fail
+/// use fixed_map::Key;
+///
+/// #[derive(Clone, Copy, Key)]
+/// pub enum Status {
+///     Ok = 200,
+///     Success = 200,
+/// }
+/// ```
+///
+/// ## Variants with fields
+///
+/// Any number of variants may have a single unnamed field, as long as the
+/// type of that field implements `Key`. The generated storage then holds the
+/// storage of the field's `Key` implementation for each such variant, next to
+/// a slot for each unit variant. Entries are iterated over one variant at a
+/// time in declaration order.
+///
+/// ```rust
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// pub enum Part {
+///     One,
+///     Two,
+/// }
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// pub enum Route {
+///     Part(Part),
+///     Flag(bool),
+///     Fallback,
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(Route::Fallback, "fallback");
+/// map.insert(Route::Flag(true), "on");
+/// map.insert(Route::Part(Part::Two), "two");
+///
+/// assert!(map.keys().eq([Route::Part(Part::Two), Route::Flag(true), Route::Fallback]));
+/// ```
+///
+/// Fields whose type doesn't implement `Key` are rejected:
+///
+/// ```rust,compile_fail
+/// use fixed_map::Key;
+///
+/// #[derive(Clone, Copy, Key)]
+/// pub enum Route {
+///     Weight(f32),
+///     Fallback,
+/// }
+/// ```
+///
+/// As are variants with named fields:
+///
+/// ```rust,compile_fail
+/// use fixed_map::Key;
+///
+/// #[derive(Clone, Copy, Key)]
+/// pub enum Route {
+///     Flag { enabled: bool },
+///     Fallback,
+/// }
+/// ```
+///
+/// ## Ordered storage
+///
+/// Variants with a field can be marked with `#[key(ordered)]`, which stores
+/// them in a `BTreeMap` instead of the storage provided by the field's `Key`
+/// implementation. Keys belonging to that variant are then iterated over in
+/// ascending order. This requires the `alloc` feature.
+///
+/// ```rust
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// pub enum Key {
+///     #[key(ordered)]
+///     Score(u32),
+///     Unranked,
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(Key::Score(300), "third");
+/// map.insert(Key::Score(100), "first");
+/// map.insert(Key::Score(200), "second");
+///
+/// assert!(map.keys().eq([Key::Score(100), Key::Score(200), Key::Score(300)]));
+/// ```
+///
+/// ## Selecting storage
+///
+/// The storage used for a key can be selected with `#[key(storage = "..")]`
+/// on the enum. The following storages are supported:
+///
+/// * `"dense"` - the default for keys with only unit variants, which are
+///   stored in an array. Each array slot is an `Option<V>`, so even a fully
+///   populated map can't lend out its values as a contiguous `&mut [V]`; use
+///   `Map::values_mut` to update them in bulk instead. Variants with a field
+///   always use the storage of the field's `Key` implementation, so selecting
+///   this for a key which has them is an error.
+/// * `"btree"` - the whole key is stored in a `BTreeMap`, so keys are iterated
+///   over in the order of their [`Ord`] implementation. This requires the
+///   `alloc` feature.
+///
+/// ```rust
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Key)]
+/// #[key(storage = "btree")]
+/// pub enum Key {
+///     Score(u32),
+///     Unranked,
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(Key::Unranked, "last");
+/// map.insert(Key::Score(200), "second");
+/// map.insert(Key::Score(100), "first");
+///
+/// assert!(map.values().eq([&"first", &"second", &"last"]));
+/// ```
+///
+/// Selecting `"dense"` storage for a key with a variant that has a field is
+/// rejected:
+///
+/// ```rust,compile_fail
+/// use fixed_map::Key;
+///
+/// #[derive(Clone, Copy, Key)]
+/// #[key(storage = "dense")]
+/// pub enum Key {
+///     First,
+///     Second(bool),
+/// }
+/// ```
+///
+/// ### Indexing by discriminant
+///
+/// If `"dense"` storage is explicitly selected for a key with only unit
+/// variants which is marked `#[repr(u8)]`, each variant is instead stored in
+/// the array slot given by its discriminant. The array then has one slot more
+/// than the largest discriminant, so its layout mirrors the numeric values of
+/// the key, and `FixedKey::index` returns the discriminant. Discriminants may
+/// be sparse and in any order, and entries are iterated over in the order of
+/// their discriminants.
+///
+/// ```rust
+/// use core::mem::size_of;
+///
+/// use fixed_map::{Key, Map};
+/// use fixed_map::key::FixedKey;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// #[key(storage = "dense")]
+/// #[repr(u8)]
+/// pub enum Register {
+///     Data = 7,
+///     Status = 0,
+///     Control = 2,
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(Register::Data, 0xffu8);
+/// map.insert(Register::Status, 0x01u8);
+///
+/// assert!(map.keys().eq([Register::Status, Register::Data]));
+/// assert_eq!(size_of::<Map<Register, u8>>(), size_of::<[Option<u8>; 8]>());
+///
+/// assert_eq!(Register::Data.index(), 7);
+/// assert_eq!(Register::from_index(2), Some(Register::Control));
+/// assert_eq!(Register::from_index(1), None);
+/// ```
+///
+/// Discriminants must be known while deriving, so explicit discriminants have
+/// to be integer literals:
+///
+/// ```rust,compile_fail
+/// use fixed_map::Key;
+///
+/// #[derive(Clone, Copy, Key)]
+/// #[key(storage = "dense")]
+/// #[repr(u8)]
+/// pub enum Register {
+///     Status = 0,
+///     Control = 1 << 1,
+/// }
+/// ```
+///
+/// ## Ordering
+///
+/// With `#[key(ord)]` on a key with only unit variants, the derive also
+/// implements `PartialOrd` and `Ord` for the key, ordering variants by
+/// `FixedKey::index`. This is their position in declaration order, or their
+/// discriminant for keys which are [indexed by
+/// discriminant](#indexing-by-discriminant), and matches the order in which
+/// entries are iterated over. The key must also implement `Eq`.
+///
+/// ```rust
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+/// #[key(ord)]
+/// pub enum Level {
+///     Low = 10,
+///     High = 1,
+/// }
+///
+/// assert!(Level::Low < Level::High);
+///
+/// let mut levels = vec![Level::High, Level::Low];
+/// levels.sort();
+/// assert_eq!(levels, [Level::Low, Level::High]);
+/// ```
+///
+/// Variants with fields can't be ordered by their position alone, so this is
+/// rejected:
+///
+/// ```rust,compile_fail
+/// use fixed_map::Key;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Key)]
+/// #[key(ord)]
+/// pub enum Level {
+///     Low(bool),
+///     High,
+/// }
+/// ```
+///
+/// ## Generic keys
+///
+/// Keys may have type and const parameters. The fields of variants which
+/// mention a type parameter must themselves implement `Key`, which is added
+/// as a bound to the generated implementation. Lifetime parameters are not
+/// supported.
+///
+/// ```rust
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// pub enum Slot<T> {
+///     Item(T),
+///     Empty,
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(Slot::Item(true), 1);
+/// map.insert(Slot::Empty, 2);
+///
+/// assert_eq!(map.get(Slot::Item(true)), Some(&1));
+/// assert_eq!(map.get(Slot::Item(false)), None);
+/// ```
+///
+/// ## Type aliases
+///
+/// With `#[key(aliases)]` on the enum, the derive also emits a `<Name>Map<V>`
+/// and a `<Name>Set` type alias for `Map<Name, V>` and `Set<Name>`, with the
+/// same visibility as the enum. This isn't supported for generic keys.
+///
+/// ```rust
+/// use fixed_map::Key;
 ///
-/// /// Implement the `Key` trait to point out storage.
-/// impl<V> fixed_map::key::Key<Key, V> for Key {
-///     type Storage = KeyStorage<V>;
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// #[key(aliases)]
+/// pub enum Perm {
+///     Read,
+///     Write,
+///     Execute,
 /// }
+///
+/// let mut granted = PermSet::new();
+/// granted.insert(Perm::Read);
+///
+/// let mut owners: PermMap<&str> = PermMap::new();
+/// owners.insert(Perm::Write, "root");
+///
+/// assert!(granted.contains(Perm::Read));
+/// assert_eq!(owners.get(Perm::Write), Some(&"root"));
 /// ```
 #[proc_macro_derive(Key, attributes(key))]
 pub fn storage_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
@@ -152,8 +477,8 @@ pub fn storage_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 
     let result = impl_storage(&cx);
 
-    if let Ok(gen) = result {
-        return gen.into();
+    if let Ok(output) = result {
+        return output.into();
     }
 
     let errors = cx.into_errors();
@@ -165,16 +490,68 @@ pub fn storage_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream
 fn impl_storage(cx: &context::Ctxt<'_>) -> Result<TokenStream, ()> {
     let opts = attrs::parse(cx)?;
 
-    if let Data::Enum(en) = &cx.ast.data {
-        if is_all_unit_variants(en) {
-            unit_variants::implement(cx, &opts, en)
-        } else {
-            any_variants::implement(cx, en)
-        }
-    } else {
+    let Data::Enum(en) = &cx.ast.data else {
         cx.error(cx.ast.span(), "named fields are not supported");
-        Err(())
+        return Err(());
+    };
+
+    let output = match opts.storage {
+        Some((_, context::Storage::BTree)) => btree::implement(cx, &opts, en)?,
+        Some((span, context::Storage::Dense)) if !is_all_unit_variants(en) => {
+            cx.error(
+                span,
+                "`dense` storage is only supported for keys with only unit variants",
+            );
+            return Err(());
+        }
+        _ if is_all_unit_variants(en) => unit_variants::implement(cx, &opts, en)?,
+        _ => any_variants::implement(cx, en)?,
+    };
+
+    let ord = match opts.ord {
+        Some(span) if !is_all_unit_variants(en) => {
+            cx.error(
+                span,
+                "`ord` is only supported for keys with only unit variants",
+            );
+            return Err(());
+        }
+        Some(..) => unit_variants::impl_ord(cx),
+        None => TokenStream::new(),
+    };
+
+    let Some(span) = opts.aliases else {
+        return Ok(quote!(#output #ord));
+    };
+
+    let aliases = impl_aliases(cx, span)?;
+    Ok(quote!(#output #ord #aliases))
+}
+
+/// Emit the type aliases requested with `#[key(aliases)]`.
+fn impl_aliases(cx: &context::Ctxt<'_>, span: Span) -> Result<TokenStream, ()> {
+    if !cx.ast.generics.params.is_empty() {
+        cx.error(span, "`aliases` is not supported for generic keys");
+        return Err(());
     }
+
+    let vis = &cx.ast.vis;
+    let ident = &cx.ast.ident;
+    let map = cx.toks.map();
+    let set = cx.toks.set();
+
+    let map_ident = format_ident!("{ident}Map");
+    let set_ident = format_ident!("{ident}Set");
+    let map_doc = format!(" A map keyed by [`{ident}`].");
+    let set_doc = format!(" A set of [`{ident}`].");
+
+    Ok(quote! {
+        #[doc = #map_doc]
+        #vis type #map_ident<V> = #map<#ident, V>;
+
+        #[doc = #set_doc]
+        #vis type #set_ident = #set<#ident>;
+    })
 }
 
 fn is_all_unit_variants(en: &DataEnum) -> bool {
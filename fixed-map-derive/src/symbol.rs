@@ -6,7 +6,13 @@ use syn::{Ident, Path};
 pub struct Symbol(&'static str);
 
 pub(crate) const KEY: Symbol = Symbol("key");
+pub(crate) const ALIASES: Symbol = Symbol("aliases");
 pub(crate) const BITSET: Symbol = Symbol("bitset");
+pub(crate) const ORD: Symbol = Symbol("ord");
+pub(crate) const ORDERED: Symbol = Symbol("ordered");
+pub(crate) const REPR: Symbol = Symbol("repr");
+pub(crate) const STORAGE: Symbol = Symbol("storage");
+pub(crate) const U8: Symbol = Symbol("u8");
 
 impl PartialEq<Symbol> for Ident {
     fn eq(&self, word: &Symbol) -> bool {
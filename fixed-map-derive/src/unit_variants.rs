@@ -1,46 +1,57 @@
 use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, quote};
 use syn::spanned::Spanned;
-use syn::{DataEnum, Ident, LitInt};
+use syn::{DataEnum, Expr, ExprLit, Ident, Lit, LitInt};
 
-use crate::context::{Ctxt, Opts};
+use crate::attrs;
+use crate::context::{Ctxt, Opts, Storage};
 
 /// Every variant is a unit variant.
 pub(crate) fn implement(cx: &Ctxt<'_>, opts: &Opts, en: &DataEnum) -> Result<TokenStream, ()> {
-    let const_wrapper = Ident::new(
-        &format!("__IMPL_KEY_FOR_{}", cx.ast.ident),
-        Span::call_site(),
-    );
-
     let map_storage = format_ident!("__MapStorage");
     let set_storage = format_ident!("__SetStorage");
 
     let count = en.variants.len();
     let mut names = Vec::with_capacity(count);
 
-    for (index, _) in en.variants.iter().enumerate() {
+    for (index, variant) in en.variants.iter().enumerate() {
+        if let Some(span) = attrs::variant(cx, variant)?.ordered {
+            cx.error(span, "`ordered` is only supported on variants with a field");
+            return Err(());
+        }
+
         names.push(format_ident!("_{}", index));
     }
 
+    let slots = layout(cx, opts, en)?;
+
     let entry_impl = impl_entry(cx, &map_storage)?;
-    let map_storage_impl = impl_map(cx, en, &map_storage, &names)?;
+    let map_storage_impl = impl_map(cx, en, &map_storage, &names, slots.as_deref())?;
 
-    let set_storage_impl = if let Some(span) = opts.bitset {
+    let (set_storage_impl, set_storage) = if let Some(span) = opts.bitset {
         if !cfg!(fixed_map_experimental) {
             cx.error(span, "trying to use experimental feature `bitset` without specifying `--cfg fixed_map_experimental`");
             return Err(());
         }
 
-        impl_bitset(cx, en, &set_storage)?
+        (impl_bitset(cx, en, &set_storage)?, quote!(#set_storage))
     } else {
-        impl_set(cx, en, &set_storage, &names)?
+        // Pack membership into as many 64-bit words as are needed to hold a
+        // bit for every index of the key.
+        let bitset_set_storage = cx.toks.bitset_set_storage();
+        let bits = slots.as_deref().map_or(count, slot_count);
+        (
+            TokenStream::new(),
+            quote!(#bitset_set_storage<{ (#bits + 63) / 64 }>),
+        )
     };
 
     let ident = &cx.ast.ident;
     let key_t = cx.toks.key_t();
+    let fixed_key_impl = impl_fixed_key(cx, en, slots.as_deref());
 
     Ok(quote! {
-        const #const_wrapper: () = {
+        const _: () = {
             #entry_impl
             #map_storage_impl
             #set_storage_impl
@@ -50,10 +61,86 @@ pub(crate) fn implement(cx: &Ctxt<'_>, opts: &Opts, en: &DataEnum) -> Result<Tok
                 type MapStorage<V> = #map_storage<V>;
                 type SetStorage = #set_storage;
             }
+
+            #fixed_key_impl
         };
     })
 }
 
+/// Implement `FixedKey`, which is possible since every variant is a unit
+/// variant.
+///
+/// The index of each variant is its position in declaration order, unless
+/// `slots` are given in which case it's the slot the variant is stored in.
+pub(crate) fn impl_fixed_key(cx: &Ctxt<'_>, en: &DataEnum, slots: Option<&[usize]>) -> TokenStream {
+    let ident = &cx.ast.ident;
+    let count = en.variants.len();
+    let fixed_key_t = cx.toks.fixed_key_t();
+    let iterator_t = cx.toks.iterator_t();
+    let iterator_copied = cx.toks.iterator_copied();
+    let slice_iter = cx.toks.slice_iter();
+    let option = cx.toks.option();
+    let variants = en.variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+    let indexes = slots.map_or_else(|| (0..count).collect(), <[usize]>::to_vec);
+
+    quote! {
+        #[automatically_derived]
+        impl #fixed_key_t for #ident {
+            const COUNT: usize = #count;
+
+            #[inline]
+            fn keys() -> #iterator_copied<#slice_iter<'static, Self>> {
+                static KEYS: [#ident; #count] = [#(#ident::#variants),*];
+                #iterator_t::copied(<[#ident]>::iter(&KEYS))
+            }
+
+            #[inline]
+            fn index(self) -> usize {
+                match self {
+                    #(#ident::#variants => #indexes,)*
+                }
+            }
+
+            #[inline]
+            fn from_index(index: usize) -> #option<Self> {
+                match index {
+                    #(#indexes => #option::Some(#ident::#variants),)*
+                    _ => #option::None,
+                }
+            }
+        }
+    }
+}
+
+/// Implement `PartialOrd` and `Ord` by comparing the indexes of the variants,
+/// as requested with `#[key(ord)]`.
+pub(crate) fn impl_ord(cx: &Ctxt<'_>) -> TokenStream {
+    let ident = &cx.ast.ident;
+    let fixed_key_t = cx.toks.fixed_key_t();
+    let option = cx.toks.option();
+    let ord_t = cx.toks.ord_t();
+    let ordering = cx.toks.ordering();
+    let partial_ord_t = cx.toks.partial_ord_t();
+
+    quote! {
+        #[automatically_derived]
+        impl #partial_ord_t for #ident {
+            #[inline]
+            fn partial_cmp(&self, other: &Self) -> #option<#ordering> {
+                #option::Some(#ord_t::cmp(self, other))
+            }
+        }
+
+        #[automatically_derived]
+        impl #ord_t for #ident {
+            #[inline]
+            fn cmp(&self, other: &Self) -> #ordering {
+                #ord_t::cmp(&#fixed_key_t::index(*self), &#fixed_key_t::index(*other))
+            }
+        }
+    }
+}
+
 fn impl_entry(cx: &Ctxt<'_>, map_storage: &Ident) -> Result<TokenStream, ()> {
     let ident = &cx.ast.ident;
     let lt = cx.lt;
@@ -80,6 +167,11 @@ fn impl_entry(cx: &Ctxt<'_>, map_storage: &Ident) -> Result<TokenStream, ()> {
                 self.key
             }
 
+            #[inline]
+            fn into_key(self) -> #ident {
+                self.key
+            }
+
             #[inline]
             fn insert(self, value: V) -> &#lt mut V {
                 #option_bucket_none::insert(self.inner, value)
@@ -122,6 +214,11 @@ fn impl_entry(cx: &Ctxt<'_>, map_storage: &Ident) -> Result<TokenStream, ()> {
             fn remove(self) -> V {
                 #option_bucket_some::take(self.inner)
             }
+
+            #[inline]
+            fn replace_key(self, key: #ident) -> #ident {
+                key
+            }
         }
 
         #[inline]
@@ -134,11 +231,67 @@ fn impl_entry(cx: &Ctxt<'_>, map_storage: &Ident) -> Result<TokenStream, ()> {
     })
 }
 
+/// Determine the array slot of each variant when they should be stored by
+/// discriminant, which is the case for `#[repr(u8)]` keys which explicitly
+/// select dense storage.
+///
+/// Discriminants must be integer literals, since they have to be known while
+/// expanding the derive. Variants without one follow the previous variant, as
+/// they do in the language.
+fn layout(cx: &Ctxt<'_>, opts: &Opts, en: &DataEnum) -> Result<Option<Vec<usize>>, ()> {
+    if !matches!(opts.storage, Some((_, Storage::Dense))) || !attrs::is_repr_u8(cx) {
+        return Ok(None);
+    }
+
+    let mut slots = Vec::with_capacity(en.variants.len());
+    let mut next = 0usize;
+
+    for variant in &en.variants {
+        let slot = match &variant.discriminant {
+            Some((_, expr)) => {
+                let Expr::Lit(ExprLit {
+                    lit: Lit::Int(lit), ..
+                }) = expr
+                else {
+                    cx.error(
+                        expr.span(),
+                        "discriminant must be an integer literal to be used for dense storage",
+                    );
+                    return Err(());
+                };
+
+                let slot = cx.fallible(|| lit.base10_parse::<u8>())?;
+                usize::from(slot)
+            }
+            None => next,
+        };
+
+        if slot > usize::from(u8::MAX) {
+            cx.error(
+                variant.span(),
+                "discriminant doesn't fit in `u8` to be used for dense storage",
+            );
+            return Err(());
+        }
+
+        slots.push(slot);
+        next = slot + 1;
+    }
+
+    Ok(Some(slots))
+}
+
+/// The number of slots needed to store every variant at the given slots.
+fn slot_count(slots: &[usize]) -> usize {
+    slots.iter().max().map_or(0, |slot| slot + 1)
+}
+
 fn impl_map(
     cx: &Ctxt<'_>,
     en: &DataEnum,
     map_storage: &Ident,
     names: &[Ident],
+    slots: Option<&[usize]>,
 ) -> Result<TokenStream, ()> {
     let ident = &cx.ast.ident;
     let lt = &cx.lt;
@@ -154,6 +307,7 @@ fn impl_map(
     let hash_t = cx.toks.hash_t();
     let hasher_t = cx.toks.hasher_t();
     let iterator_cmp = cx.toks.iterator_cmp();
+    let exact_size = cx.toks.exact_size();
     let iterator_flat_map = cx.toks.iterator_flat_map();
     let iterator_flatten = cx.toks.iterator_flatten();
     let iterator_partial_cmp = cx.toks.iterator_partial_cmp();
@@ -166,19 +320,58 @@ fn impl_map(
     let slice_iter = cx.toks.slice_iter();
     let slice_iter_mut = cx.toks.slice_iter_mut();
     let map_storage_t = cx.toks.map_storage_t();
+    let const_map_storage_t = cx.toks.const_map_storage_t();
+    let par_map_storage = cx.toks.par_map_storage();
+    let option_bucket_option = cx.toks.option_bucket_option();
+    let option_bucket_some = cx.toks.option_bucket_some();
 
-    let variants = en.variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
-    let init = en
-        .variants
+    let mut variants = en.variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
+    let mut names = names.iter().collect::<Vec<_>>();
+    let count = en.variants.len();
+
+    // The pattern used to destructure the array, where slots which don't
+    // belong to any variant are ignored.
+    let mut pattern = Vec::new();
+    // The key associated with each slot. Unused slots are always empty, so
+    // they can be associated with any key.
+    let mut slot_keys = Vec::new();
+
+    if let Some(slots) = slots {
+        let len = slot_count(slots);
+        pattern.resize(len, quote!(_));
+
+        if let Some(first) = variants.first() {
+            slot_keys.resize(len, quote!(#ident::#first));
+        }
+
+        for ((slot, name), variant) in slots.iter().zip(&names).zip(&variants) {
+            pattern[*slot] = quote!(#name);
+            slot_keys[*slot] = quote!(#ident::#variant);
+        }
+
+        // Entries are visited in the order of their slots, which is the order
+        // of their indexes.
+        let mut order = slots
+            .iter()
+            .zip(variants.into_iter().zip(names))
+            .collect::<Vec<_>>();
+        order.sort_by_key(|(slot, _)| **slot);
+        (variants, names) = order.into_iter().map(|(_, pair)| pair).unzip();
+    } else {
+        pattern.extend(names.iter().map(|name| quote!(#name)));
+        slot_keys.extend(variants.iter().map(|variant| quote!(#ident::#variant)));
+    }
+
+    let len = pattern.len();
+    let init = pattern
         .iter()
         .map(|_| quote!(#option::None))
         .collect::<Vec<_>>();
-    let count = en.variants.len();
 
     Ok(quote! {
         #[repr(transparent)]
         #vis struct #map_storage<V> {
-            data: [#option<V>; #count],
+            data: [#option<V>; #len],
         }
 
         #[automatically_derived]
@@ -189,6 +382,11 @@ fn impl_map(
                     data: #clone_t::clone(&self.data),
                 }
             }
+
+            #[inline]
+            fn clone_from(&mut self, source: &Self) {
+                #clone_t::clone_from(&mut self.data, &source.data);
+            }
         }
 
         #[automatically_derived]
@@ -233,26 +431,33 @@ fn impl_map(
             }
         }
 
+        #[automatically_derived]
+        impl<V> #const_map_storage_t<#ident, V> for #map_storage<V> {
+            const EMPTY: Self = Self {
+                data: [#(#init),*],
+            };
+        }
+
         #[automatically_derived]
         impl<V> #map_storage_t<#ident, V> for #map_storage<V> {
-            type Iter<#lt> = #iterator_flat_map<
+            type Iter<#lt> = #exact_size<#iterator_flat_map<
                 #array_into_iter<(#ident, &#lt #option<V>), #count>,
                 #option<(#ident, &#lt V)>,
                 fn((#ident, &#lt #option<V>)) -> #option<(#ident, &#lt V)>
-            > where V: #lt;
-            type Keys<#lt> = #iterator_flatten<#array_into_iter<#option<#ident>, #count>> where V: #lt;
-            type Values<#lt> = #iterator_flatten<#slice_iter<#lt, #option<V>>> where V: #lt;
-            type IterMut<#lt> = #iterator_flat_map<
+            >> where V: #lt;
+            type Keys<#lt> = #exact_size<#iterator_flatten<#array_into_iter<#option<#ident>, #count>>> where V: #lt;
+            type Values<#lt> = #exact_size<#iterator_flatten<#slice_iter<#lt, #option<V>>>> where V: #lt;
+            type IterMut<#lt> = #exact_size<#iterator_flat_map<
                 #array_into_iter<(#ident, &#lt mut #option<V>), #count>,
                 #option<(#ident, &#lt mut V)>,
                 fn((#ident, &#lt mut #option<V>)) -> #option<(#ident, &#lt mut V)>
-            > where V: #lt;
-            type ValuesMut<#lt> = #iterator_flatten<#slice_iter_mut<#lt, #option<V>>> where V: #lt;
-            type IntoIter = #iterator_flat_map<
+            >> where V: #lt;
+            type ValuesMut<#lt> = #exact_size<#iterator_flatten<#slice_iter_mut<#lt, #option<V>>>> where V: #lt;
+            type IntoIter = #exact_size<#iterator_flat_map<
                 #array_into_iter<(#ident, #option<V>), #count>,
                 #option<(#ident, V)>,
                 fn((#ident, #option<V>)) -> #option<(#ident, V)>
-            >;
+            >>;
             type Occupied<#lt> = OccupiedEntry<#lt, V> where V: #lt;
             type Vacant<#lt> = VacantEntry<#lt, V> where V: #lt;
 
@@ -265,28 +470,28 @@ fn impl_map(
 
             #[inline]
             fn len(&self) -> usize {
-                let [#(#names),*] = &self.data;
+                let [#(#pattern),*] = &self.data;
                 0 #(+ usize::from(#option::is_some(#names)))*
             }
 
             #[inline]
             fn is_empty(&self) -> bool {
-                let [#(#names),*] = &self.data;
+                let [#(#pattern),*] = &self.data;
                 true #(&& #option::is_none(#names))*
             }
 
             #[inline]
             fn insert(&mut self, key: #ident, value: V) -> #option<V> {
-                let [#(#names),*] = &mut self.data;
+                let [#(#pattern),*] = &mut self.data;
 
                 match key {
-                    #(#ident::#variants => #mem::replace(#names, #option::Some(value)),)*
+                    #(#ident::#variants => #option::replace(#names, value),)*
                 }
             }
 
             #[inline]
             fn contains_key(&self, value: #ident) -> bool {
-                let [#(#names),*] = &self.data;
+                let [#(#pattern),*] = &self.data;
 
                 match value {
                     #(#ident::#variants => #option::is_some(#names),)*
@@ -295,7 +500,7 @@ fn impl_map(
 
             #[inline]
             fn get(&self, value: #ident) -> #option<&V> {
-                let [#(#names),*] = &self.data;
+                let [#(#pattern),*] = &self.data;
 
                 match value {
                     #(#ident::#variants => #option::as_ref(#names),)*
@@ -304,7 +509,7 @@ fn impl_map(
 
             #[inline]
             fn get_mut(&mut self, value: #ident) -> #option<&mut V> {
-                let [#(#names),*] = &mut self.data;
+                let [#(#pattern),*] = &mut self.data;
 
                 match value {
                     #(#ident::#variants => #option::as_mut(#names),)*
@@ -313,7 +518,7 @@ fn impl_map(
 
             #[inline]
             fn remove(&mut self, value: #ident) -> #option<V> {
-                let [#(#names),*] = &mut self.data;
+                let [#(#pattern),*] = &mut self.data;
 
                 match value {
                     #(#ident::#variants => #mem::take(#names),)*
@@ -325,7 +530,7 @@ fn impl_map(
             where
                 F: FnMut(#ident, &mut V) -> bool
             {
-                let [#(#names),*] = &mut self.data;
+                let [#(#pattern),*] = &mut self.data;
 
                 #(if let #option::Some(val) = #option::as_mut(#names) {
                     if !func(#ident::#variants, val) {
@@ -334,6 +539,21 @@ fn impl_map(
                 })*
             }
 
+            #[inline]
+            fn retain_with_removed<F, R>(&mut self, mut keep: F, mut on_remove: R)
+            where
+                F: FnMut(#ident, &mut V) -> bool,
+                R: FnMut(#ident, V)
+            {
+                let [#(#pattern),*] = &mut self.data;
+
+                #(if let #option_bucket_option::Some(mut bucket) = #option_bucket_option::new(#names) {
+                    if !keep(#ident::#variants, #option_bucket_some::as_mut(&mut bucket)) {
+                        on_remove(#ident::#variants, #option_bucket_some::take(bucket));
+                    }
+                })*
+            }
+
             #[inline]
             fn clear(&mut self) {
                 self.data = [#(#init),*];
@@ -341,47 +561,55 @@ fn impl_map(
 
             #[inline]
             fn iter(&self) -> Self::Iter<'_> {
-                let [#(#names),*] = &self.data;
-                #iterator_t::flat_map(#into_iterator_t::into_iter([#((#ident::#variants, #names)),*]), |(k, v)| #option::Some((k, #option::as_ref(v)?)))
+                let len = <Self as #map_storage_t<#ident, V>>::len(self);
+                let [#(#pattern),*] = &self.data;
+                #exact_size::new(#iterator_t::flat_map(#into_iterator_t::into_iter([#((#ident::#variants, #names)),*]), |(k, v)| #option::Some((k, #option::as_ref(v)?))), len)
             }
 
             #[inline]
             fn keys(&self) -> Self::Keys<'_> {
-                let [#(#names),*] = &self.data;
-                #iterator_t::flatten(#into_iterator_t::into_iter([#(if #names.is_some() { Some(#ident::#variants) } else { None }),*]))
+                let len = <Self as #map_storage_t<#ident, V>>::len(self);
+                let [#(#pattern),*] = &self.data;
+                #exact_size::new(#iterator_t::flatten(#into_iterator_t::into_iter([#(if #names.is_some() { Some(#ident::#variants) } else { None }),*])), len)
             }
 
             #[inline]
             fn values(&self) -> Self::Values<'_> {
-                #iterator_t::flatten(#into_iterator_t::into_iter(&self.data))
+                let len = <Self as #map_storage_t<#ident, V>>::len(self);
+                #exact_size::new(#iterator_t::flatten(#into_iterator_t::into_iter(&self.data)), len)
             }
 
             #[inline]
             fn iter_mut(&mut self) -> Self::IterMut<'_> {
-                let [#(#names),*] = &mut self.data;
-                #iterator_t::flat_map(#into_iterator_t::into_iter([#((#ident::#variants, #names)),*]), |(k, v)| #option::Some((k, #option::as_mut(v)?)))
+                let len = <Self as #map_storage_t<#ident, V>>::len(self);
+                let [#(#pattern),*] = &mut self.data;
+                #exact_size::new(#iterator_t::flat_map(#into_iterator_t::into_iter([#((#ident::#variants, #names)),*]), |(k, v)| #option::Some((k, #option::as_mut(v)?))), len)
             }
 
             #[inline]
             fn values_mut(&mut self) -> Self::ValuesMut<'_> {
-                #iterator_t::flatten(#into_iterator_t::into_iter(&mut self.data))
+                let len = <Self as #map_storage_t<#ident, V>>::len(self);
+                #exact_size::new(#iterator_t::flatten(#into_iterator_t::into_iter(&mut self.data)), len)
             }
 
             #[inline]
             fn into_iter(self) -> Self::IntoIter {
-                let [#(#names),*] = self.data;
-                #iterator_t::flat_map(#into_iterator_t::into_iter([#((#ident::#variants, #names)),*]), |(k, v)| #option::Some((k, v?)))
+                let len = <Self as #map_storage_t<#ident, V>>::len(&self);
+                let [#(#pattern),*] = self.data;
+                #exact_size::new(#iterator_t::flat_map(#into_iterator_t::into_iter([#((#ident::#variants, #names)),*]), |(k, v)| #option::Some((k, v?))), len)
             }
 
             #[inline]
             fn entry(&mut self, key: #ident) -> #entry_enum<'_, Self, #ident, V> {
-                let [#(#names),*] = &mut self.data;
+                let [#(#pattern),*] = &mut self.data;
 
                 match key {
                     #(#ident::#variants => option_to_entry(#names, key),)*
                 }
             }
         }
+
+        #par_map_storage!(#map_storage, #ident, #len, [#(#slot_keys),*]);
     })
 }
 
@@ -401,6 +629,7 @@ fn impl_bitset(cx: &Ctxt<'_>, en: &DataEnum, set_storage: &Ident) -> Result<Toke
     let copy_t = cx.toks.copy_t();
     let eq_t = cx.toks.eq_t();
     let hash_t = cx.toks.hash_t();
+    let exact_size = cx.toks.exact_size();
     let iterator_flatten = cx.toks.iterator_flatten();
     let mem = cx.toks.mem();
     let option = cx.toks.option();
@@ -450,8 +679,8 @@ fn impl_bitset(cx: &Ctxt<'_>, en: &DataEnum, set_storage: &Ident) -> Result<Toke
 
         #[automatically_derived]
         impl #set_storage_t<#ident> for #set_storage {
-            type Iter<#lt> = #iterator_flatten<#array_into_iter<#option<#ident>, #count>>;
-            type IntoIter = #iterator_flatten<#array_into_iter<#option<#ident>, #count>>;
+            type Iter<#lt> = #exact_size<#iterator_flatten<#array_into_iter<#option<#ident>, #count>>>;
+            type IntoIter = #exact_size<#iterator_flatten<#array_into_iter<#option<#ident>, #count>>>;
 
             #[inline]
             fn empty() -> Self {
@@ -512,12 +741,14 @@ fn impl_bitset(cx: &Ctxt<'_>, en: &DataEnum, set_storage: &Ident) -> Result<Toke
 
             #[inline]
             fn iter(&self) -> Self::Iter<'_> {
-                #iterator_t::flatten(#into_iterator_t::into_iter([#(if self.data & #numbers != 0 { Some(#ident::#variants) } else { None }),*]))
+                let len = <Self as #set_storage_t<#ident>>::len(self);
+                #exact_size::new(#iterator_t::flatten(#into_iterator_t::into_iter([#(if self.data & #numbers != 0 { Some(#ident::#variants) } else { None }),*])), len)
             }
 
             #[inline]
             fn into_iter(self) -> Self::IntoIter {
-                #iterator_t::flatten(#into_iterator_t::into_iter([#(if self.data & #numbers != 0 { Some(#ident::#variants) } else { None }),*]))
+                let len = <Self as #set_storage_t<#ident>>::len(&self);
+                #exact_size::new(#iterator_t::flatten(#into_iterator_t::into_iter([#(if self.data & #numbers != 0 { Some(#ident::#variants) } else { None }),*])), len)
             }
         }
     })
@@ -539,146 +770,3 @@ fn determine_bits(cx: &Ctxt<'_>, en: &DataEnum) -> Result<(Ident, usize), ()> {
         }
     })
 }
-
-/// Implement set storage.
-fn impl_set(
-    cx: &Ctxt<'_>,
-    en: &DataEnum,
-    set_storage: &Ident,
-    names: &[Ident],
-) -> Result<TokenStream, ()> {
-    let vis = &cx.ast.vis;
-    let ident = &cx.ast.ident;
-    let lt = cx.lt;
-
-    let iterator_t = cx.toks.iterator_t();
-    let count = en.variants.len();
-    let into_iterator_t = cx.toks.into_iterator_t();
-    let array_into_iter = cx.toks.array_into_iter();
-    let clone_t = cx.toks.clone_t();
-    let copy_t = cx.toks.copy_t();
-    let eq_t = cx.toks.eq_t();
-    let hash_t = cx.toks.hash_t();
-    let iterator_cmp_bool = cx.toks.iterator_cmp_bool();
-    let iterator_flatten = cx.toks.iterator_flatten();
-    let iterator_partial_cmp_bool = cx.toks.iterator_partial_cmp_bool();
-    let mem = cx.toks.mem();
-    let option = cx.toks.option();
-    let ord_t = cx.toks.ord_t();
-    let ordering = cx.toks.ordering();
-    let partial_eq_t = cx.toks.partial_eq_t();
-    let partial_ord_t = cx.toks.partial_ord_t();
-    let set_storage_t = cx.toks.set_storage_t();
-
-    let variants = en.variants.iter().map(|v| &v.ident).collect::<Vec<_>>();
-    let init = en
-        .variants
-        .iter()
-        .map(|_| quote!(false))
-        .collect::<Vec<_>>();
-
-    Ok(quote! {
-        #[repr(transparent)]
-        #[derive(#clone_t, #copy_t, #partial_eq_t, #eq_t, #hash_t)]
-        #vis struct #set_storage {
-            data: [bool; #count],
-        }
-
-        #[automatically_derived]
-        impl #partial_ord_t for #set_storage {
-            #[inline]
-            fn partial_cmp(&self, other: &Self) -> Option<#ordering> {
-                #iterator_partial_cmp_bool(&self.data, &other.data)
-            }
-        }
-
-        #[automatically_derived]
-        impl #ord_t for #set_storage {
-            #[inline]
-            fn cmp(&self, other: &Self) -> #ordering {
-                #iterator_cmp_bool(&self.data, &other.data)
-            }
-        }
-
-        #[automatically_derived]
-        impl #set_storage_t<#ident> for #set_storage {
-            type Iter<#lt> = #iterator_flatten<#array_into_iter<#option<#ident>, #count>>;
-            type IntoIter = #iterator_flatten<#array_into_iter<#option<#ident>, #count>>;
-
-            #[inline]
-            fn empty() -> Self {
-                Self {
-                    data: [#(#init),*],
-                }
-            }
-
-            #[inline]
-            fn len(&self) -> usize {
-                let [#(#names),*] = &self.data;
-                0 #(+ usize::from(*#names))*
-            }
-
-            #[inline]
-            fn is_empty(&self) -> bool {
-                let [#(#names),*] = &self.data;
-                true #(&& !*#names)*
-            }
-
-            #[inline]
-            fn insert(&mut self, value: #ident) -> bool {
-                let [#(#names),*] = &mut self.data;
-
-                match value {
-                    #(#ident::#variants => !#mem::replace(#names, true),)*
-                }
-            }
-
-            #[inline]
-            fn contains(&self, value: #ident) -> bool {
-                let [#(#names),*] = &self.data;
-
-                match value {
-                    #(#ident::#variants => *#names,)*
-                }
-            }
-
-            #[inline]
-            fn remove(&mut self, value: #ident) -> bool {
-                let [#(#names),*] = &mut self.data;
-
-                match value {
-                    #(#ident::#variants => #mem::replace(#names, false),)*
-                }
-            }
-
-            #[inline]
-            fn retain<F>(&mut self, mut f: F)
-            where
-                F: FnMut(#ident) -> bool
-            {
-                let [#(#names),*] = &mut self.data;
-
-                #(if *#names {
-                    *#names = f(#ident::#variants);
-                })*
-            }
-
-            #[inline]
-            fn clear(&mut self) {
-                self.data = [#(#init),*];
-            }
-
-            #[inline]
-            fn iter(&self) -> Self::Iter<'_> {
-                let [#(#names),*] = &self.data;
-                #iterator_t::flatten(#into_iterator_t::into_iter([#(if *#names { Some(#ident::#variants) } else { None }),*]))
-            }
-
-            #[inline]
-            fn into_iter(self) -> Self::IntoIter {
-                let [#(#names),*] = &self.data;
-                #iterator_t::flatten(#into_iterator_t::into_iter([#(if *#names { Some(#ident::#variants) } else { None }),*]))
-            }
-        }
-    })
-}
@@ -0,0 +1,466 @@
+//! Iterator types shared by storage implementations.
+
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+
+/// An iterator adapter which keeps track of the number of remaining items.
+///
+/// This allows storage which produces its items by filtering or chaining
+/// other iterators to implement [`ExactSizeIterator`], since it already knows
+/// how many items it holds. Once it has returned `None` it always will, so it
+/// also implements [`FusedIterator`].
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Clone, Copy, Key)]
+/// enum Key {
+///     First,
+///     Second,
+///     Third,
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(Key::First, 1);
+/// map.insert(Key::Third, 3);
+///
+/// let mut it = map.values();
+/// assert_eq!(it.len(), 2);
+/// it.next();
+/// assert_eq!(it.len(), 1);
+/// ```
+#[derive(Clone)]
+pub struct ExactSize<I> {
+    iter: I,
+    len: usize,
+}
+
+impl<I> ExactSize<I> {
+    /// Wrap `iter`, which is expected to produce exactly `len` items.
+    ///
+    /// Producing any other number of items is a bug in the storage, which is
+    /// caught by debug assertions. Without them, the iterator stops after
+    /// `len` items or as soon as `iter` does, whichever comes first.
+    #[inline]
+    pub fn new(iter: I, len: usize) -> Self {
+        Self { iter, len }
+    }
+}
+
+impl<I> Iterator for ExactSize<I>
+where
+    I: Iterator,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let item = self.iter.next();
+
+        debug_assert!(
+            item.is_some(),
+            "iterator produced fewer items than its length"
+        );
+
+        let Some(item) = item else {
+            self.len = 0;
+            return None;
+        };
+
+        self.len -= 1;
+
+        debug_assert!(
+            self.len != 0 || self.iter.next().is_none(),
+            "iterator produced more items than its length"
+        );
+
+        Some(item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<I> DoubleEndedIterator for ExactSize<I>
+where
+    I: DoubleEndedIterator,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let item = self.iter.next_back();
+
+        debug_assert!(
+            item.is_some(),
+            "iterator produced fewer items than its length"
+        );
+
+        let Some(item) = item else {
+            self.len = 0;
+            return None;
+        };
+
+        self.len -= 1;
+
+        debug_assert!(
+            self.len != 0 || self.iter.next_back().is_none(),
+            "iterator produced more items than its length"
+        );
+
+        Some(item)
+    }
+}
+
+impl<I> ExactSizeIterator for ExactSize<I>
+where
+    I: Iterator,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<I> FusedIterator for ExactSize<I> where I: Iterator {}
+
+/// Describes how the items of a [`Nested`] iterator are produced.
+///
+/// Every item of the outer iterator is split into the part of the key it
+/// belongs to and an inner iterator, whose items are then joined with that
+/// part of the key. This is implemented by zero-sized types in the storage
+/// which uses [`Nested`], so that splitting and joining are resolved when the
+/// iterator is compiled.
+pub trait SplitNested<T> {
+    /// The part of the key taken from an item of the outer iterator.
+    type Key: Clone;
+
+    /// The inner iterator taken from an item of the outer iterator.
+    type Iter: Iterator;
+
+    /// The items produced by the iterator.
+    type Item;
+
+    /// Split an item of the outer iterator.
+    fn split(item: T) -> (Self::Key, Self::Iter);
+
+    /// Join an item of the inner iterator with the part of the key it
+    /// belongs to.
+    fn join(key: Self::Key, item: <Self::Iter as Iterator>::Item) -> Self::Item;
+}
+
+/// An iterator over nested storage, which yields the items of an inner
+/// iterator for every item of an outer iterator.
+///
+/// This is used by the storage for tuple keys, where the storage for the
+/// second element of the key is nested inside of the storage for the first.
+/// How items of the outer iterator are split and joined is described by
+/// [`SplitNested`].
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::Map;
+///
+/// let mut map = Map::new();
+/// map.insert((true, false), 1);
+/// map.insert((false, true), 2);
+/// map.insert((true, true), 3);
+///
+/// let mut it = map.iter();
+/// assert_eq!(it.len(), 3);
+/// assert_eq!(it.next(), Some(((false, true), &2)));
+/// assert_eq!(it.next_back(), Some(((true, true), &3)));
+/// assert_eq!(it.len(), 1);
+/// ```
+pub struct Nested<O, S>
+where
+    O: Iterator,
+    S: SplitNested<O::Item>,
+{
+    outer: O,
+    front: Option<(S::Key, S::Iter)>,
+    back: Option<(S::Key, S::Iter)>,
+    len: usize,
+    _marker: PhantomData<S>,
+}
+
+impl<O, S> Nested<O, S>
+where
+    O: Iterator,
+    S: SplitNested<O::Item>,
+{
+    /// Construct a nested iterator which is expected to produce exactly `len`
+    /// items.
+    #[inline]
+    pub(crate) fn new(outer: O, len: usize) -> Self {
+        Self {
+            outer,
+            front: None,
+            back: None,
+            len,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<O, S> Clone for Nested<O, S>
+where
+    O: Iterator + Clone,
+    S: SplitNested<O::Item>,
+    S::Iter: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            outer: self.outer.clone(),
+            front: self.front.clone(),
+            back: self.back.clone(),
+            len: self.len,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<O, S> Iterator for Nested<O, S>
+where
+    O: Iterator,
+    S: SplitNested<O::Item>,
+{
+    type Item = S::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((key, inner)) = &mut self.front {
+                if let Some(item) = inner.next() {
+                    self.len -= 1;
+                    return Some(S::join(key.clone(), item));
+                }
+
+                self.front = None;
+            }
+
+            let Some(item) = self.outer.next() else {
+                break;
+            };
+
+            self.front = Some(S::split(item));
+        }
+
+        let (key, inner) = self.back.as_mut()?;
+        let item = inner.next()?;
+        self.len -= 1;
+        Some(S::join(key.clone(), item))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<O, S> DoubleEndedIterator for Nested<O, S>
+where
+    O: DoubleEndedIterator,
+    S: SplitNested<O::Item>,
+    S::Iter: DoubleEndedIterator,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((key, inner)) = &mut self.back {
+                if let Some(item) = inner.next_back() {
+                    self.len -= 1;
+                    return Some(S::join(key.clone(), item));
+                }
+
+                self.back = None;
+            }
+
+            let Some(item) = self.outer.next_back() else {
+                break;
+            };
+
+            self.back = Some(S::split(item));
+        }
+
+        let (key, inner) = self.front.as_mut()?;
+        let item = inner.next_back()?;
+        self.len -= 1;
+        Some(S::join(key.clone(), item))
+    }
+}
+
+impl<O, S> ExactSizeIterator for Nested<O, S>
+where
+    O: Iterator,
+    S: SplitNested<O::Item>,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<O, S> FusedIterator for Nested<O, S>
+where
+    O: FusedIterator,
+    S: SplitNested<O::Item>,
+    S::Iter: FusedIterator,
+{
+}
+
+/// Describes how the items of the two halves of a [`Halves`] iterator are
+/// mapped into the items it produces.
+///
+/// This is implemented by zero-sized types in the storage which uses
+/// [`Halves`], so that the mapping is resolved when the iterator is compiled.
+pub trait MapHalves<A, B> {
+    /// The items produced by the iterator.
+    type Item;
+
+    /// Map an item of the first half.
+    fn first(item: A) -> Self::Item;
+
+    /// Map an item of the second half.
+    fn second(item: B) -> Self::Item;
+}
+
+/// An iterator over storage which is split into two halves, which yields the
+/// items of the first half followed by the items of the second.
+///
+/// This is used by the storage for [`Option`] keys, where the second half is
+/// the entry for `None`, and for [`Result`] keys, where the halves hold the
+/// `Ok` and `Err` keys. How the items of each half are mapped into the items
+/// of the whole iterator is described by [`MapHalves`].
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Map, Set};
+///
+/// let mut map = Map::new();
+/// map.insert(None, 1);
+/// map.insert(Some(true), 2);
+///
+/// let mut it = map.iter();
+/// assert_eq!(it.len(), 2);
+/// assert_eq!(it.next(), Some((Some(true), &2)));
+/// assert_eq!(it.next_back(), Some((None, &1)));
+/// assert_eq!(it.len(), 0);
+///
+/// let set = Set::from_array([Err(false), Ok(true)]);
+/// assert!(set.iter().rev().eq([Err(false), Ok(true)]));
+/// ```
+pub struct Halves<A, B, M> {
+    first: A,
+    second: B,
+    _marker: PhantomData<M>,
+}
+
+impl<A, B, M> Halves<A, B, M> {
+    /// Construct an iterator over two halves.
+    #[inline]
+    pub(crate) fn new(first: A, second: B) -> Self {
+        Self {
+            first,
+            second,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<A, B, M> Clone for Halves<A, B, M>
+where
+    A: Clone,
+    B: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            first: self.first.clone(),
+            second: self.second.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<A, B, M> Iterator for Halves<A, B, M>
+where
+    A: Iterator,
+    B: Iterator,
+    M: MapHalves<A::Item, B::Item>,
+{
+    type Item = M::Item;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(item) = self.first.next() {
+            return Some(M::first(item));
+        }
+
+        Some(M::second(self.second.next()?))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (first_lower, first_upper) = self.first.size_hint();
+        let (second_lower, second_upper) = self.second.size_hint();
+
+        let lower = first_lower.saturating_add(second_lower);
+
+        let upper = match (first_upper, second_upper) {
+            (Some(first), Some(second)) => first.checked_add(second),
+            _ => None,
+        };
+
+        (lower, upper)
+    }
+}
+
+impl<A, B, M> DoubleEndedIterator for Halves<A, B, M>
+where
+    A: DoubleEndedIterator,
+    B: DoubleEndedIterator,
+    M: MapHalves<A::Item, B::Item>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if let Some(item) = self.second.next_back() {
+            return Some(M::second(item));
+        }
+
+        Some(M::first(self.first.next_back()?))
+    }
+}
+
+impl<A, B, M> ExactSizeIterator for Halves<A, B, M>
+where
+    A: ExactSizeIterator,
+    B: ExactSizeIterator,
+    M: MapHalves<A::Item, B::Item>,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.first.len() + self.second.len()
+    }
+}
+
+impl<A, B, M> FusedIterator for Halves<A, B, M>
+where
+    A: FusedIterator,
+    B: FusedIterator,
+    M: MapHalves<A::Item, B::Item>,
+{
+}
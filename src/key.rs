@@ -1,11 +1,20 @@
 //! Module for the trait to define a `Key`.
 
+use core::iter::Copied;
+use core::slice;
+
 #[cfg(feature = "hashbrown")]
 use crate::map::storage::HashbrownMapStorage;
-use crate::map::storage::{BooleanMapStorage, MapStorage, OptionMapStorage, SingletonMapStorage};
+use crate::map::storage::{
+    BooleanMapStorage, ByteMapStorage, MapStorage, OptionMapStorage, ResultMapStorage,
+    SingletonMapStorage, TupleMapStorage,
+};
 #[cfg(feature = "hashbrown")]
 use crate::set::storage::HashbrownSetStorage;
-use crate::set::storage::{BooleanSetStorage, OptionSetStorage, SetStorage, SingletonSetStorage};
+use crate::set::storage::{
+    BooleanSetStorage, ByteSetStorage, OptionSetStorage, ResultSetStorage, SetStorage,
+    SingletonSetStorage, TupleSetStorage,
+};
 
 /// The trait for a key that can be used to store values in a
 /// [`Map`][crate::Set] or [`Set`][crate::Set].
@@ -56,6 +65,53 @@ use crate::set::storage::{BooleanSetStorage, OptionSetStorage, SetStorage, Singl
 /// }
 /// ```
 ///
+/// The same dynamic storage allows primitive types such as `char`, `u32` or
+/// `&'static str` to be used as keys directly, without wrapping them in an
+/// enum. Their storage is a hash map, so it only takes up memory proportional
+/// to the number of entries in it, rather than reserving a slot for each of
+/// the (more than a million) possible `char` values:
+///
+/// ```
+/// # #[cfg(feature = "hashbrown")] {
+/// use fixed_map::Map;
+///
+/// let mut map = Map::new();
+/// map.insert('a', 1);
+/// map.insert('ö', 2);
+///
+/// assert_eq!(map.get('a'), Some(&1));
+/// assert_eq!(map.get('ö'), Some(&2));
+/// assert_eq!(map.get('b'), None);
+/// # }
+/// ```
+///
+/// Keys are passed to and returned from maps and sets by value, so they have
+/// to be [`Clone`]. Keys which only have unit variants also have to be
+/// [`Copy`], but keys with dynamic storage don't, so owned types such as
+/// `String` can be used as keys or as fields in a derived key:
+///
+/// ```
+/// # #[cfg(feature = "hashbrown")] {
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, PartialEq, Key)]
+/// enum MyKey {
+///     First(String),
+///     Second,
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(MyKey::First(String::from("a")), 1);
+/// map.insert(MyKey::Second, 2);
+///
+/// assert_eq!(map.get(MyKey::First(String::from("a"))), Some(&1));
+/// assert!(map.keys().eq([MyKey::First(String::from("a")), MyKey::Second]));
+/// # }
+/// ```
+///
+/// Such keys are cloned whenever a map or set hands out a key, for example
+/// while iterating over it.
+///
 /// ## Ordering
 ///
 /// Keys provide their own ordering semantics instead of relying on the
@@ -118,11 +174,38 @@ use crate::set::storage::{BooleanSetStorage, OptionSetStorage, SetStorage, Singl
 /// assert!(a < c);
 /// ```
 ///
+/// Iteration follows the same order. For a key which only has unit variants,
+/// every iterator over a [`Map`] or [`Set`] - including `keys`, `values`, their
+/// mutable counterparts and `into_iter` - is guaranteed to visit entries in
+/// declaration order, regardless of the order in which they were inserted.
+/// The exception are keys which are [indexed by
+/// discriminant](derive@crate::Key#indexing-by-discriminant), whose entries are
+/// visited in the order of their discriminants.
+///
+/// ```
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Key {
+///     First,
+///     Second,
+///     Third,
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(Key::Third, 3);
+/// map.insert(Key::First, 1);
+/// map.insert(Key::Second, 2);
+///
+/// assert!(map.keys().eq([Key::First, Key::Second, Key::Third]));
+/// assert!(map.into_iter().eq([(Key::First, 1), (Key::Second, 2), (Key::Third, 3)]));
+/// ```
+///
 /// [`BTreeMap`]: https://doc.rust-lang.org/std/collections/struct.BTreeMap.html
 /// [`BTreeSet`]: https://doc.rust-lang.org/std/collections/struct.BTreeSet.html
 /// [`Map`]: crate::Map
 /// [`Set`]: crate::Set
-pub trait Key: Copy {
+pub trait Key: Clone {
     /// The [`Map`] storage implementation to use for the key implementing
     /// this trait.
     type MapStorage<V>: MapStorage<Self, V>;
@@ -132,11 +215,140 @@ pub trait Key: Copy {
     type SetStorage: SetStorage<Self>;
 }
 
+/// A [`Key`] which can only inhabit a fixed number of values.
+///
+/// This is implemented by `#[derive(Key)]` for enums where every variant is a
+/// unit variant. It is not implemented for keys with fields or for keys which
+/// use dynamic storage, since they don't have a meaningful fixed number of
+/// values. Unlike other keys, keys with a fixed number of values are always
+/// [`Copy`].
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::Key;
+/// use fixed_map::key::FixedKey;
+///
+/// #[derive(Clone, Copy, Key)]
+/// enum MyKey {
+///     First,
+///     Second,
+///     Third,
+/// }
+///
+/// assert_eq!(MyKey::COUNT, 3);
+///
+/// // Size parallel arrays by the number of keys.
+/// let weights = [0.0f32; MyKey::COUNT];
+/// assert_eq!(weights.len(), 3);
+/// ```
+///
+/// Keys with fields don't implement it:
+///
+/// ```compile_fail
+/// use fixed_map::Key;
+/// use fixed_map::key::FixedKey;
+///
+/// #[derive(Clone, Copy, Key)]
+/// enum MyKey {
+///     First(bool),
+///     Second,
+/// }
+///
+/// let _ = MyKey::COUNT;
+/// ```
+pub trait FixedKey: Key + Copy {
+    /// The number of values the key can inhabit.
+    const COUNT: usize;
+
+    /// Iterate over every value the key can inhabit, in declaration order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    /// use fixed_map::key::FixedKey;
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    ///     Third,
+    /// }
+    ///
+    /// assert!(MyKey::keys().eq([MyKey::First, MyKey::Second, MyKey::Third]));
+    ///
+    /// let map: Map<MyKey, u32> = MyKey::keys().map(|k| (k, 0)).collect();
+    /// assert_eq!(map.len(), MyKey::COUNT);
+    /// ```
+    fn keys() -> Copied<slice::Iter<'static, Self>>
+    where
+        Self: 'static;
+
+    /// The position of the key in declaration order, starting at `0`.
+    ///
+    /// This is stable for as long as the variants of the key aren't reordered,
+    /// which makes it suitable as a compact identifier for the key. For keys
+    /// which are [indexed by
+    /// discriminant](derive@crate::Key#indexing-by-discriminant) it is the
+    /// discriminant instead, which is the slot the key is stored in, so
+    /// indexes can be sparse and aren't bounded by [`FixedKey::COUNT`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::Key;
+    /// use fixed_map::key::FixedKey;
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    ///     Third,
+    /// }
+    ///
+    /// assert_eq!(MyKey::First.index(), 0);
+    /// assert_eq!(MyKey::Third.index(), 2);
+    /// ```
+    fn index(self) -> usize;
+
+    /// Construct the key with the given index, or `None` if no key has it.
+    ///
+    /// This is the inverse of [`FixedKey::index`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::Key;
+    /// use fixed_map::key::FixedKey;
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum MyKey {
+    ///     First,
+    ///     Second,
+    ///     Third,
+    /// }
+    ///
+    /// assert_eq!(MyKey::from_index(1), Some(MyKey::Second));
+    /// assert_eq!(MyKey::from_index(3), None);
+    ///
+    /// for key in MyKey::keys() {
+    ///     assert_eq!(MyKey::from_index(key.index()), Some(key));
+    /// }
+    /// ```
+    fn from_index(index: usize) -> Option<Self>;
+}
+
 impl Key for bool {
     type MapStorage<V> = BooleanMapStorage<V>;
     type SetStorage = BooleanSetStorage;
 }
 
+impl Key for u8 {
+    type MapStorage<V> = ByteMapStorage<V>;
+    type SetStorage = ByteSetStorage;
+}
+
 impl<K> Key for Option<K>
 where
     K: Key,
@@ -145,6 +357,77 @@ where
     type SetStorage = OptionSetStorage<K>;
 }
 
+/// Results are keys, where entries with an `Ok` key are stored separately from
+/// entries with an `Err` key. All `Ok` entries are iterated over before all
+/// `Err` entries.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Success {
+///     Created,
+///     Updated,
+/// }
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Failure {
+///     NotFound,
+///     Conflict,
+/// }
+///
+/// let mut outcomes = Map::new();
+/// outcomes.insert(Err(Failure::NotFound), 3);
+/// outcomes.insert(Ok(Success::Updated), 2);
+/// outcomes.insert(Ok(Success::Created), 1);
+///
+/// assert_eq!(outcomes.get(Err(Failure::NotFound)), Some(&3));
+/// assert_eq!(outcomes.get(Err(Failure::Conflict)), None);
+/// assert!(outcomes.keys().eq([Ok(Success::Created), Ok(Success::Updated), Err(Failure::NotFound)]));
+/// ```
+impl<T, E> Key for Result<T, E>
+where
+    T: Key,
+    E: Key,
+{
+    type MapStorage<V> = ResultMapStorage<T, E, V>;
+    type SetStorage = ResultSetStorage<T, E>;
+}
+
+/// Tuples of two keys are keys, where the storage for the second element is
+/// nested inside of the storage for the first. Entries are iterated over in
+/// lexicographic order, first by the first element and then by the second.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum State {
+///     Idle,
+///     Running,
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert((State::Running, false), 1);
+/// map.insert((State::Idle, true), 2);
+/// map.insert((State::Running, true), 3);
+///
+/// assert_eq!(map.get((State::Idle, true)), Some(&2));
+/// assert!(map.keys().eq([(State::Idle, true), (State::Running, false), (State::Running, true)]));
+/// ```
+impl<A, B> Key for (A, B)
+where
+    A: Key,
+    B: Key,
+{
+    type MapStorage<V> = TupleMapStorage<A, B, V>;
+    type SetStorage = TupleSetStorage<A, B>;
+}
+
 macro_rules! map_key {
     ($ty:ty) => {
         #[cfg(feature = "hashbrown")]
@@ -165,7 +448,6 @@ macro_rules! singleton_key {
 }
 
 map_key!(char);
-map_key!(u8);
 map_key!(u32);
 map_key!(u64);
 map_key!(u128);
@@ -177,4 +459,5 @@ map_key!(i128);
 map_key!(isize);
 map_key!(&'static str);
 map_key!(&'static [u8]);
+map_key!(alloc::string::String);
 singleton_key!(());
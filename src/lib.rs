@@ -63,21 +63,35 @@
 //!
 //! The following features are available:
 //!
-//! * `std` - Disabling this feature causes this crate to be no-std. This means
-//!   that dynamic types cannot be used in keys, like ones enabled by the `map`
-//!   feature (default).
+//! * `std` - Enables `std` support in dependencies, and implies `alloc`
+//!   (default). This crate itself is always `no_std`.
+//! * `alloc` - Enables storage which requires an allocator, such as the
+//!   [`BTreeMap`]-backed storage used by `#[key(ordered)]` (default through
+//!   `std`).
 //! * `hashbrown` - Causes [`Storage`] to be implemented by dynamic types such
-//!   as `&'static str` or `u32`. These are backed by a `hashbrown` (default).
+//!   as `&'static str`, `String` or `u32`. These are backed by a `hashbrown`
+//!   and imply `alloc` (default).
 //! * `entry` - Enables an [`entry`] API similar to that found on [`HashMap`].
 //! * `serde` - Causes [`Map`] and [`Set`] to implement [`Serialize`] and
 //!   [`Deserialize`] if it's implemented by the key and value.
+//! * `rayon` - Enables parallel iteration with [`Map::par_iter`] and
+//!   [`Set::par_iter`] for unit enum keys and dynamic keys backed by `hashbrown`,
+//!   as well as [`Map`] with `u8` keys. Implies `std`.
+//! * `arbitrary` - Causes [`Map`] and [`Set`] to implement [`Arbitrary`] if it's
+//!   implemented by the key and value, which is useful for fuzzing.
+//! * `proptest` - Provides strategies for generating [`Map`] and [`Set`] in the
+//!   [`fixed_map::proptest`] module. Implies `std`.
+//!
+//! With default features disabled the crate doesn't need an allocator. Derived
+//! keys, as well as `bool`, `u8`, `Option<K>` and `()`, are all stored inline.
 //!
 //! <br>
 //!
 //! ## Specialized storage through the [`Key`] trait
 //!
 //! The [`Key` derive] is provided to instruct our containers on how to build
-//! optimized storage for a given [`Key`]. We also require any key to be [`Copy`].
+//! optimized storage for a given [`Key`]. We also require any key to be [`Clone`],
+//! and keys which only have unit variants to be [`Copy`].
 //!
 //! ```
 //! use fixed_map::Key;
@@ -269,13 +283,19 @@
 //! ls target/release/examples/
 //! ```
 //!
+//! [`Arbitrary`]: https://docs.rs/arbitrary/1/arbitrary/trait.Arbitrary.html
+//! [`BTreeMap`]: https://doc.rust-lang.org/stable/alloc/collections/btree_map/struct.BTreeMap.html
+//! [`Clone`]: https://doc.rust-lang.org/std/clone/trait.Clone.html
 //! [`Copy`]: https://doc.rust-lang.org/std/marker/trait.Copy.html
 //! [`Deserialize`]: https://docs.rs/serde/1/serde/trait.Deserialize.html
 //! [`hashbrown`]: https://github.com/Amanieu/hashbrown
 //! [`Key` derive]: https://docs.rs/fixed-map/latest/fixed_map/derive.Key.html
 //! [`Key`]: https://docs.rs/fixed-map/latest/fixed_map/derive.Key.html
 //! [`Map`]: https://docs.rs/fixed-map/latest/fixed_map/map/struct.Map.html
+//! [`Map::par_iter`]: https://docs.rs/fixed-map/latest/fixed_map/map/struct.Map.html#method.par_iter
+//! [`Set::par_iter`]: https://docs.rs/fixed-map/latest/fixed_map/set/struct.Set.html#method.par_iter
 //! [`entry`]: https://docs.rs/fixed-map/latest/fixed_map/map/struct.Map.html#method.entry
+//! [`fixed_map::proptest`]: https://docs.rs/fixed-map/latest/fixed_map/proptest/index.html
 //! [`HashMap`]: https://doc.rust-lang.org/stable/std/collections/hash_map/struct.HashMap.html#method.entry
 //! [`Serialize`]: https://docs.rs/serde/1/serde/trait.Serialize.html
 //! [`Set`]: https://docs.rs/fixed-map/latest/fixed_map/set/struct.Set.html
@@ -298,7 +318,6 @@
     missing_copy_implementations,
     non_ascii_idents,
     noop_method_call,
-    pointer_structural_match,
     single_use_lifetimes,
     trivial_casts,
     trivial_numeric_casts,
@@ -308,7 +327,6 @@
     unused_lifetimes,
     unused_macro_rules,
     unused_qualifications,
-    unused_tuple_struct_fields,
     variant_size_differences,
     // Enable pedantic clippy lints
     clippy::pedantic,
@@ -329,6 +347,14 @@
     clippy::expl_impl_clone_on_copy
 )]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+extern crate std;
+
+pub mod iter;
+
 pub mod key;
 #[doc(inline)]
 pub use fixed_map_derive::*;
@@ -341,6 +367,11 @@ pub mod set;
 #[doc(inline)]
 pub use self::set::Set;
 
+pub mod observe;
+
+#[cfg(feature = "proptest")]
+pub mod proptest;
+
 // Re-export the option bucket types for use in `derive(Key)`
 #[doc(hidden)]
 pub mod option_bucket;
@@ -18,11 +18,11 @@ fn flatten<T>(value: (usize, &Option<T>)) -> Option<(usize, &T)> {
 
 /// `partial_cmp` implementation over iterators which ensures that storage
 /// ordering between `None` and `Some` is handled in a reasonable manner.
-pub fn __storage_iterator_partial_cmp<'a, A, B, T: 'a>(a: A, b: B) -> Option<Ordering>
+pub fn __storage_iterator_partial_cmp<'a, A, B, T>(a: A, b: B) -> Option<Ordering>
 where
     A: IntoIterator<Item = &'a Option<T>>,
     B: IntoIterator<Item = &'a Option<T>>,
-    T: PartialOrd<T>,
+    T: 'a + PartialOrd<T>,
 {
     let a = a.into_iter().enumerate().filter_map(flatten);
     let b = b.into_iter().enumerate().filter_map(flatten);
@@ -31,42 +31,83 @@ where
 
 /// `cmp` implementation over iterators which ensures that storage ordering
 /// between `None` and `Some` is handled in a reasonable manner.
-pub fn __storage_iterator_cmp<'a, A, B, T: 'a>(a: A, b: B) -> Ordering
+pub fn __storage_iterator_cmp<'a, A, B, T>(a: A, b: B) -> Ordering
 where
     A: IntoIterator<Item = &'a Option<T>>,
     B: IntoIterator<Item = &'a Option<T>>,
-    T: Ord,
+    T: 'a + Ord,
 {
     let a = a.into_iter().enumerate().filter_map(flatten);
     let b = b.into_iter().enumerate().filter_map(flatten);
     a.cmp(b)
 }
 
-#[inline]
-fn filter_bool(&(_, value): &(usize, &bool)) -> bool {
-    *value
-}
+#[cfg(feature = "rayon")]
+#[doc(hidden)]
+pub use rayon;
 
-/// `partial_cmp` implementation over iterators which ensures that storage
-/// ordering between `false` and `true` is handled in a reasonable manner.
-pub fn __storage_iterator_partial_cmp_bool<'a, A, B>(a: A, b: B) -> Option<Ordering>
-where
-    A: IntoIterator<Item = &'a bool>,
-    B: IntoIterator<Item = &'a bool>,
-{
-    let a = a.into_iter().enumerate().filter(filter_bool);
-    let b = b.into_iter().enumerate().filter(filter_bool);
-    a.partial_cmp(b)
+/// Implement `ParMapStorage` for the map storage of an enum with only unit
+/// variants.
+///
+/// This is a macro so that `derive(Key)` doesn't need to know whether the
+/// `rayon` feature is enabled.
+#[cfg(feature = "rayon")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __par_map_storage {
+    ($map_storage:ident, $key:ty, $count:expr, [$($variant:expr),* $(,)?]) => {
+        #[automatically_derived]
+        impl<V> $crate::map::ParMapStorage<$key, V> for $map_storage<V>
+        where
+            V: ::core::marker::Sync,
+        {
+            type ParIter<'this> = $crate::macro_support::rayon::iter::FilterMap<
+                $crate::macro_support::rayon::iter::Zip<
+                    $crate::macro_support::rayon::iter::Copied<$crate::macro_support::rayon::slice::Iter<'static, $key>>,
+                    $crate::macro_support::rayon::slice::Iter<'this, ::core::option::Option<V>>,
+                >,
+                fn(($key, &'this ::core::option::Option<V>)) -> ::core::option::Option<($key, &'this V)>,
+            > where V: 'this;
+            type ParKeys<'this> = $crate::macro_support::rayon::iter::FilterMap<
+                $crate::macro_support::rayon::iter::Zip<
+                    $crate::macro_support::rayon::iter::Copied<$crate::macro_support::rayon::slice::Iter<'static, $key>>,
+                    $crate::macro_support::rayon::slice::Iter<'this, ::core::option::Option<V>>,
+                >,
+                fn(($key, &'this ::core::option::Option<V>)) -> ::core::option::Option<$key>,
+            > where V: 'this;
+            type ParValues<'this> = $crate::macro_support::rayon::iter::Flatten<
+                $crate::macro_support::rayon::slice::Iter<'this, ::core::option::Option<V>>,
+            > where V: 'this;
+
+            #[inline]
+            fn par_iter(&self) -> Self::ParIter<'_> {
+                use $crate::macro_support::rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
+                static KEYS: [$key; $count] = [$($variant),*];
+                let map: fn(_) -> _ = |(k, v): ($key, &::core::option::Option<V>)| ::core::option::Option::Some((k, ::core::option::Option::as_ref(v)?));
+                KEYS.par_iter().copied().zip(self.data.par_iter()).filter_map(map)
+            }
+
+            #[inline]
+            fn par_keys(&self) -> Self::ParKeys<'_> {
+                use $crate::macro_support::rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
+                static KEYS: [$key; $count] = [$($variant),*];
+                let map: fn(_) -> _ = |(k, v): ($key, &::core::option::Option<V>)| ::core::option::Option::is_some(v).then_some(k);
+                KEYS.par_iter().copied().zip(self.data.par_iter()).filter_map(map)
+            }
+
+            #[inline]
+            fn par_values(&self) -> Self::ParValues<'_> {
+                use $crate::macro_support::rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+                self.data.par_iter().flatten()
+            }
+        }
+    };
 }
 
-/// `cmp` implementation over iterators which ensures that storage ordering
-/// between `false` and `true` is handled in a reasonable manner.
-pub fn __storage_iterator_cmp_bool<'a, A, B>(a: A, b: B) -> Ordering
-where
-    A: IntoIterator<Item = &'a bool>,
-    B: IntoIterator<Item = &'a bool>,
-{
-    let a = a.into_iter().enumerate().filter(filter_bool);
-    let b = b.into_iter().enumerate().filter(filter_bool);
-    a.cmp(b)
+/// Without the `rayon` feature there is nothing to implement.
+#[cfg(not(feature = "rayon"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __par_map_storage {
+    ($($tt:tt)*) => {};
 }
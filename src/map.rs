@@ -1,16 +1,36 @@
 //! Contains the fixed [`Map`] implementation.
 
+mod diff;
+pub use self::diff::{Change, DiffIter, MapDiff};
+
+mod display;
+pub use self::display::Display;
+
 mod entry;
 pub use self::entry::Entry;
 
-pub(crate) mod storage;
-pub use self::storage::{MapStorage, OccupiedEntry, VacantEntry};
+mod occupied_error;
+pub use self::occupied_error::OccupiedError;
+
+mod snapshot;
+pub use self::snapshot::Snapshot;
+
+pub mod storage;
+#[cfg(feature = "rayon")]
+pub use self::storage::ParMapStorage;
+pub use self::storage::{ConstMapStorage, MapStorage, OccupiedEntry, VacantEntry};
 
+#[cfg(feature = "hashbrown")]
+use core::borrow::Borrow;
 use core::cmp::{Ord, Ordering, PartialOrd};
 use core::fmt;
 use core::hash::{Hash, Hasher};
+use core::iter::Sum;
+use core::mem;
+use core::ops::AddAssign;
 
-use crate::key::Key;
+use crate::key::{FixedKey, Key};
+use crate::set::Set;
 
 /// The iterator produced by [`Map::iter`].
 pub type Iter<'a, K, V> = <<K as Key>::MapStorage<V> as MapStorage<K, V>>::Iter<'a>;
@@ -30,6 +50,18 @@ pub type ValuesMut<'a, K, V> = <<K as Key>::MapStorage<V> as MapStorage<K, V>>::
 /// The iterator produced by [`Map::into_iter`].
 pub type IntoIter<K, V> = <<K as Key>::MapStorage<V> as MapStorage<K, V>>::IntoIter;
 
+/// The parallel iterator produced by [`Map::par_iter`].
+#[cfg(feature = "rayon")]
+pub type ParIter<'a, K, V> = <<K as Key>::MapStorage<V> as ParMapStorage<K, V>>::ParIter<'a>;
+
+/// The parallel iterator produced by [`Map::par_keys`].
+#[cfg(feature = "rayon")]
+pub type ParKeys<'a, K, V> = <<K as Key>::MapStorage<V> as ParMapStorage<K, V>>::ParKeys<'a>;
+
+/// The parallel iterator produced by [`Map::par_values`].
+#[cfg(feature = "rayon")]
+pub type ParValues<'a, K, V> = <<K as Key>::MapStorage<V> as ParMapStorage<K, V>>::ParValues<'a>;
+
 /// A fixed map with storage specialized through the [`Key`] trait.
 ///
 /// # Examples
@@ -160,8 +192,12 @@ where
 impl<K, V> Map<K, V>
 where
     K: Key,
+    K::MapStorage<V>: ConstMapStorage<K, V>,
 {
-    /// Creates an empty [`Map`].
+    /// An empty [`Map`] which can be used in `const` and `static` items.
+    ///
+    /// This is available for keys whose storage doesn't allocate, such as
+    /// enums with unit variants.
     ///
     /// # Examples
     ///
@@ -174,456 +210,2215 @@ where
     ///     Two,
     /// }
     ///
-    /// let mut map: Map<Key, i32> = Map::new();
+    /// static TABLE: Map<Key, u32> = Map::EMPTY;
+    ///
+    /// assert!(TABLE.is_empty());
+    /// assert_eq!(TABLE.get(Key::One), None);
     /// ```
-    #[inline]
-    #[must_use]
-    pub fn new() -> Map<K, V> {
-        Map {
-            storage: K::MapStorage::empty(),
-        }
-    }
+    pub const EMPTY: Self = Map {
+        storage: <K::MapStorage<V> as ConstMapStorage<K, V>>::EMPTY,
+    };
+}
 
-    /// An iterator visiting all key-value pairs in arbitrary order.
-    /// The iterator element type is `(K, &'a V)`.
+impl<K, V> Map<K, V>
+where
+    K: FixedKey + 'static,
+{
+    /// Creates a [`Map`] where every possible key is populated with the value
+    /// returned by `f`.
+    ///
+    /// The function is called once for each key in declaration order. This is
+    /// only available for keys which implement [`FixedKey`], such as enums with
+    /// unit variants.
     ///
     /// # Examples
     ///
     /// ```
     /// use fixed_map::{Key, Map};
     ///
-    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
     /// enum Key {
     ///     One,
     ///     Two,
-    ///     Three,
     /// }
     ///
-    /// let mut map = Map::new();
-    /// map.insert(Key::One, 1);
-    /// map.insert(Key::Two, 2);
+    /// let map = Map::from_fn(|key| match key {
+    ///     Key::One => 1,
+    ///     Key::Two => 2,
+    /// });
     ///
-    /// assert_eq!(map.iter().collect::<Vec<_>>(), vec![(Key::One, &1), (Key::Two, &2)]);
+    /// assert_eq!(map.get(Key::One), Some(&1));
+    /// assert_eq!(map.get(Key::Two), Some(&2));
+    /// assert_eq!(map.len(), 2);
     /// ```
     #[inline]
-    pub fn iter(&self) -> Iter<'_, K, V> {
-        self.storage.iter()
+    pub fn from_fn<F>(mut f: F) -> Map<K, V>
+    where
+        F: FnMut(K) -> V,
+    {
+        let mut map = Map::new();
+
+        for key in K::keys() {
+            map.insert(key, f(key));
+        }
+
+        map
     }
 
-    /// An iterator visiting all keys in arbitrary order.
-    /// The iterator element type is `K`.
+    /// Creates a [`Map`] where every possible key is populated with
+    /// `V::default()`.
+    ///
+    /// Unlike [`Map::default`], which creates an empty map, the returned map
+    /// contains an entry for each key. This is useful for maps of counters
+    /// which should all start out at zero.
     ///
     /// # Examples
     ///
     /// ```
     /// use fixed_map::{Key, Map};
     ///
-    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
-    /// pub enum Key {
-    ///     First,
-    ///     Second,
-    ///     Third,
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
     /// }
     ///
-    /// let mut map = Map::new();
-    /// map.insert(Key::First, 1);
-    /// map.insert(Key::Second, 2);
+    /// let mut map: Map<Key, u32> = Map::default_filled();
+    /// assert_eq!(map.len(), 2);
+    /// assert_eq!(map.get(Key::Two), Some(&0));
     ///
-    /// assert!(map.keys().eq([Key::First, Key::Second]));
-    /// assert!(map.keys().rev().eq([Key::Second, Key::First]));
+    /// if let Some(count) = map.get_mut(Key::One) {
+    ///     *count += 1;
+    /// }
+    ///
+    /// assert_eq!(map.get(Key::One), Some(&1));
+    /// assert!(Map::<Key, u32>::default().is_empty());
     /// ```
+    #[inline]
+    #[must_use]
+    pub fn default_filled() -> Map<K, V>
+    where
+        V: Default,
+    {
+        Map::from_fn(|_| V::default())
+    }
+}
+
+/// Implement borrowed lookups for a dynamic key which is a reference or an
+/// owned type.
+macro_rules! get_borrowed {
+    ($ty:ty, $borrowed:ty, $example:literal) => {
+        #[cfg(feature = "hashbrown")]
+        impl<V> Map<$ty, V> {
+            #[doc = concat!("Returns a reference to the value corresponding to a borrowed form of the key, such as a `", stringify!($borrowed), "` which doesn't live for `'static`.")]
+            ///
+            /// This mirrors how `HashMap::get` accepts any borrowed form of the
+            /// key, and avoids having to produce an owned key or a key with a
+            /// `'static` lifetime only to perform a lookup.
+            ///
+            /// This is only available when the key of the map is the dynamic
+            /// type itself, not when it is the field of a derived key.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use fixed_map::Map;
+            ///
+            /// let mut map = Map::new();
+            #[doc = concat!("map.insert(", $example, ", 1);")]
+            ///
+            #[doc = concat!("let owned = ", $example, ".to_owned();")]
+            /// assert_eq!(map.get_borrowed(&owned[..]), Some(&1));
+            /// assert_eq!(map.get_borrowed(&owned[1..]), None);
+            /// ```
+            #[inline]
+            pub fn get_borrowed<Q>(&self, key: &Q) -> Option<&V>
+            where
+                $ty: Borrow<Q>,
+                Q: ?Sized + Eq + Hash,
+            {
+                self.storage.get_borrowed(key)
+            }
+        }
+    };
+}
+
+get_borrowed!(&'static str, &str, "\"first\"");
+get_borrowed!(&'static [u8], &[u8], "b\"first\".as_slice()");
+get_borrowed!(alloc::string::String, &str, "String::from(\"first\")");
+
+impl<K, V> Map<Option<K>, V>
+where
+    K: Key,
+{
+    /// Returns how the entries of a map keyed by [`Option`] are split between
+    /// its two halves: whether the `None` key is present, and the number of
+    /// `Some` keys present.
     ///
-    /// Using a composite key:
+    /// The `None` entry is stored separately from the `Some` entries, so this
+    /// doesn't need to iterate over the map.
+    ///
+    /// # Examples
     ///
     /// ```
     /// use fixed_map::{Key, Map};
     ///
-    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
-    /// pub enum Key {
-    ///     First,
-    ///     Second(bool),
-    ///     Third,
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Part {
+    ///     One,
+    ///     Two,
     /// }
     ///
     /// let mut map = Map::new();
-    /// map.insert(Key::First, 1);
-    /// map.insert(Key::Second(false), 2);
+    /// assert_eq!(map.occupancy(), (false, 0));
     ///
-    /// dbg!(map.keys().collect::<Vec<_>>());
+    /// map.insert(Some(Part::One), 1);
+    /// map.insert(Some(Part::Two), 2);
+    /// assert_eq!(map.occupancy(), (false, 2));
     ///
-    /// assert!(map.keys().eq([Key::First, Key::Second(false)]));
-    /// assert!(map.keys().rev().eq([Key::Second(false), Key::First]));
+    /// map.insert(None, 3);
+    /// assert_eq!(map.occupancy(), (true, 2));
     /// ```
     #[inline]
-    pub fn keys(&self) -> Keys<'_, K, V> {
-        self.storage.keys()
+    pub fn occupancy(&self) -> (bool, usize) {
+        self.storage.occupancy()
     }
+}
 
-    /// An iterator visiting all values in arbitrary order.
-    /// The iterator element type is `&'a V`.
+#[cfg(feature = "rayon")]
+impl<K, V> Map<K, V>
+where
+    K: Key,
+    K::MapStorage<V>: ParMapStorage<K, V>,
+{
+    /// A parallel iterator visiting all key-value pairs.
+    ///
+    /// This requires the `rayon` feature.
     ///
     /// # Examples
     ///
     /// ```
     /// use fixed_map::{Key, Map};
+    /// use rayon::prelude::*;
     ///
-    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
-    /// pub enum Key {
-    ///     First,
-    ///     Second,
-    ///     Third,
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    ///     Three,
     /// }
     ///
     /// let mut map = Map::new();
-    /// map.insert(Key::First, 1);
-    /// map.insert(Key::Second, 2);
+    /// map.insert(Key::One, 1);
+    /// map.insert(Key::Three, 3);
     ///
-    /// assert!(map.values().copied().eq([1, 2]));
-    /// assert!(map.values().rev().copied().eq([2, 1]));
+    /// let sum: i32 = map.par_iter().map(|(_, v)| *v).sum();
+    /// assert_eq!(sum, 4);
     /// ```
+    #[inline]
+    pub fn par_iter(&self) -> ParIter<'_, K, V> {
+        self.storage.par_iter()
+    }
+
+    /// A parallel iterator visiting all keys.
     ///
-    /// Using a composite key:
+    /// This requires the `rayon` feature.
     ///
-    /// ```
-    /// use fixed_map::{Key, Map};
+    /// # Examples
     ///
-    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
-    /// pub enum Key {
-    ///     First(bool),
-    ///     Second,
-    ///     Third,
-    /// }
+    /// ```
+    /// # #[cfg(feature = "hashbrown")] {
+    /// use fixed_map::Map;
+    /// use rayon::prelude::*;
     ///
     /// let mut map = Map::new();
-    /// map.insert(Key::First(false), 1);
-    /// map.insert(Key::Second, 2);
+    /// map.insert(1u32, "a");
+    /// map.insert(2u32, "b");
     ///
-    /// assert!(map.values().copied().eq([1, 2]));
-    /// assert!(map.values().rev().copied().eq([2, 1]));
+    /// let sum: u32 = map.par_keys().sum();
+    /// assert_eq!(sum, 3);
+    /// # }
     /// ```
     #[inline]
-    pub fn values(&self) -> Values<'_, K, V> {
-        self.storage.values()
+    pub fn par_keys(&self) -> ParKeys<'_, K, V> {
+        self.storage.par_keys()
     }
 
-    /// An iterator visiting all key-value pairs in arbitrary order,
-    /// with mutable references to the values.
-    /// The iterator element type is `(K, &'a mut V)`.
+    /// A parallel iterator visiting all values.
+    ///
+    /// This requires the `rayon` feature.
     ///
     /// # Examples
     ///
     /// ```
-    /// use fixed_map::{Key, Map};
-    ///
-    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
-    /// enum Key {
-    ///     First,
-    ///     Second,
-    /// }
+    /// use fixed_map::Map;
+    /// use rayon::prelude::*;
     ///
     /// let mut map = Map::new();
-    /// map.insert(Key::First, 1);
-    /// map.insert(Key::Second, 2);
-    ///
-    /// // Update all values
-    /// for (_, val) in map.iter_mut() {
-    ///     *val *= 2;
-    /// }
+    /// map.insert(3u8, 30);
+    /// map.insert(7u8, 70);
     ///
-    /// assert!(map.iter().eq([(Key::First, &2), (Key::Second, &4)]));
+    /// let max = map.par_values().copied().max();
+    /// assert_eq!(max, Some(70));
     /// ```
+    #[inline]
+    pub fn par_values(&self) -> ParValues<'_, K, V> {
+        self.storage.par_values()
+    }
+}
+
+impl<K, V> Map<K, V>
+where
+    K: Key,
+{
+    /// Creates an empty [`Map`].
     ///
-    /// Using a composite key:
+    /// # Examples
     ///
     /// ```
     /// use fixed_map::{Key, Map};
     ///
-    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// #[derive(Clone, Copy, Key)]
     /// enum Key {
-    ///     First(bool),
-    ///     Second,
+    ///     One,
+    ///     Two,
     /// }
     ///
-    /// let mut map = Map::new();
-    /// map.insert(Key::First(true), 1);
-    /// map.insert(Key::Second, 2);
+    /// let mut map: Map<Key, i32> = Map::new();
+    /// ```
     ///
-    /// // Update all values
-    /// for (_, val) in map.iter_mut() {
-    ///     *val *= 2;
+    /// To construct an empty map in a `const` or `static` item, use
+    /// [`Map::EMPTY`].
+    #[inline]
+    #[must_use]
+    pub fn new() -> Map<K, V> {
+        Map {
+            storage: K::MapStorage::empty(),
+        }
+    }
+
+    /// Creates an empty [`Map`] with space for at least `capacity` entries.
+    ///
+    /// Only storage which allocates, like the one used for dynamic keys such
+    /// as `u32`, makes use of the capacity. For other keys this is the same as
+    /// [`Map::new`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "hashbrown")] {
+    /// use fixed_map::Map;
+    ///
+    /// let mut map = Map::with_capacity(1000);
+    ///
+    /// for n in 0..1000u32 {
+    ///     map.insert(n, n * 2);
     /// }
     ///
-    /// assert!(map.iter().eq([(Key::First(true), &2), (Key::Second, &4)]));
+    /// assert_eq!(map.len(), 1000);
+    /// # }
     /// ```
     #[inline]
-    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
-        self.storage.iter_mut()
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Map<K, V> {
+        let mut map = Map::new();
+        map.reserve(capacity);
+        map
     }
 
-    /// An iterator visiting all values mutably in arbitrary order.
-    /// The iterator element type is `&'a mut V`.
+    /// Creates a [`Map`] from an array of key-value pairs.
+    ///
+    /// If a key occurs more than once, the value which comes later in the
+    /// array overwrites the earlier one.
     ///
     /// # Examples
     ///
     /// ```
     /// use fixed_map::{Key, Map};
     ///
-    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
-    /// pub enum Key {
-    ///     First,
-    ///     Second,
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Op {
+    ///     Add,
+    ///     Sub,
+    ///     Mul,
     /// }
     ///
-    /// let mut map = Map::new();
-    /// map.insert(Key::First, 2);
-    /// map.insert(Key::Second, 5);
-    ///
-    /// for (index, val) in map.values_mut().enumerate() {
-    ///     *val *= index + 1;
+    /// fn add(a: u32, b: u32) -> u32 {
+    ///     a + b
     /// }
     ///
-    /// assert!(map.values().copied().eq([2, 10]));
-    ///
-    /// let mut map = Map::new();
-    /// map.insert(Key::First, 2);
-    /// map.insert(Key::Second, 5);
-    ///
-    /// for (index, val) in map.values_mut().rev().enumerate() {
-    ///     *val *= index + 1;
+    /// fn sub(a: u32, b: u32) -> u32 {
+    ///     a - b
     /// }
     ///
-    /// assert!(map.values().copied().eq([4, 5]));
+    /// let handlers = Map::from_array([(Op::Add, add as fn(u32, u32) -> u32), (Op::Sub, sub)]);
+    ///
+    /// assert_eq!(handlers.get(Op::Add).map(|f| f(3, 2)), Some(5));
+    /// assert_eq!(handlers.get(Op::Sub).map(|f| f(3, 2)), Some(1));
+    /// assert!(handlers.get(Op::Mul).is_none());
     /// ```
     ///
-    /// Using a composite key:
+    /// Later duplicates overwrite earlier ones:
     ///
     /// ```
     /// use fixed_map::{Key, Map};
     ///
-    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
-    /// pub enum Key {
-    ///     First(bool),
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Key {
+    ///     First,
     ///     Second,
     /// }
     ///
-    /// let mut map = Map::new();
-    /// map.insert(Key::First(false), 2);
-    /// map.insert(Key::Second, 5);
-    ///
-    /// for (index, val) in map.values_mut().enumerate() {
-    ///     *val *= index + 1;
-    /// }
-    ///
-    /// assert!(map.values().copied().eq([2, 10]));
-    ///
-    /// let mut map = Map::new();
-    /// map.insert(Key::First(false), 2);
-    /// map.insert(Key::Second, 5);
-    ///
-    /// for (index, val) in map.values_mut().rev().enumerate() {
-    ///     *val *= index + 1;
-    /// }
-    ///
-    /// assert!(map.values().copied().eq([4, 5]));
+    /// let map = Map::from_array([(Key::First, 1), (Key::Second, 2), (Key::First, 3)]);
+    /// assert!(map.iter().eq([(Key::First, &3), (Key::Second, &2)]));
     /// ```
     #[inline]
-    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
-        self.storage.values_mut()
+    pub fn from_array<const N: usize>(pairs: [(K, V); N]) -> Map<K, V> {
+        let mut map = Map::new();
+
+        for (key, value) in pairs {
+            map.insert(key, value);
+        }
+
+        map
     }
 
-    /// Returns `true` if the map currently contains the given key.
+    /// Creates a map from an iterator of key-value pairs, failing on the first
+    /// duplicate key.
+    ///
+    /// This is like [`FromIterator`], except that instead of letting later
+    /// duplicates overwrite earlier ones the duplicate pair is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first key-value pair whose key was already seen, together
+    /// with its value. Entries after it are not consumed from the iterator.
     ///
     /// # Examples
     ///
     /// ```
     /// use fixed_map::{Key, Map};
     ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Flag {
+    ///     Verbose(bool),
+    ///     Jobs,
+    /// }
+    ///
+    /// let map = Map::try_from_iter([(Flag::Verbose(true), 1), (Flag::Jobs, 4)]);
+    /// assert_eq!(map.map(|map| map.len()), Ok(2));
+    ///
+    /// let map = Map::try_from_iter([
+    ///     (Flag::Jobs, 4),
+    ///     (Flag::Verbose(false), 1),
+    ///     (Flag::Jobs, 8),
+    ///     (Flag::Verbose(false), 2),
+    /// ]);
+    ///
+    /// assert_eq!(map, Err((Flag::Jobs, 8)));
+    /// ```
+    #[inline]
+    pub fn try_from_iter<T>(iter: T) -> Result<Map<K, V>, (K, V)>
+    where
+        T: IntoIterator<Item = (K, V)>,
+    {
+        let mut map = Map::new();
+
+        for (key, value) in iter {
+            map.insert_unique(key, value)?;
+        }
+
+        Ok(map)
+    }
+
+    /// Reserves capacity for at least `additional` more entries.
+    ///
+    /// This does nothing for keys whose storage doesn't allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "hashbrown")] {
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     First(u32),
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.reserve(10);
+    /// map.insert(Key::First(1), 1);
+    /// map.insert(Key::Second, 2);
+    /// assert_eq!(map.len(), 2);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.storage.reserve(additional);
+    }
+
+    /// Shrinks the capacity of the map as much as possible.
+    ///
+    /// This does nothing for keys whose storage doesn't allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "hashbrown")] {
+    /// use fixed_map::Map;
+    ///
+    /// let mut map = Map::with_capacity(100);
+    /// map.insert(1u32, "a");
+    /// map.shrink_to_fit();
+    /// assert_eq!(map.get(1), Some(&"a"));
+    /// # }
+    /// ```
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.storage.shrink_to_fit();
+    }
+
+    /// Returns the usable capacity of the map's heap storage multiplied by the
+    /// size of a key-value pair.
+    ///
+    /// This is `0` for keys whose storage is stored inline, such as enums with
+    /// unit variants, and can be used to compare how much memory maps with
+    /// dynamic storage hold on to. It is not the number of bytes allocated: it
+    /// doesn't count the slots a hash table keeps empty to stay fast, its
+    /// control bytes, or heap memory owned by the values. Removing entries
+    /// from a hash table may also leave markers behind which reduce its usable
+    /// capacity until it is rehashed, so this can go down without any memory
+    /// being released.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second(Option<bool>),
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::Second(None), 1u64);
+    /// assert_eq!(map.memory_footprint(), 0);
+    /// ```
+    ///
+    /// Using dynamic storage:
+    ///
+    /// ```
+    /// # #[cfg(feature = "hashbrown")] {
+    /// use fixed_map::Map;
+    ///
+    /// let mut map = Map::with_capacity(100);
+    /// map.insert(1u32, 1u32);
+    ///
+    /// assert!(map.memory_footprint() >= 100 * core::mem::size_of::<(u32, u32)>());
+    ///
+    /// map.clear_and_shrink();
+    /// assert_eq!(map.memory_footprint(), 0);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn memory_footprint(&self) -> usize {
+        self.storage.heap_size()
+    }
+
+    /// Returns the ratio between the number of entries in the map and its
+    /// usable capacity.
+    ///
+    /// Storage which doesn't allocate, such as for enums with unit variants,
+    /// is always full and has a load factor of `1.0`. For dynamic storage a low
+    /// load factor, such as after removing many entries, indicates that
+    /// [`Map::shrink_to_fit`] would release memory. A map which hasn't
+    /// allocated at all also has a load factor of `1.0`.
+    ///
+    /// The usable capacity of a hash table is less than the number of slots
+    /// it has allocated, and removing entries may leave markers behind which
+    /// reduce it further until the table is rehashed. So this is a measure of
+    /// how much room is left before the map grows, not of how densely its
+    /// allocation is used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// assert_eq!(map.load_factor(), 1.0);
+    /// map.insert(Key::First, 1);
+    /// assert_eq!(map.load_factor(), 1.0);
+    /// ```
+    ///
+    /// Using dynamic storage:
+    ///
+    /// ```
+    /// # #[cfg(feature = "hashbrown")] {
+    /// use fixed_map::Map;
+    ///
+    /// let mut map: Map<u32, u32> = (0..100).map(|n| (n, n)).collect();
+    /// map.retain(|key, _| key < 10);
+    ///
+    /// if map.load_factor() < 0.5 {
+    ///     map.shrink_to_fit();
+    /// }
+    ///
+    /// assert!(map.load_factor() >= 0.5);
+    /// # }
+    /// ```
+    #[inline]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn load_factor(&self) -> f32 {
+        let capacity = self.storage.capacity();
+
+        if capacity == 0 {
+            return 1.0;
+        }
+
+        self.storage.len() as f32 / capacity as f32
+    }
+
+    /// An iterator visiting all key-value pairs in [key order](crate::Key#ordering).
+    /// The iterator element type is `(K, &'a V)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    ///     Three,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::One, 1);
+    /// map.insert(Key::Two, 2);
+    ///
+    /// assert_eq!(map.iter().collect::<Vec<_>>(), vec![(Key::One, &1), (Key::Two, &2)]);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.storage.iter()
+    }
+
+    /// An iterator visiting all key-value pairs sorted by the [`Ord`]
+    /// implementation of the key.
+    ///
+    /// This gives deterministic output for storage such as `hashbrown` which
+    /// otherwise iterates in an arbitrary order. Note that [`Map::iter`]
+    /// already visits unit variants in declaration order, which can differ
+    /// from the order given by [`Ord`] if it's implemented manually.
+    ///
+    /// Entries are collected and sorted when this is called, which requires
+    /// the `alloc` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "hashbrown")] {
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Key)]
+    /// enum Key {
+    ///     First(u32),
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::Second, "c");
+    /// map.insert(Key::First(20), "b");
+    /// map.insert(Key::First(10), "a");
+    ///
+    /// assert!(map.sorted_iter().eq([
+    ///     (Key::First(10), &"a"),
+    ///     (Key::First(20), &"b"),
+    ///     (Key::Second, &"c"),
+    /// ]));
+    /// # }
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn sorted_iter(&self) -> alloc::vec::IntoIter<(K, &V)>
+    where
+        K: Ord,
+    {
+        let mut entries = self.storage.iter().collect::<alloc::vec::Vec<_>>();
+        entries.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        entries.into_iter()
+    }
+
+    /// An iterator visiting all key-value pairs sorted with the comparator
+    /// function `cmp`.
+    ///
+    /// This allows entries to be ordered by something other than the key, such
+    /// as by their value. The sort is stable, so entries which compare equal
+    /// are visited in [key order](crate::Key#ordering).
+    ///
+    /// Like [`Map::sorted_iter`], every entry is collected into a vector which
+    /// is allocated and sorted when this is called, which requires the `alloc`
+    /// feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Team {
+    ///     Red,
+    ///     Green,
+    ///     Blue,
+    ///     Yellow,
+    /// }
+    ///
+    /// let mut scores = Map::new();
+    /// scores.insert(Team::Red, 10);
+    /// scores.insert(Team::Green, 30);
+    /// scores.insert(Team::Blue, 20);
+    /// scores.insert(Team::Yellow, 30);
+    ///
+    /// // Top two scores, highest first.
+    /// let top = scores
+    ///     .iter_sorted_by(|(_, a), (_, b)| b.cmp(a))
+    ///     .take(2)
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(top, [(Team::Green, &30), (Team::Yellow, &30)]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn iter_sorted_by<F>(&self, cmp: F) -> alloc::vec::IntoIter<(K, &V)>
+    where
+        F: FnMut(&(K, &V), &(K, &V)) -> Ordering,
+    {
+        let mut entries = self.storage.iter().collect::<alloc::vec::Vec<_>>();
+        entries.sort_by(cmp);
+        entries.into_iter()
+    }
+
+    /// An iterator visiting all keys in [key order](crate::Key#ordering).
+    /// The iterator element type is `K`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// pub enum Key {
+    ///     First,
+    ///     Second,
+    ///     Third,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::First, 1);
+    /// map.insert(Key::Second, 2);
+    ///
+    /// assert!(map.keys().eq([Key::First, Key::Second]));
+    /// assert!(map.keys().rev().eq([Key::Second, Key::First]));
+    /// ```
+    ///
+    /// Using a composite key:
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// pub enum Key {
+    ///     First,
+    ///     Second(bool),
+    ///     Third,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::First, 1);
+    /// map.insert(Key::Second(false), 2);
+    ///
+    /// dbg!(map.keys().collect::<Vec<_>>());
+    ///
+    /// assert!(map.keys().eq([Key::First, Key::Second(false)]));
+    /// assert!(map.keys().rev().eq([Key::Second(false), Key::First]));
+    /// ```
+    #[inline]
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        self.storage.keys()
+    }
+
+    /// An iterator visiting all values in [key order](crate::Key#ordering).
+    /// The iterator element type is `&'a V`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// pub enum Key {
+    ///     First,
+    ///     Second,
+    ///     Third,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::First, 1);
+    /// map.insert(Key::Second, 2);
+    ///
+    /// assert!(map.values().copied().eq([1, 2]));
+    /// assert!(map.values().rev().copied().eq([2, 1]));
+    /// ```
+    ///
+    /// Using a composite key:
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// pub enum Key {
+    ///     First(bool),
+    ///     Second,
+    ///     Third,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::First(false), 1);
+    /// map.insert(Key::Second, 2);
+    ///
+    /// assert!(map.values().copied().eq([1, 2]));
+    /// assert!(map.values().rev().copied().eq([2, 1]));
+    /// ```
+    #[inline]
+    pub fn values(&self) -> Values<'_, K, V> {
+        self.storage.values()
+    }
+
+    /// An iterator visiting all key-value pairs in [key order](crate::Key#ordering),
+    /// with mutable references to the values.
+    /// The iterator element type is `(K, &'a mut V)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::First, 1);
+    /// map.insert(Key::Second, 2);
+    ///
+    /// // Update all values
+    /// for (_, val) in map.iter_mut() {
+    ///     *val *= 2;
+    /// }
+    ///
+    /// assert!(map.iter().eq([(Key::First, &2), (Key::Second, &4)]));
+    /// ```
+    ///
+    /// Using a composite key:
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// enum Key {
+    ///     First(bool),
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::First(true), 1);
+    /// map.insert(Key::Second, 2);
+    ///
+    /// // Update all values
+    /// for (_, val) in map.iter_mut() {
+    ///     *val *= 2;
+    /// }
+    ///
+    /// assert!(map.iter().eq([(Key::First(true), &2), (Key::Second, &4)]));
+    /// ```
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        self.storage.iter_mut()
+    }
+
+    /// An iterator visiting all values mutably in [key order](crate::Key#ordering).
+    /// The iterator element type is `&'a mut V`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// pub enum Key {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::First, 2);
+    /// map.insert(Key::Second, 5);
+    ///
+    /// for (index, val) in map.values_mut().enumerate() {
+    ///     *val *= index + 1;
+    /// }
+    ///
+    /// assert!(map.values().copied().eq([2, 10]));
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::First, 2);
+    /// map.insert(Key::Second, 5);
+    ///
+    /// for (index, val) in map.values_mut().rev().enumerate() {
+    ///     *val *= index + 1;
+    /// }
+    ///
+    /// assert!(map.values().copied().eq([4, 5]));
+    /// ```
+    ///
+    /// Using a composite key:
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    /// pub enum Key {
+    ///     First(bool),
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::First(false), 2);
+    /// map.insert(Key::Second, 5);
+    ///
+    /// for (index, val) in map.values_mut().enumerate() {
+    ///     *val *= index + 1;
+    /// }
+    ///
+    /// assert!(map.values().copied().eq([2, 10]));
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::First(false), 2);
+    /// map.insert(Key::Second, 5);
+    ///
+    /// for (index, val) in map.values_mut().rev().enumerate() {
+    ///     *val *= index + 1;
+    /// }
+    ///
+    /// assert!(map.values().copied().eq([4, 5]));
+    /// ```
+    #[inline]
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        self.storage.values_mut()
+    }
+
+    /// Returns `true` if the map currently contains the given key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::First, "a");
+    /// assert_eq!(map.contains_key(Key::First), true);
+    /// assert_eq!(map.contains_key(Key::Second), false);
+    /// ```
+    #[inline]
+    pub fn contains_key(&self, key: K) -> bool {
+        self.storage.contains_key(key)
+    }
+
+    /// Returns `true` if any key in the map is associated with the given value.
+    ///
+    /// This is a linear scan over every value in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::First, "a");
+    /// assert!(map.contains_value(&"a"));
+    /// assert!(!map.contains_value(&"b"));
+    /// ```
+    #[inline]
+    pub fn contains_value(&self, value: &V) -> bool
+    where
+        V: PartialEq,
+    {
+        self.values().any(|v| v == value)
+    }
+
+    /// Tests if two maps are equal while skipping any keys which are present
+    /// in `ignore`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map, Set};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Field {
+    ///     Name,
+    ///     Port,
+    ///     Timestamp,
+    /// }
+    ///
+    /// let a = Map::from_array([(Field::Name, 1), (Field::Port, 80), (Field::Timestamp, 100)]);
+    /// let b = Map::from_array([(Field::Name, 1), (Field::Port, 80), (Field::Timestamp, 200)]);
+    /// let c = Map::from_array([(Field::Name, 1), (Field::Port, 80)]);
+    ///
+    /// let ignore = Set::from_array([Field::Timestamp]);
+    ///
+    /// assert!(a != b);
+    /// assert!(a.eq_ignoring(&b, &ignore));
+    /// assert!(a.eq_ignoring(&c, &ignore));
+    /// assert!(!a.eq_ignoring(&c, &Set::new()));
+    /// ```
+    #[inline]
+    pub fn eq_ignoring(&self, other: &Map<K, V>, ignore: &Set<K>) -> bool
+    where
+        V: PartialEq,
+    {
+        let matches = self
+            .storage
+            .iter()
+            .filter(|(key, _)| !ignore.contains(key.clone()))
+            .all(|(key, value)| other.storage.get(key) == Some(value));
+
+        matches
+            && other
+                .storage
+                .keys()
+                .filter(|key| !ignore.contains(key.clone()))
+                .all(|key| self.storage.contains_key(key))
+    }
+
+    /// Compare the map against `other`, producing the changes needed to turn
+    /// this map into `other`.
+    ///
+    /// Iterating over the returned [`MapDiff`] yields a [`Change`] for every
+    /// key which was removed, changed or added. The changes can be replayed on
+    /// another map with [`Map::apply_diff`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    /// use fixed_map::map::Change;
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second(bool),
+    ///     Third,
+    /// }
+    ///
+    /// let mut a = Map::new();
+    /// a.insert(Key::First, 1);
+    /// a.insert(Key::Second(true), 2);
+    ///
+    /// let mut b = a;
+    /// b.insert(Key::Second(true), 3);
+    /// b.insert(Key::Third, 4);
+    /// b.remove(Key::First);
+    ///
+    /// assert!(a.diff(&b).iter().eq([
+    ///     Change::Removed(Key::First, &1),
+    ///     Change::Changed(Key::Second(true), &2, &3),
+    ///     Change::Added(Key::Third, &4),
+    /// ]));
+    ///
+    /// assert!(a.diff(&a).is_empty());
+    /// ```
+    #[inline]
+    pub fn diff(&self, other: &Map<K, V>) -> MapDiff<K, V>
+    where
+        V: PartialEq + Clone,
+    {
+        MapDiff::new(self, other)
+    }
+
+    /// Apply the changes in `diff` to the map, removing, changing and adding
+    /// entries so that a map equal to the one `diff` was created from becomes
+    /// equal to the one it was compared against.
+    ///
+    /// Applying a diff is idempotent, applying it to a map which is already
+    /// equal to the target of the diff leaves the map unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second(bool),
+    ///     Third,
+    /// }
+    ///
+    /// let mut a = Map::new();
+    /// a.insert(Key::First, 1);
+    /// a.insert(Key::Second(false), 2);
+    ///
+    /// let mut b = Map::new();
+    /// b.insert(Key::Second(false), 20);
+    /// b.insert(Key::Third, 3);
+    ///
+    /// a.apply_diff(&a.diff(&b));
+    /// assert_eq!(a, b);
+    ///
+    /// let diff = a.diff(&b);
+    /// a.apply_diff(&diff);
+    /// a.apply_diff(&diff);
+    /// assert_eq!(a, b);
+    /// ```
+    #[inline]
+    pub fn apply_diff(&mut self, diff: &MapDiff<K, V>)
+    where
+        V: Clone,
+    {
+        diff.apply_to(self);
+    }
+
+    /// Returns the sum of all values in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Resource {
+    ///     Cpu,
+    ///     Memory,
+    ///     Disk,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Resource::Cpu, 4u64);
+    /// map.insert(Resource::Disk, 16u64);
+    ///
+    /// assert_eq!(map.sum_values::<u64>(), 20);
+    /// assert_eq!(Map::<Resource, u64>::new().sum_values::<u64>(), 0);
+    /// ```
+    #[inline]
+    pub fn sum_values<'a, S>(&'a self) -> S
+    where
+        S: Sum<&'a V>,
+    {
+        self.storage.values().sum()
+    }
+
+    /// Returns the entry with the largest value, or `None` if the map is
+    /// empty.
+    ///
+    /// If several entries have the largest value, the last one in iteration
+    /// order is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Resource {
+    ///     Cpu,
+    ///     Memory,
+    ///     Disk,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// assert_eq!(map.max_value(), None);
+    ///
+    /// map.insert(Resource::Cpu, 4);
+    /// map.insert(Resource::Memory, 16);
+    /// map.insert(Resource::Disk, 16);
+    ///
+    /// assert_eq!(map.max_value(), Some((Resource::Disk, &16)));
+    /// ```
+    #[inline]
+    pub fn max_value(&self) -> Option<(K, &V)>
+    where
+        V: Ord,
+    {
+        self.storage.iter().max_by(|a, b| a.1.cmp(b.1))
+    }
+
+    /// Returns the entry with the smallest value, or `None` if the map is
+    /// empty.
+    ///
+    /// If several entries have the smallest value, the first one in iteration
+    /// order is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Resource {
+    ///     Cpu,
+    ///     Memory,
+    ///     Disk,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// assert_eq!(map.min_value(), None);
+    ///
+    /// map.insert(Resource::Cpu, 4);
+    /// map.insert(Resource::Memory, 16);
+    /// map.insert(Resource::Disk, 4);
+    ///
+    /// assert_eq!(map.min_value(), Some((Resource::Cpu, &4)));
+    /// ```
+    #[inline]
+    pub fn min_value(&self) -> Option<(K, &V)>
+    where
+        V: Ord,
+    {
+        self.storage.iter().min_by(|a, b| a.1.cmp(b.1))
+    }
+
+    /// Returns the first entry in the map in [key order](crate::Key#ordering),
+    /// or `None` if the map is empty.
+    ///
+    /// This is only available for storage which iterates in a deterministic
+    /// order, which is indicated by its iterator implementing
+    /// [`DoubleEndedIterator`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Priority {
+    ///     High,
+    ///     Medium,
+    ///     Low,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// assert_eq!(map.first(), None);
+    ///
+    /// map.insert(Priority::Low, "cleanup");
+    /// map.insert(Priority::Medium, "build");
+    ///
+    /// assert_eq!(map.first(), Some((Priority::Medium, &"build")));
+    /// ```
+    #[inline]
+    pub fn first<'a>(&'a self) -> Option<(K, &'a V)>
+    where
+        Iter<'a, K, V>: DoubleEndedIterator,
+    {
+        self.storage.iter().next()
+    }
+
+    /// Returns the last entry in the map in [key order](crate::Key#ordering),
+    /// or `None` if the map is empty.
+    ///
+    /// Like [`Map::first`], this is only available for storage which iterates
+    /// in a deterministic order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Priority {
+    ///     High,
+    ///     Medium,
+    ///     Low,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// assert_eq!(map.last(), None);
+    ///
+    /// map.insert(Priority::High, "deploy");
+    /// map.insert(Priority::Medium, "build");
+    ///
+    /// assert_eq!(map.last(), Some((Priority::Medium, &"build")));
+    /// ```
+    #[inline]
+    pub fn last<'a>(&'a self) -> Option<(K, &'a V)>
+    where
+        Iter<'a, K, V>: DoubleEndedIterator,
+    {
+        self.storage.iter().next_back()
+    }
+
+    /// Removes and returns the first entry in the map in [key
+    /// order](crate::Key#ordering), or `None` if the map is empty.
+    ///
+    /// For derived keys this is the entry whose variant is declared first.
+    /// Like [`Map::first`], this is only available for storage which iterates
+    /// in a deterministic order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Priority {
+    ///     High,
+    ///     Medium,
+    ///     Low,
+    /// }
+    ///
+    /// let mut queue = Map::new();
+    /// queue.insert(Priority::Low, "cleanup");
+    /// queue.insert(Priority::High, "deploy");
+    ///
+    /// assert_eq!(queue.pop_first(), Some((Priority::High, "deploy")));
+    /// assert_eq!(queue.pop_first(), Some((Priority::Low, "cleanup")));
+    /// assert_eq!(queue.pop_first(), None);
+    /// ```
+    #[inline]
+    pub fn pop_first(&mut self) -> Option<(K, V)>
+    where
+        for<'a> Iter<'a, K, V>: DoubleEndedIterator,
+    {
+        let (key, _) = self.storage.iter().next()?;
+        let value = self.storage.remove(key.clone())?;
+        Some((key, value))
+    }
+
+    /// Removes and returns the last entry in the map in [key
+    /// order](crate::Key#ordering), or `None` if the map is empty.
+    ///
+    /// For derived keys this is the entry whose variant is declared last.
+    /// Like [`Map::first`], this is only available for storage which iterates
+    /// in a deterministic order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Priority {
+    ///     High,
+    ///     Medium,
+    ///     Low,
+    /// }
+    ///
+    /// let mut queue = Map::new();
+    /// queue.insert(Priority::High, "deploy");
+    /// queue.insert(Priority::Medium, "build");
+    ///
+    /// assert_eq!(queue.pop_last(), Some((Priority::Medium, "build")));
+    /// assert_eq!(queue.len(), 1);
+    /// ```
+    #[inline]
+    pub fn pop_last(&mut self) -> Option<(K, V)>
+    where
+        for<'a> Iter<'a, K, V>: DoubleEndedIterator,
+    {
+        let (key, _) = self.storage.iter().next_back()?;
+        let value = self.storage.remove(key.clone())?;
+        Some((key, value))
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::First, "a");
+    /// assert_eq!(map.get(Key::First).copied(), Some("a"));
+    /// assert_eq!(map.get(Key::Second), None);
+    /// ```
+    ///
+    /// Using a composite key:
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     First(bool),
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::First(true), "a");
+    /// assert_eq!(map.get(Key::First(true)).copied(), Some("a"));
+    /// assert_eq!(map.get(Key::Second), None);
+    /// ```
+    #[inline]
+    pub fn get(&self, key: K) -> Option<&V> {
+        self.storage.get(key)
+    }
+
+    /// Returns references to the values corresponding to each of the given
+    /// keys, in the same order as the keys.
+    ///
+    /// The same key may be requested more than once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Channel {
+    ///     Red,
+    ///     Green,
+    ///     Blue,
+    ///     Alpha,
+    /// }
+    ///
+    /// let mut pixel = Map::new();
+    /// pixel.insert(Channel::Red, 255u8);
+    /// pixel.insert(Channel::Blue, 128u8);
+    ///
+    /// let [r, g, b, a] = pixel.get_all([Channel::Red, Channel::Green, Channel::Blue, Channel::Alpha]);
+    ///
+    /// assert_eq!(r, Some(&255));
+    /// assert_eq!(g, None);
+    /// assert_eq!(b, Some(&128));
+    /// assert_eq!(a, None);
+    /// ```
+    #[inline]
+    pub fn get_all<const N: usize>(&self, keys: [K; N]) -> [Option<&V>; N] {
+        keys.map(|key| self.storage.get(key))
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::First, "a");
+    ///
+    /// if let Some(x) = map.get_mut(Key::First) {
+    ///     *x = "b";
+    /// }
+    ///
+    /// assert_eq!(map.get(Key::First).copied(), Some("b"));
+    /// ```
+    ///
+    /// Using a composite key:
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     First(bool),
+    ///     Second(()),
+    ///     Third,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::First(true), "a");
+    ///
+    /// if let Some(x) = map.get_mut(Key::First(true)) {
+    ///     *x = "b";
+    /// }
+    ///
+    /// assert_eq!(map.get(Key::First(true)).copied(), Some("b"));
+    /// ```
+    #[inline]
+    pub fn get_mut(&mut self, key: K) -> Option<&mut V> {
+        self.storage.get_mut(key)
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// If the map did not have this key present, [`None`] is returned.
+    ///
+    /// If the map did have this key present, the value is updated, and the old
+    /// value is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// assert_eq!(map.insert(Key::One, "a"), None);
+    /// assert_eq!(map.is_empty(), false);
+    ///
+    /// map.insert(Key::Two, "b");
+    /// assert_eq!(map.insert(Key::Two, "c"), Some("b"));
+    /// assert_eq!(map.get(Key::Two), Some(&"c"));
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.storage.insert(key, value)
+    }
+
+    /// Tries to insert a key-value pair into the map, and returns a mutable
+    /// reference to the value in the entry.
+    ///
+    /// # Errors
+    ///
+    /// If the map already had this key present, nothing is updated, and an
+    /// [`OccupiedError`] containing the occupied entry and the value is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// assert_eq!(map.try_insert(Key::One, "a").unwrap(), &"a");
+    ///
+    /// let err = map.try_insert(Key::One, "b").unwrap_err();
+    /// assert_eq!(err.value, "b");
+    /// assert_eq!(map.get(Key::One), Some(&"a"));
+    /// ```
+    ///
+    /// Using a composite key:
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    /// use fixed_map::map::OccupiedEntry;
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Key {
+    ///     First(Option<bool>),
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// assert_eq!(map.try_insert(Key::First(None), 1).unwrap(), &1);
+    /// assert_eq!(map.try_insert(Key::First(Some(true)), 2).unwrap(), &2);
+    ///
+    /// let err = map.try_insert(Key::First(None), 3).unwrap_err();
+    /// assert_eq!(err.entry.key(), Key::First(None));
+    /// assert_eq!(err.entry.get(), &1);
+    /// assert_eq!(err.value, 3);
+    /// ```
+    #[inline]
+    pub fn try_insert(
+        &mut self,
+        key: K,
+        value: V,
+    ) -> Result<&mut V, OccupiedError<'_, K::MapStorage<V>, K, V>> {
+        match self.entry(key) {
+            Entry::Occupied(entry) => Err(OccupiedError { entry, value }),
+            Entry::Vacant(entry) => Ok(entry.insert(value)),
+        }
+    }
+
+    /// Inserts a key-value pair into the map only if the key doesn't already
+    /// hold a value.
+    ///
+    /// # Errors
+    ///
+    /// If the key is already present, nothing is updated and the rejected
+    /// key-value pair is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Key {
+    ///     Registry(()),
+    ///     Other,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// assert_eq!(map.insert_unique(Key::Registry(()), "a"), Ok(()));
+    /// assert_eq!(map.insert_unique(Key::Registry(()), "b"), Err((Key::Registry(()), "b")));
+    /// assert_eq!(map.get(Key::Registry(())), Some(&"a"));
+    /// ```
+    #[inline]
+    pub fn insert_unique(&mut self, key: K, value: V) -> Result<(), (K, V)> {
+        match self.storage.entry(key.clone()) {
+            Entry::Occupied(..) => Err((key, value)),
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+                Ok(())
+            }
+        }
+    }
+
+    /// Inserts every key-value pair from `iter` into the map, returning the
+    /// set of keys which weren't present before.
+    ///
+    /// Keys which already held a value have it overwritten and are not part
+    /// of the returned set. A key which occurs more than once in `iter` keeps
+    /// the last value, and is part of the returned set if it wasn't present
+    /// before the call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    ///     Third,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::First, 1);
+    ///
+    /// let added = map.insert_many([(Key::First, 10), (Key::Third, 30)]);
+    ///
+    /// assert!(added.iter().eq([Key::Third]));
+    /// assert!(map.iter().eq([(Key::First, &10), (Key::Third, &30)]));
+    /// ```
+    #[inline]
+    pub fn insert_many<T>(&mut self, iter: T) -> Set<K>
+    where
+        T: IntoIterator<Item = (K, V)>,
+    {
+        let mut added = Set::new();
+
+        for (key, value) in iter {
+            if self.storage.insert(key.clone(), value).is_none() {
+                added.insert(key);
+            }
+        }
+
+        added
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key
+    /// was previously in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     One,
+    ///     Two,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::One, "a");
+    /// assert_eq!(map.remove(Key::One), Some("a"));
+    /// assert_eq!(map.remove(Key::One), None);
+    /// ```
+    #[inline]
+    pub fn remove(&mut self, key: K) -> Option<V> {
+        self.storage.remove(key)
+    }
+
+    /// Removes every key in `keys` from the map, returning the number of
+    /// entries which were actually removed.
+    ///
+    /// This is the counterpart to [`Map::insert_many`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    ///     Third,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::First, 1);
+    /// map.insert(Key::Second, 2);
+    ///
+    /// let stale = Set::from_array([Key::First, Key::Third]);
+    ///
+    /// assert_eq!(map.remove_all(&stale), 1);
+    /// assert!(map.iter().eq([(Key::Second, &2)]));
+    /// ```
+    #[inline]
+    pub fn remove_all(&mut self, keys: &Set<K>) -> usize {
+        let mut removed = 0;
+
+        for key in keys {
+            if self.storage.remove(key).is_some() {
+                removed += 1;
+            }
+        }
+
+        removed
+    }
+
+    /// Swaps the values stored at keys `a` and `b`.
+    ///
+    /// If only one of the keys has a value, that value is moved to the other
+    /// key. Swapping a key with itself leaves the map unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    ///     Third,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::First, "a");
+    /// map.insert(Key::Second, "b");
+    ///
+    /// map.swap(Key::First, Key::Second);
+    /// assert!(map.iter().eq([(Key::First, &"b"), (Key::Second, &"a")]));
+    ///
+    /// map.swap(Key::Second, Key::Third);
+    /// assert!(map.iter().eq([(Key::First, &"b"), (Key::Third, &"a")]));
+    ///
+    /// map.swap(Key::First, Key::First);
+    /// assert!(map.iter().eq([(Key::First, &"b"), (Key::Third, &"a")]));
+    /// ```
+    #[inline]
+    pub fn swap(&mut self, a: K, b: K) {
+        let Some(mut value) = self.storage.remove(b.clone()) else {
+            // Only `a` can have a value, which is moved over to `b`.
+            if let Some(value) = self.storage.remove(a) {
+                self.storage.insert(b, value);
+            }
+
+            return;
+        };
+
+        // The value of `b` is exchanged with the value of `a` in place, so
+        // only the value of `b` has to be inserted again.
+        match self.storage.get_mut(a.clone()) {
+            Some(slot) => {
+                mem::swap(slot, &mut value);
+                self.storage.insert(b, value);
+            }
+            None => {
+                self.storage.insert(a, value);
+            }
+        }
+    }
+
+    /// Retains only the elements specified by the predicate.
+    ///
+    /// In other words, remove all pairs (k, v) for which f(k, &mut v) returns false.
+    /// The elements are visited in unsorted (and unspecified) order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map: Map<Key, i32> = Map::new();
+    ///
+    /// map.insert(Key::First, 42);
+    /// map.insert(Key::Second, -10);
+    ///
+    /// map.retain(|k, v| *v > 0);
+    ///
+    /// assert_eq!(map.len(), 1);
+    /// assert_eq!(map.get(Key::First), Some(&42));
+    /// assert_eq!(map.get(Key::Second), None);
+    /// ```
+    ///
+    /// Using a composite key:
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
     /// #[derive(Clone, Copy, Key)]
     /// enum Key {
+    ///     First(bool),
+    ///     Second,
+    /// }
+    ///
+    /// let mut map: Map<Key, i32> = Map::new();
+    ///
+    /// map.insert(Key::First(true), 42);
+    /// map.insert(Key::First(false), -31);
+    /// map.insert(Key::Second, 100);
+    ///
+    /// let mut other = map.clone();
+    /// assert_eq!(map.len(), 3);
+    ///
+    /// map.retain(|k, v| *v > 0);
+    ///
+    /// assert_eq!(map.len(), 2);
+    /// assert_eq!(map.get(Key::First(true)), Some(&42));
+    /// assert_eq!(map.get(Key::First(false)), None);
+    /// assert_eq!(map.get(Key::Second), Some(&100));
+    ///
+    /// other.retain(|k, v| matches!(k, Key::First(_)));
+    ///
+    /// assert_eq!(other.len(), 2);
+    /// assert_eq!(other.get(Key::First(true)), Some(&42));
+    /// assert_eq!(other.get(Key::First(false)), Some(&-31));
+    /// assert_eq!(other.get(Key::Second), None);
+    /// ```
+    #[inline]
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(K, &mut V) -> bool,
+    {
+        self.storage.retain(f);
+    }
+
+    /// Retains only the elements specified by the predicate `keep`, handing
+    /// each removed entry to `on_remove`.
+    ///
+    /// This behaves like [`Map::retain`], except that removed values are moved
+    /// into `on_remove` instead of being dropped. This allows values which own
+    /// resources to be cleaned up explicitly.
+    ///
+    /// Entries are visited in [key order](crate::Key#ordering) and removed in
+    /// place, so storage which allocates keeps its capacity. If `keep` panics,
+    /// the entries which haven't been visited yet are left in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second(bool),
+    ///     Third,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::First, String::from("first"));
+    /// map.insert(Key::Second(true), String::from("second"));
+    /// map.insert(Key::Third, String::from("third"));
+    ///
+    /// let mut closed = Vec::new();
+    ///
+    /// map.retain_with_removed(
+    ///     |key, _| key != Key::Second(true),
+    ///     |key, value| closed.push((key, value)),
+    /// );
+    ///
+    /// assert_eq!(map.len(), 2);
+    /// assert_eq!(closed, [(Key::Second(true), String::from("second"))]);
+    /// ```
+    #[inline]
+    pub fn retain_with_removed<F, R>(&mut self, keep: F, on_remove: R)
+    where
+        F: FnMut(K, &mut V) -> bool,
+        R: FnMut(K, V),
+    {
+        self.storage.retain_with_removed(keep, on_remove);
+    }
+
+    /// Returns a new map containing clones of the entries for which `f`
+    /// returns `true`, leaving this map untouched.
+    ///
+    /// This is the non-destructive counterpart to [`Map::retain`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Key {
     ///     First,
     ///     Second,
+    ///     Third,
     /// }
     ///
     /// let mut map = Map::new();
-    /// map.insert(Key::First, "a");
-    /// assert_eq!(map.contains_key(Key::First), true);
-    /// assert_eq!(map.contains_key(Key::Second), false);
+    /// map.insert(Key::First, 1);
+    /// map.insert(Key::Second, -2);
+    /// map.insert(Key::Third, 3);
+    ///
+    /// let positive = map.filter(|_, v| *v > 0);
+    ///
+    /// assert!(positive.iter().eq([(Key::First, &1), (Key::Third, &3)]));
+    /// assert_eq!(map.len(), 3);
     /// ```
     #[inline]
-    pub fn contains_key(&self, key: K) -> bool {
-        self.storage.contains_key(key)
+    #[must_use]
+    pub fn filter<F>(&self, mut f: F) -> Map<K, V>
+    where
+        V: Clone,
+        F: FnMut(K, &V) -> bool,
+    {
+        self.storage
+            .iter()
+            .filter(|(key, value)| f(key.clone(), value))
+            .map(|(key, value)| (key, value.clone()))
+            .collect()
     }
 
-    /// Returns a reference to the value corresponding to the key.
+    /// Moves every entry whose key satisfies `f` into a new map which is
+    /// returned, leaving the remaining entries in this map.
+    ///
+    /// Values are moved rather than cloned, and entries are removed from this
+    /// map in place so that it keeps the memory it has allocated.
     ///
     /// # Examples
     ///
     /// ```
     /// use fixed_map::{Key, Map};
     ///
-    /// #[derive(Clone, Copy, Key)]
-    /// enum Key {
-    ///     First,
-    ///     Second,
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Setting {
+    ///     Theme,
+    ///     Port,
+    ///     Volume,
     /// }
     ///
-    /// let mut map = Map::new();
-    /// map.insert(Key::First, "a");
-    /// assert_eq!(map.get(Key::First).copied(), Some("a"));
-    /// assert_eq!(map.get(Key::Second), None);
+    /// let mut config = Map::new();
+    /// config.insert(Setting::Theme, String::from("dark"));
+    /// config.insert(Setting::Port, String::from("8080"));
+    /// config.insert(Setting::Volume, String::from("10"));
+    ///
+    /// let hot = config.split_off(|key| key != Setting::Port);
+    ///
+    /// assert!(hot.keys().eq([Setting::Theme, Setting::Volume]));
+    /// assert!(config.keys().eq([Setting::Port]));
     /// ```
+    #[inline]
+    #[must_use]
+    pub fn split_off<F>(&mut self, mut f: F) -> Map<K, V>
+    where
+        F: FnMut(K) -> bool,
+    {
+        let mut other = Map::<K, V>::new();
+
+        self.storage.retain_with_removed(
+            |key, _| !f(key),
+            |key, value| {
+                other.storage.insert(key, value);
+            },
+        );
+
+        other
+    }
+
+    /// Clears the map, removing all key-value pairs. Keeps the allocated memory
+    /// for reuse.
     ///
-    /// Using a composite key:
+    /// Only storage for dynamic keys such as `u32` allocates, and it holds on
+    /// to its capacity. Use [`Map::clear_and_shrink`] to release it instead.
+    ///
+    /// # Examples
     ///
     /// ```
     /// use fixed_map::{Key, Map};
     ///
     /// #[derive(Clone, Copy, Key)]
     /// enum Key {
-    ///     First(bool),
-    ///     Second,
+    ///     One,
+    ///     Two,
     /// }
     ///
     /// let mut map = Map::new();
-    /// map.insert(Key::First(true), "a");
-    /// assert_eq!(map.get(Key::First(true)).copied(), Some("a"));
-    /// assert_eq!(map.get(Key::Second), None);
+    /// map.insert(Key::One, "a");
+    /// map.clear();
+    /// assert!(map.is_empty());
     /// ```
     #[inline]
-    pub fn get(&self, key: K) -> Option<&V> {
-        self.storage.get(key)
+    pub fn clear(&mut self) {
+        self.storage.clear();
     }
 
-    /// Returns a mutable reference to the value corresponding to the key.
+    /// Clears the map, removing all key-value pairs, and releases any memory
+    /// allocated by the storage.
+    ///
+    /// For keys whose storage doesn't allocate this is the same as
+    /// [`Map::clear`].
     ///
     /// # Examples
     ///
     /// ```
+    /// # #[cfg(feature = "hashbrown")] {
     /// use fixed_map::{Key, Map};
     ///
     /// #[derive(Clone, Copy, Key)]
     /// enum Key {
-    ///     First,
-    ///     Second,
+    ///     Frame(u32),
+    ///     Idle,
     /// }
     ///
     /// let mut map = Map::new();
-    /// map.insert(Key::First, "a");
     ///
-    /// if let Some(x) = map.get_mut(Key::First) {
-    ///     *x = "b";
+    /// for frame in 0..1000 {
+    ///     map.insert(Key::Frame(frame), frame);
     /// }
     ///
-    /// assert_eq!(map.get(Key::First).copied(), Some("b"));
+    /// map.clear_and_shrink();
+    /// assert!(map.is_empty());
+    /// # }
     /// ```
+    #[inline]
+    pub fn clear_and_shrink(&mut self) {
+        self.storage.clear();
+        self.storage.shrink_to_fit();
+    }
+
+    /// Saves a copy of the entries in the map, which can later be restored
+    /// with [`Map::restore`].
     ///
-    /// Using a composite key:
+    /// For keys which don't use dynamic storage this is a plain copy of the
+    /// map which doesn't allocate.
+    ///
+    /// # Examples
     ///
     /// ```
     /// use fixed_map::{Key, Map};
     ///
-    /// #[derive(Clone, Copy, Key)]
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
     /// enum Key {
-    ///     First(bool),
-    ///     Second(()),
-    ///     Third,
+    ///     First,
+    ///     Second(bool),
     /// }
     ///
     /// let mut map = Map::new();
-    /// map.insert(Key::First(true), "a");
+    /// map.insert(Key::First, 1);
     ///
-    /// if let Some(x) = map.get_mut(Key::First(true)) {
-    ///     *x = "b";
-    /// }
+    /// let snapshot = map.snapshot();
     ///
-    /// assert_eq!(map.get(Key::First(true)).copied(), Some("b"));
+    /// map.insert(Key::Second(true), 2);
+    /// map.remove(Key::First);
+    ///
+    /// map.restore(&snapshot);
+    /// assert!(map.iter().eq([(Key::First, &1)]));
     /// ```
     #[inline]
-    pub fn get_mut(&mut self, key: K) -> Option<&mut V> {
-        self.storage.get_mut(key)
+    pub fn snapshot(&self) -> Snapshot<K, V>
+    where
+        K::MapStorage<V>: Clone,
+    {
+        Snapshot::new(self.clone())
     }
 
-    /// Inserts a key-value pair into the map.
+    /// Restores the map to the entries saved in `snapshot`.
     ///
-    /// If the map did not have this key present, [`None`] is returned.
+    /// The snapshot is left untouched, so it can be restored any number of
+    /// times. This is implemented with [`Clone::clone_from`], allowing storage
+    /// which allocates to reuse the existing allocation of the map.
     ///
-    /// If the map did have this key present, the value is updated, and the old
-    /// value is returned.
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::Map;
+    ///
+    /// let mut map = Map::from_array([(1u8, "one")]);
+    /// let snapshot = map.snapshot();
+    ///
+    /// for n in 2..5u8 {
+    ///     map.insert(n, "speculative");
+    ///     map.restore(&snapshot);
+    /// }
+    ///
+    /// assert_eq!(map, Map::from_array([(1u8, "one")]));
+    /// ```
+    #[inline]
+    pub fn restore(&mut self, snapshot: &Snapshot<K, V>)
+    where
+        K::MapStorage<V>: Clone,
+    {
+        self.clone_from(snapshot.map());
+    }
+
+    /// Moves all entries from `other` into `self`, leaving `other` empty.
+    ///
+    /// If a key is present in both maps, the value from `other` replaces the
+    /// one in `self`.
     ///
     /// # Examples
     ///
     /// ```
     /// use fixed_map::{Key, Map};
     ///
-    /// #[derive(Clone, Copy, Key)]
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
     /// enum Key {
-    ///     One,
-    ///     Two,
+    ///     First,
+    ///     Second,
+    ///     Third,
     /// }
     ///
-    /// let mut map = Map::new();
-    /// assert_eq!(map.insert(Key::One, "a"), None);
-    /// assert_eq!(map.is_empty(), false);
+    /// let mut a = Map::new();
+    /// a.insert(Key::First, "a");
+    /// a.insert(Key::Second, "b");
     ///
-    /// map.insert(Key::Two, "b");
-    /// assert_eq!(map.insert(Key::Two, "c"), Some("b"));
-    /// assert_eq!(map.get(Key::Two), Some(&"c"));
+    /// let mut b = Map::new();
+    /// b.insert(Key::Second, "c");
+    /// b.insert(Key::Third, "d");
+    ///
+    /// a.append(&mut b);
+    ///
+    /// assert!(b.is_empty());
+    /// assert!(a.iter().eq([(Key::First, &"a"), (Key::Second, &"c"), (Key::Third, &"d")]));
+    /// ```
+    ///
+    /// Using a byte key:
+    ///
+    /// ```
+    /// use fixed_map::Map;
+    ///
+    /// let mut a = Map::new();
+    /// a.insert(1u8, String::from("one"));
+    ///
+    /// let mut b = Map::new();
+    /// b.insert(1u8, String::from("uno"));
+    /// b.insert(2u8, String::from("dos"));
+    ///
+    /// a.append(&mut b);
+    ///
+    /// assert_eq!(b.len(), 0);
+    /// assert_eq!(a.len(), 2);
+    /// assert_eq!(a.get(1).map(String::as_str), Some("uno"));
+    /// assert_eq!(a.get(2).map(String::as_str), Some("dos"));
     /// ```
     #[inline]
-    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
-        self.storage.insert(key, value)
+    pub fn append(&mut self, other: &mut Map<K, V>) {
+        let other = mem::replace(&mut other.storage, K::MapStorage::empty());
+
+        for (key, value) in other.into_iter() {
+            self.storage.insert(key, value);
+        }
     }
 
-    /// Removes a key from the map, returning the value at the key if the key
-    /// was previously in the map.
+    /// Moves all entries from `other` into `self`, calling `f` with the
+    /// existing and the incoming value to combine them when a key is present
+    /// in both maps.
     ///
     /// # Examples
     ///
     /// ```
     /// use fixed_map::{Key, Map};
     ///
-    /// #[derive(Clone, Copy, Key)]
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
     /// enum Key {
-    ///     One,
-    ///     Two,
+    ///     First,
+    ///     Second,
+    ///     Third,
     /// }
     ///
-    /// let mut map = Map::new();
-    /// map.insert(Key::One, "a");
-    /// assert_eq!(map.remove(Key::One), Some("a"));
-    /// assert_eq!(map.remove(Key::One), None);
+    /// let mut a = Map::new();
+    /// a.insert(Key::First, 1);
+    /// a.insert(Key::Second, 2);
+    ///
+    /// let mut b = Map::new();
+    /// b.insert(Key::Second, 10);
+    /// b.insert(Key::Third, 20);
+    ///
+    /// a.merge_with(b, |a, b| *a += b);
+    ///
+    /// assert!(a.iter().eq([(Key::First, &1), (Key::Second, &12), (Key::Third, &20)]));
     /// ```
     #[inline]
-    pub fn remove(&mut self, key: K) -> Option<V> {
-        self.storage.remove(key)
+    pub fn merge_with<F>(&mut self, other: Map<K, V>, mut f: F)
+    where
+        F: FnMut(&mut V, V),
+    {
+        for (key, value) in other.storage.into_iter() {
+            match self.storage.entry(key) {
+                Entry::Occupied(mut entry) => f(entry.get_mut(), value),
+                Entry::Vacant(entry) => {
+                    entry.insert(value);
+                }
+            }
+        }
     }
 
-    /// Retains only the elements specified by the predicate.
-    ///
-    /// In other words, remove all pairs (k, v) for which f(k, &mut v) returns false.
-    /// The elements are visited in unsorted (and unspecified) order.
+    /// Consumes the map and returns a new map with the same keys, where each
+    /// value has been transformed by `f`.
     ///
     /// # Examples
     ///
     /// ```
     /// use fixed_map::{Key, Map};
     ///
-    /// #[derive(Clone, Copy, Key)]
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
     /// enum Key {
     ///     First,
     ///     Second,
+    ///     Third,
     /// }
     ///
-    /// let mut map: Map<Key, i32> = Map::new();
-    ///
-    /// map.insert(Key::First, 42);
-    /// map.insert(Key::Second, -10);
-    ///
-    /// map.retain(|k, v| *v > 0);
+    /// let mut map = Map::new();
+    /// map.insert(Key::First, "1");
+    /// map.insert(Key::Third, "3");
     ///
-    /// assert_eq!(map.len(), 1);
-    /// assert_eq!(map.get(Key::First), Some(&42));
-    /// assert_eq!(map.get(Key::Second), None);
+    /// let map: Map<Key, u32> = map.map_values(|v| v.parse().unwrap());
+    /// assert!(map.iter().eq([(Key::First, &1), (Key::Third, &3)]));
     /// ```
     ///
     /// Using a composite key:
@@ -631,69 +2426,168 @@ where
     /// ```
     /// use fixed_map::{Key, Map};
     ///
-    /// #[derive(Clone, Copy, Key)]
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
     /// enum Key {
     ///     First(bool),
     ///     Second,
     /// }
     ///
-    /// let mut map: Map<Key, i32> = Map::new();
+    /// let mut map = Map::new();
+    /// map.insert(Key::First(true), vec![1, 2, 3]);
+    /// map.insert(Key::Second, vec![4]);
     ///
-    /// map.insert(Key::First(true), 42);
-    /// map.insert(Key::First(false), -31);
-    /// map.insert(Key::Second, 100);
+    /// let map = map.map_values(|v| v.len());
+    /// assert_eq!(map.get(Key::First(true)), Some(&3));
+    /// assert_eq!(map.get(Key::First(false)), None);
+    /// assert_eq!(map.get(Key::Second), Some(&1));
+    /// ```
+    #[inline]
+    pub fn map_values<U, F>(self, mut f: F) -> Map<K, U>
+    where
+        F: FnMut(V) -> U,
+    {
+        let mut map = Map::new();
+
+        for (key, value) in self.storage.into_iter() {
+            map.insert(key, f(value));
+        }
+
+        map
+    }
+
+    /// Consumes the map and returns a new map with the same keys, where each
+    /// value has been converted with [`Into`].
     ///
-    /// let mut other = map.clone();
-    /// assert_eq!(map.len(), 3);
+    /// This is a shorthand for `map.map_values(Into::into)`.
     ///
-    /// map.retain(|k, v| *v > 0);
+    /// # Examples
     ///
-    /// assert_eq!(map.len(), 2);
-    /// assert_eq!(map.get(Key::First(true)), Some(&42));
-    /// assert_eq!(map.get(Key::First(false)), None);
-    /// assert_eq!(map.get(Key::Second), Some(&100));
+    /// ```
+    /// use fixed_map::{Key, Map};
     ///
-    /// other.retain(|k, v| matches!(k, Key::First(_)));
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum State {
+    ///     Idle,
+    ///     Busy,
+    /// }
     ///
-    /// assert_eq!(other.len(), 2);
-    /// assert_eq!(other.get(Key::First(true)), Some(&42));
-    /// assert_eq!(other.get(Key::First(false)), Some(&-31));
-    /// assert_eq!(other.get(Key::Second), None);
+    /// let mut map = Map::new();
+    /// map.insert(State::Busy, 3u32);
+    ///
+    /// let map: Map<State, u64> = map.into_mapped();
+    /// assert!(map.iter().eq([(State::Busy, &3u64)]));
     /// ```
     #[inline]
-    pub fn retain<F>(&mut self, f: F)
+    pub fn into_mapped<U>(self) -> Map<K, U>
     where
-        F: FnMut(K, &mut V) -> bool,
+        V: Into<U>,
     {
-        self.storage.retain(f);
+        self.map_values(Into::into)
     }
 
-    /// Clears the map, removing all key-value pairs. Keeps the allocated memory
-    /// for reuse.
+    /// Returns a new map with the same keys, where each value has been
+    /// computed by `f` from a reference to the value in this map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::Second, String::from("hello"));
+    ///
+    /// let lengths = map.map_values_ref(String::len);
+    /// assert!(lengths.iter().eq([(Key::Second, &5)]));
+    /// assert_eq!(map.get(Key::Second).map(String::as_str), Some("hello"));
+    /// ```
+    #[inline]
+    pub fn map_values_ref<U, F>(&self, mut f: F) -> Map<K, U>
+    where
+        F: FnMut(&V) -> U,
+    {
+        let mut map = Map::new();
+
+        for (key, value) in self.storage.iter() {
+            map.insert(key, f(value));
+        }
+
+        map
+    }
+
+    /// Returns a new map with the same keys, where each value is a reference
+    /// to the value in this map.
+    ///
+    /// This is useful to hand out a map without cloning its values.
     ///
     /// # Examples
     ///
     /// ```
     /// use fixed_map::{Key, Map};
     ///
-    /// #[derive(Clone, Copy, Key)]
-    /// enum Key {
-    ///     One,
-    ///     Two,
-    /// }
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// fn total_len(map: Map<Key, &String>) -> usize {
+    ///     map.values().map(|value| value.len()).sum()
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Key::First, String::from("hello"));
+    /// map.insert(Key::Second, String::from("world!"));
+    ///
+    /// let view = map.as_ref_map();
+    /// assert_eq!(view.get(Key::First).map(|value| value.as_str()), Some("hello"));
+    /// assert_eq!(total_len(view), 11);
+    /// ```
+    #[inline]
+    pub fn as_ref_map(&self) -> Map<K, &V> {
+        let mut map = Map::new();
+
+        for (key, value) in self.storage.iter() {
+            map.insert(key, value);
+        }
+
+        map
+    }
+
+    /// Returns a wrapper which implements [`Display`][fmt::Display], formatting
+    /// each entry as `key=value` separated by `", "`.
+    ///
+    /// The separator can be changed with [`Display::separator`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::Map;
     ///
     /// let mut map = Map::new();
-    /// map.insert(Key::One, "a");
-    /// map.clear();
-    /// assert!(map.is_empty());
+    /// map.insert(b'a', 1);
+    /// map.insert(b'b', 2);
+    ///
+    /// assert_eq!(map.display().to_string(), "97=1, 98=2");
+    /// assert_eq!(format!("[{}]", map.display().separator(" | ")), "[97=1 | 98=2]");
     /// ```
     #[inline]
-    pub fn clear(&mut self) {
-        self.storage.clear();
+    pub fn display(&self) -> Display<'_, K, V> {
+        Display::new(self)
     }
 
     /// Returns true if the map contains no elements.
     ///
+    /// This isn't a `const fn`, since storage is accessed through the
+    /// [`MapStorage`] trait and trait methods can't be called in constant
+    /// contexts on stable Rust. The only map which can be built in a constant
+    /// context is [`Map::EMPTY`], which is always empty.
+    ///
     /// # Examples
     ///
     /// ```
@@ -781,6 +2675,78 @@ where
         self.storage.len()
     }
 
+    /// Returns the number of entries for which `f` returns `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Sensor {
+    ///     Front,
+    ///     Back(bool),
+    /// }
+    ///
+    /// let mut readings = Map::new();
+    /// readings.insert(Sensor::Front, Err("timeout"));
+    /// readings.insert(Sensor::Back(true), Ok(12));
+    /// readings.insert(Sensor::Back(false), Err("overflow"));
+    ///
+    /// assert_eq!(readings.count(|_, reading| reading.is_err()), 2);
+    /// assert_eq!(readings.count(|sensor, _| matches!(sensor, Sensor::Back(_))), 2);
+    /// ```
+    #[inline]
+    pub fn count<F>(&self, mut f: F) -> usize
+    where
+        F: FnMut(K, &V) -> bool,
+    {
+        self.storage
+            .iter()
+            .filter(|(key, value)| f(key.clone(), value))
+            .count()
+    }
+
+    /// Folds every entry in [key order](crate::Key#ordering) into an
+    /// accumulator, passing the key and a reference to the value to `f`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Grade {
+    ///     Exam,
+    ///     Homework(bool),
+    /// }
+    ///
+    /// let mut scores = Map::new();
+    /// scores.insert(Grade::Exam, 80);
+    /// scores.insert(Grade::Homework(true), 100);
+    /// scores.insert(Grade::Homework(false), 50);
+    ///
+    /// let weighted = scores.fold(0, |total, grade, score| {
+    ///     let weight = match grade {
+    ///         Grade::Exam => 2,
+    ///         Grade::Homework(_) => 1,
+    ///     };
+    ///
+    ///     total + weight * score
+    /// });
+    ///
+    /// assert_eq!(weighted, 310);
+    /// ```
+    #[inline]
+    pub fn fold<B, F>(&self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, K, &V) -> B,
+    {
+        self.storage
+            .iter()
+            .fold(init, |acc, (key, value)| f(acc, key, value))
+    }
+
     /// Gets the given key’s corresponding [`Entry`] in the [`Map`] for in-place manipulation.
     ///
     /// # Examples
@@ -830,6 +2796,218 @@ where
     pub fn entry(&mut self, key: K) -> Entry<'_, K::MapStorage<V>, K, V> {
         K::MapStorage::entry(&mut self.storage, key)
     }
+
+    /// Returns a mutable reference to the value for `key`, inserting `default`
+    /// first if the key is not present.
+    ///
+    /// This is a shorthand for `map.entry(key).or_insert(default)`. Use
+    /// [`Map::get_or_insert_with`] if the default is expensive to construct.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second(bool),
+    /// }
+    ///
+    /// let mut map: Map<Key, u32> = Map::new();
+    ///
+    /// assert_eq!(*map.get_or_insert(Key::Second(false), 10), 10);
+    /// *map.get_or_insert(Key::Second(false), 20) += 1;
+    ///
+    /// assert_eq!(map.get(Key::Second(false)), Some(&11));
+    /// assert_eq!(map.get(Key::First), None);
+    /// ```
+    #[inline]
+    pub fn get_or_insert(&mut self, key: K, default: V) -> &mut V {
+        self.entry(key).or_insert(default)
+    }
+
+    /// Returns a mutable reference to the value for `key`, inserting the
+    /// result of `f` first if the key is not present.
+    ///
+    /// This is a shorthand for `map.entry(key).or_insert_with(f)`, so the key
+    /// is only looked up once, which means it is only hashed once for keys
+    /// with dynamic storage.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second(bool),
+    /// }
+    ///
+    /// let mut map: Map<Key, Vec<u32>> = Map::new();
+    ///
+    /// map.get_or_insert_with(Key::Second(true), Vec::new).push(1);
+    /// map.get_or_insert_with(Key::Second(true), || vec![2; 10]).push(3);
+    ///
+    /// assert_eq!(map.get(Key::Second(true)), Some(&vec![1, 3]));
+    /// assert_eq!(map.get(Key::First), None);
+    /// ```
+    #[inline]
+    pub fn get_or_insert_with<F>(&mut self, key: K, f: F) -> &mut V
+    where
+        F: FnOnce() -> V,
+    {
+        self.entry(key).or_insert_with(f)
+    }
+
+    /// Returns a mutable reference to the value for `key`, inserting the
+    /// result of calling `f` with the key first if it is not present.
+    ///
+    /// This is useful when the initial value is derived from the key itself,
+    /// since `f` doesn't have to capture it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     First(u8),
+    ///     Second,
+    /// }
+    ///
+    /// let initial = |key| match key {
+    ///     Key::First(n) => u32::from(n),
+    ///     Key::Second => 0,
+    /// };
+    ///
+    /// let mut map = Map::new();
+    /// *map.get_or_insert_with_key(Key::First(7), initial) += 1;
+    /// *map.get_or_insert_with_key(Key::First(7), initial) += 1;
+    /// map.get_or_insert_with_key(Key::Second, initial);
+    ///
+    /// assert_eq!(map.get(Key::First(7)), Some(&9));
+    /// assert_eq!(map.get(Key::Second), Some(&0));
+    /// ```
+    #[inline]
+    pub fn get_or_insert_with_key<F>(&mut self, key: K, f: F) -> &mut V
+    where
+        F: FnOnce(K) -> V,
+    {
+        self.storage.get_or_insert_with_key(key, f)
+    }
+
+    /// Adds `by` to the value at `key`, starting from [`Default::default`] if
+    /// the key is absent, and returns a mutable reference to the updated
+    /// value.
+    ///
+    /// This is convenient when a map is used as a histogram.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Outcome {
+    ///     Hit,
+    ///     Miss,
+    /// }
+    ///
+    /// let mut counts = Map::new();
+    ///
+    /// for outcome in [Outcome::Hit, Outcome::Miss, Outcome::Hit] {
+    ///     counts.increment(outcome, 1u32);
+    /// }
+    ///
+    /// assert_eq!(counts.get(Outcome::Hit), Some(&2));
+    /// assert_eq!(counts.get(Outcome::Miss), Some(&1));
+    /// assert_eq!(*counts.increment(Outcome::Miss, 10), 11);
+    /// ```
+    #[inline]
+    pub fn increment(&mut self, key: K, by: V) -> &mut V
+    where
+        V: Default + AddAssign,
+    {
+        let value = self.entry(key).or_default();
+        *value += by;
+        value
+    }
+
+    /// Updates the entry for `key` with the function `f`.
+    ///
+    /// The current value, if any, is moved into `f` and whatever it returns is
+    /// stored back into the map. Returning `None` removes the entry, so this
+    /// can insert, modify or remove a value in a single call. A value which is
+    /// stored back keeps the key which was already in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second(bool),
+    /// }
+    ///
+    /// let mut map: Map<Key, Vec<u32>> = Map::new();
+    ///
+    /// // Insert.
+    /// map.update(Key::Second(true), |value| {
+    ///     assert!(value.is_none());
+    ///     Some(vec![1])
+    /// });
+    ///
+    /// // Modify.
+    /// map.update(Key::Second(true), |value| {
+    ///     let mut value = value?;
+    ///     value.push(2);
+    ///     Some(value)
+    /// });
+    ///
+    /// assert_eq!(map.get(Key::Second(true)), Some(&vec![1, 2]));
+    ///
+    /// // Remove.
+    /// map.update(Key::Second(true), |_| None);
+    /// assert!(map.is_empty());
+    ///
+    /// // Vacant entries stay vacant.
+    /// map.update(Key::First, |value| value);
+    /// assert!(map.is_empty());
+    /// ```
+    #[inline]
+    pub fn update<F>(&mut self, key: K, f: F)
+    where
+        F: FnOnce(Option<V>) -> Option<V>,
+    {
+        let entry = match self.storage.entry(key) {
+            Entry::Occupied(entry) => entry,
+            Entry::Vacant(entry) => {
+                if let Some(value) = f(None) {
+                    entry.insert(value);
+                }
+
+                return;
+            }
+        };
+
+        // Moving the value out of the occupied entry consumes it, so a value
+        // which is kept is put back through the vacant entry which is left
+        // behind. This keeps the key which was stored in the map.
+        let key = entry.key();
+
+        let Some(value) = f(Some(entry.remove())) else {
+            return;
+        };
+
+        if let Entry::Vacant(entry) = self.storage.entry(key) {
+            entry.insert(value);
+        }
+    }
 }
 
 /// [`Clone`] implementation for a [`Map`].
@@ -869,6 +3047,11 @@ where
             storage: self.storage.clone(),
         }
     }
+
+    #[inline]
+    fn clone_from(&mut self, source: &Map<K, V>) {
+        self.storage.clone_from(&source.storage);
+    }
 }
 
 /// The [`Copy`] implementation for a [`Map`] depends on its [`Key`]. If the
@@ -1025,6 +3208,75 @@ where
 {
 }
 
+/// [`PartialEq`] implementation between a [`Map`] and a
+/// [`HashMap`][std::collections::HashMap], which compares the entries in
+/// both.
+///
+/// This requires the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Key)]
+/// enum Key {
+///     First,
+///     Second(Option<bool>),
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(Key::First, 1);
+/// map.insert(Key::Second(None), 2);
+///
+/// let mut hash_map = HashMap::new();
+/// hash_map.insert(Key::First, 1);
+/// hash_map.insert(Key::Second(None), 2);
+///
+/// assert_eq!(map, hash_map);
+/// assert_eq!(hash_map, map);
+///
+/// hash_map.insert(Key::Second(None), 3);
+/// assert_ne!(map, hash_map);
+///
+/// hash_map.insert(Key::Second(None), 2);
+/// hash_map.insert(Key::Second(Some(true)), 4);
+/// assert_ne!(map, hash_map);
+/// assert_ne!(hash_map, map);
+/// ```
+#[cfg(feature = "std")]
+impl<K, V, S> PartialEq<std::collections::HashMap<K, V, S>> for Map<K, V>
+where
+    K: Key + Eq + Hash,
+    V: PartialEq,
+    S: core::hash::BuildHasher,
+{
+    #[inline]
+    fn eq(&self, other: &std::collections::HashMap<K, V, S>) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(&k) == Some(v))
+    }
+}
+
+/// [`PartialEq`] implementation between a
+/// [`HashMap`][std::collections::HashMap] and a [`Map`].
+///
+/// This requires the `std` feature. See the reverse implementation for an
+/// example.
+#[cfg(feature = "std")]
+impl<K, V, S> PartialEq<Map<K, V>> for std::collections::HashMap<K, V, S>
+where
+    K: Key + Eq + Hash,
+    V: PartialEq,
+    S: core::hash::BuildHasher,
+{
+    #[inline]
+    fn eq(&self, other: &Map<K, V>) -> bool {
+        other == self
+    }
+}
+
 /// [`Hash`] implementation for a [`Set`].
 ///
 /// [`Set`]: crate::Set
@@ -1271,8 +3523,8 @@ where
     }
 }
 
-/// Produce an owning iterator visiting all key-value pairs of the [`Map`] in an
-/// arbitrary order. The iterator element type is `(K, V)`.
+/// Produce an owning iterator visiting all key-value pairs of the [`Map`] in
+/// [key order](crate::Key#ordering). The iterator element type is `(K, V)`.
 ///
 /// # Examples
 ///
@@ -1379,6 +3631,78 @@ where
     }
 }
 
+/// Convert a [`HashMap`][std::collections::HashMap] into a [`Map`].
+///
+/// This requires the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Key)]
+/// enum Key {
+///     First,
+///     Second,
+/// }
+///
+/// let mut hash_map = HashMap::new();
+/// hash_map.insert(Key::Second, 2);
+///
+/// let map = Map::from(hash_map);
+/// assert!(map.iter().eq([(Key::Second, &2)]));
+/// ```
+#[cfg(feature = "std")]
+impl<K, V, S> From<std::collections::HashMap<K, V, S>> for Map<K, V>
+where
+    K: Key,
+{
+    #[inline]
+    fn from(map: std::collections::HashMap<K, V, S>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+/// Convert a [`Map`] into a [`HashMap`][std::collections::HashMap].
+///
+/// This requires the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Key)]
+/// enum Key {
+///     First,
+///     Second,
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(Key::First, 1);
+/// map.insert(Key::Second, 2);
+///
+/// let hash_map: HashMap<_, _> = HashMap::from(map);
+/// assert_eq!(hash_map.len(), 2);
+/// assert_eq!(hash_map.get(&Key::First), Some(&1));
+/// assert_eq!(hash_map.get(&Key::Second), Some(&2));
+/// ```
+#[cfg(feature = "std")]
+impl<K, V, S> From<Map<K, V>> for std::collections::HashMap<K, V, S>
+where
+    K: Key + Eq + Hash,
+    S: core::hash::BuildHasher + Default,
+{
+    #[inline]
+    fn from(map: Map<K, V>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
 #[cfg(feature = "serde")]
 impl<K, V> serde::Serialize for Map<K, V>
 where
@@ -1394,7 +3718,7 @@ where
 
         let mut map = serializer.serialize_map(Some(self.len()))?;
 
-        for (k, v) in self.iter() {
+        for (k, v) in self {
             map.serialize_entry(&k, v)?;
         }
 
@@ -1444,3 +3768,34 @@ where
         deserializer.deserialize_map(MapVisitor(core::marker::PhantomData))
     }
 }
+
+#[cfg(feature = "arbitrary")]
+impl<'a, K, V> arbitrary::Arbitrary<'a> for Map<K, V>
+where
+    K: Key + arbitrary::Arbitrary<'a>,
+    V: arbitrary::Arbitrary<'a>,
+{
+    #[inline]
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut map = Map::new();
+
+        for entry in u.arbitrary_iter()? {
+            let (key, value) = entry?;
+            map.insert(key, value);
+        }
+
+        Ok(map)
+    }
+
+    #[inline]
+    fn arbitrary_take_rest(u: arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut map = Map::new();
+
+        for entry in u.arbitrary_take_rest_iter()? {
+            let (key, value) = entry?;
+            map.insert(key, value);
+        }
+
+        Ok(map)
+    }
+}
@@ -0,0 +1,287 @@
+use core::fmt;
+use core::iter::FusedIterator;
+
+use crate::key::Key;
+use crate::map::{Iter, Map};
+
+/// A change to a single entry of a map.
+///
+/// This is produced by iterating over a [`MapDiff`], where it describes how
+/// the original map differs from the one it was compared against, and is
+/// reported to the observer of an
+/// [`ObservableMap`][crate::observe::ObservableMap] as the map is modified.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Change<'a, K, V> {
+    /// An entry was added for the key, with its value.
+    Added(K, &'a V),
+    /// The entry for the key was removed, with the value it had.
+    Removed(K, &'a V),
+    /// The value of the entry for the key changed, the old value followed by
+    /// the new one.
+    Changed(K, &'a V, &'a V),
+}
+
+impl<K, V> Clone for Change<'_, K, V>
+where
+    K: Copy,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V> Copy for Change<'_, K, V> where K: Copy {}
+
+impl<K, V> Change<'_, K, V>
+where
+    K: Copy,
+{
+    /// The key of the entry which changed.
+    #[inline]
+    pub fn key(&self) -> K {
+        match *self {
+            Change::Added(key, _) | Change::Removed(key, _) | Change::Changed(key, _, _) => key,
+        }
+    }
+}
+
+/// The differences between two maps, returned by [`Map::diff`].
+///
+/// The diff owns copies of the values which differ, so it can be kept around
+/// or applied with [`Map::apply_diff`] after the maps it was created from have
+/// been modified. Like a [`Map`] it doesn't allocate unless the key uses
+/// dynamic storage.
+///
+/// Iterating over it produces a [`Change`] for every key which differs. Keys
+/// which were removed come first, followed by the keys which were changed and
+/// lastly the keys which were added, each group in [key order].
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Map};
+/// use fixed_map::map::Change;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Setting {
+///     Volume,
+///     Brightness,
+///     Contrast,
+/// }
+///
+/// let local = Map::from_array([(Setting::Volume, 10), (Setting::Brightness, 50)]);
+/// let remote = Map::from_array([(Setting::Volume, 20), (Setting::Contrast, 5)]);
+///
+/// let diff = local.diff(&remote);
+///
+/// assert!(diff.iter().eq([
+///     Change::Removed(Setting::Brightness, &50),
+///     Change::Changed(Setting::Volume, &10, &20),
+///     Change::Added(Setting::Contrast, &5),
+/// ]));
+///
+/// let keys = diff.iter().map(|change| change.key());
+/// assert!(keys.eq([Setting::Brightness, Setting::Volume, Setting::Contrast]));
+/// ```
+///
+/// [key order]: crate::Key#ordering
+pub struct MapDiff<K, V>
+where
+    K: Key,
+{
+    removed: Map<K, V>,
+    changed: Map<K, (V, V)>,
+    added: Map<K, V>,
+}
+
+impl<K, V> MapDiff<K, V>
+where
+    K: Key,
+{
+    #[inline]
+    pub(crate) fn new(old: &Map<K, V>, new: &Map<K, V>) -> Self
+    where
+        V: PartialEq + Clone,
+    {
+        let mut removed = Map::new();
+        let mut changed = Map::new();
+        let mut added = Map::new();
+
+        for (key, old_value) in old {
+            match new.get(key.clone()) {
+                None => {
+                    removed.insert(key, old_value.clone());
+                }
+                Some(new_value) if new_value != old_value => {
+                    changed.insert(key, (old_value.clone(), new_value.clone()));
+                }
+                Some(..) => {}
+            }
+        }
+
+        for (key, new_value) in new {
+            if !old.contains_key(key.clone()) {
+                added.insert(key, new_value.clone());
+            }
+        }
+
+        Self {
+            removed,
+            changed,
+            added,
+        }
+    }
+
+    /// An iterator over the changes between the two maps.
+    #[inline]
+    pub fn iter(&self) -> DiffIter<'_, K, V> {
+        DiffIter {
+            removed: self.removed.iter(),
+            changed: self.changed.iter(),
+            added: self.added.iter(),
+        }
+    }
+
+    /// Returns the number of keys which differ between the two maps.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::Map;
+    ///
+    /// let a = Map::from_array([(false, 1), (true, 2)]);
+    /// let b = Map::from_array([(true, 3)]);
+    ///
+    /// assert_eq!(a.diff(&b).len(), 2);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.removed.len() + self.changed.len() + self.added.len()
+    }
+
+    /// Returns `true` if the two maps are equal, so there are no changes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::Map;
+    ///
+    /// let a = Map::from_array([(true, 1)]);
+    /// let b = Map::from_array([(true, 2)]);
+    ///
+    /// assert!(a.diff(&a).is_empty());
+    /// assert!(!a.diff(&b).is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.removed.is_empty() && self.changed.is_empty() && self.added.is_empty()
+    }
+
+    /// Apply the changes to `map`, see [`Map::apply_diff`].
+    #[inline]
+    pub(crate) fn apply_to(&self, map: &mut Map<K, V>)
+    where
+        V: Clone,
+    {
+        for key in self.removed.keys() {
+            map.remove(key);
+        }
+
+        for (key, (_, new)) in &self.changed {
+            map.insert(key, new.clone());
+        }
+
+        for (key, value) in &self.added {
+            map.insert(key, value.clone());
+        }
+    }
+}
+
+impl<K, V> Clone for MapDiff<K, V>
+where
+    K: Key,
+    K::MapStorage<V>: Clone,
+    K::MapStorage<(V, V)>: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            removed: self.removed.clone(),
+            changed: self.changed.clone(),
+            added: self.added.clone(),
+        }
+    }
+}
+
+impl<K, V> fmt::Debug for MapDiff<K, V>
+where
+    K: Key + fmt::Debug,
+    V: fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, K, V> IntoIterator for &'a MapDiff<K, V>
+where
+    K: Key,
+{
+    type Item = Change<'a, K, V>;
+    type IntoIter = DiffIter<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the changes in a [`MapDiff`].
+///
+/// See [`MapDiff::iter`].
+pub struct DiffIter<'a, K, V>
+where
+    K: Key,
+    K::MapStorage<V>: 'a,
+    K::MapStorage<(V, V)>: 'a,
+    V: 'a,
+{
+    removed: Iter<'a, K, V>,
+    changed: Iter<'a, K, (V, V)>,
+    added: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> Iterator for DiffIter<'a, K, V>
+where
+    K: Key,
+    K::MapStorage<V>: 'a,
+    K::MapStorage<(V, V)>: 'a,
+    V: 'a,
+{
+    type Item = Change<'a, K, V>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some((key, value)) = self.removed.next() {
+            return Some(Change::Removed(key, value));
+        }
+
+        if let Some((key, (old, new))) = self.changed.next() {
+            return Some(Change::Changed(key, old, new));
+        }
+
+        let (key, value) = self.added.next()?;
+        Some(Change::Added(key, value))
+    }
+}
+
+impl<'a, K, V> FusedIterator for DiffIter<'a, K, V>
+where
+    K: Key,
+    K::MapStorage<V>: 'a,
+    K::MapStorage<(V, V)>: 'a,
+    V: 'a,
+{
+}
@@ -0,0 +1,100 @@
+use core::fmt;
+
+use crate::key::Key;
+use crate::map::{Map, MapStorage};
+
+/// Wrapper returned by [`Map::display`] which formats the entries of a map
+/// using their [`Display`][fmt::Display] implementations.
+///
+/// Entries are formatted as `key=value` and joined by `", "` unless another
+/// separator is picked with [`Display::separator`].
+///
+/// # Examples
+///
+/// ```
+/// use core::fmt;
+///
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Clone, Copy, Key)]
+/// enum Feature {
+///     Audio,
+///     Video,
+/// }
+///
+/// impl fmt::Display for Feature {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         match self {
+///             Feature::Audio => f.write_str("audio"),
+///             Feature::Video => f.write_str("video"),
+///         }
+///     }
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(Feature::Audio, true);
+/// map.insert(Feature::Video, false);
+///
+/// assert_eq!(map.display().to_string(), "audio=true, video=false");
+/// assert_eq!(map.display().separator(" ").to_string(), "audio=true video=false");
+/// ```
+pub struct Display<'a, K, V>
+where
+    K: Key,
+{
+    map: &'a Map<K, V>,
+    separator: &'a str,
+}
+
+impl<'a, K, V> Display<'a, K, V>
+where
+    K: Key,
+{
+    #[inline]
+    pub(crate) fn new(map: &'a Map<K, V>) -> Self {
+        Self {
+            map,
+            separator: ", ",
+        }
+    }
+
+    /// Use `separator` between entries instead of the default `", "`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::Map;
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(1u8, "one");
+    /// map.insert(2u8, "two");
+    ///
+    /// assert_eq!(map.display().separator(";").to_string(), "1=one;2=two");
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn separator(self, separator: &'a str) -> Self {
+        Self { separator, ..self }
+    }
+}
+
+impl<K, V> fmt::Display for Display<'_, K, V>
+where
+    K: Key + fmt::Display,
+    V: fmt::Display,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut it = self.map.storage.iter();
+
+        if let Some((key, value)) = it.next() {
+            write!(f, "{key}={value}")?;
+
+            for (key, value) in it {
+                write!(f, "{}{key}={value}", self.separator)?;
+            }
+        }
+
+        Ok(())
+    }
+}
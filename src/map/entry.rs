@@ -204,6 +204,57 @@ where
         }
     }
 
+    /// Returns `true` if the entry is occupied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    /// }
+    ///
+    /// let mut map: Map<Key, i32> = Map::new();
+    /// map.insert(Key::First, 1);
+    ///
+    /// assert!(map.entry(Key::First).is_occupied());
+    /// assert!(!map.entry(Key::Second).is_occupied());
+    /// ```
+    #[inline]
+    pub fn is_occupied(&self) -> bool {
+        matches!(self, Entry::Occupied(..))
+    }
+
+    /// Returns `true` if the entry is vacant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Key {
+    ///     First(bool),
+    ///     Second,
+    /// }
+    ///
+    /// let mut map: Map<Key, i32> = Map::new();
+    /// map.insert(Key::First(true), 1);
+    ///
+    /// let entry = map.entry(Key::First(false));
+    /// assert!(entry.is_vacant());
+    /// entry.or_insert(2);
+    ///
+    /// assert!(!map.entry(Key::First(false)).is_vacant());
+    /// ```
+    #[inline]
+    pub fn is_vacant(&self) -> bool {
+        matches!(self, Entry::Vacant(..))
+    }
+
     /// Provides in-place mutable access to an occupied entry before any
     /// potential inserts into the map.
     ///
@@ -305,6 +356,35 @@ where
     /// map.entry(Key::First(false)).or_default();
     /// assert_eq!(map.get(Key::First(false)), Some(&0));
     /// ```
+    ///
+    /// The returned reference borrows from the map, so values which are
+    /// collections themselves can be modified directly, including nested maps:
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Outer {
+    ///     First(Option<bool>),
+    ///     Second,
+    /// }
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Inner {
+    ///     Left,
+    ///     Right,
+    /// }
+    ///
+    /// let mut lists: Map<Outer, Vec<u32>> = Map::new();
+    /// lists.entry(Outer::First(None)).or_default().push(1);
+    /// lists.entry(Outer::First(None)).or_default().push(2);
+    /// assert_eq!(lists.get(Outer::First(None)), Some(&vec![1, 2]));
+    ///
+    /// let mut nested: Map<Outer, Map<Inner, u32>> = Map::new();
+    /// *nested.entry(Outer::Second).or_default().entry(Inner::Right).or_default() += 1;
+    /// *nested.entry(Outer::Second).or_default().entry(Inner::Right).or_default() += 1;
+    /// assert_eq!(nested.get(Outer::Second).and_then(|m| m.get(Inner::Right)), Some(&2));
+    /// ```
     #[inline]
     pub fn or_default(self) -> &'a mut V
     where
@@ -0,0 +1,96 @@
+use core::fmt;
+
+use crate::map::{MapStorage, OccupiedEntry};
+
+/// The error returned by [`try_insert`][crate::Map::try_insert] when the key
+/// already exists.
+///
+/// Contains the occupied entry, and the value that was not inserted.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Map};
+/// use fixed_map::map::OccupiedEntry;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Key {
+///     First,
+///     Second,
+/// }
+///
+/// let mut map: Map<Key, u32> = Map::new();
+/// map.insert(Key::First, 1);
+///
+/// let error = map.try_insert(Key::First, 2).unwrap_err();
+/// assert_eq!(error.entry.key(), Key::First);
+/// assert_eq!(error.entry.get(), &1);
+/// assert_eq!(error.value, 2);
+/// ```
+pub struct OccupiedError<'a, S: 'a, K, V>
+where
+    S: MapStorage<K, V>,
+{
+    /// The entry in the map that was already occupied.
+    pub entry: S::Occupied<'a>,
+    /// The value which was not inserted, because the entry was already
+    /// occupied.
+    pub value: V,
+}
+
+impl<'a, S: 'a, K, V> fmt::Debug for OccupiedError<'a, S, K, V>
+where
+    S: MapStorage<K, V>,
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OccupiedError")
+            .field("key", &self.entry.key())
+            .field("old_value", self.entry.get())
+            .field("new_value", &self.value)
+            .finish()
+    }
+}
+
+/// [`Display`][fmt::Display] implementation for an [`OccupiedError`].
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, Key)]
+/// enum Key {
+///     First,
+///     Second,
+/// }
+///
+/// let mut map: Map<Key, u32> = Map::new();
+/// map.insert(Key::First, 1);
+///
+/// let error = map.try_insert(Key::First, 2).unwrap_err();
+///
+/// assert_eq!(
+///     error.to_string(),
+///     "failed to insert 2, key First already exists with value 1"
+/// );
+/// ```
+impl<'a, S: 'a, K, V> fmt::Display for OccupiedError<'a, S, K, V>
+where
+    S: MapStorage<K, V>,
+    K: fmt::Debug,
+    V: fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to insert {:?}, key {:?} already exists with value {:?}",
+            self.value,
+            self.entry.key(),
+            self.entry.get(),
+        )
+    }
+}
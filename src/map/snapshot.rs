@@ -0,0 +1,101 @@
+use core::fmt;
+
+use crate::key::Key;
+use crate::map::Map;
+
+/// A saved copy of the entries of a [`Map`], created with [`Map::snapshot`]
+/// and restored with [`Map::restore`].
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Account {
+///     Checking,
+///     Savings,
+/// }
+///
+/// let mut balances = Map::from_array([(Account::Checking, 100), (Account::Savings, 50)]);
+/// let snapshot = balances.snapshot();
+///
+/// // Speculatively move more money than is available.
+/// *balances.entry(Account::Checking).or_default() -= 150;
+/// *balances.entry(Account::Savings).or_default() += 150;
+///
+/// if balances.values().any(|&balance| balance < 0) {
+///     balances.restore(&snapshot);
+/// }
+///
+/// assert_eq!(balances.get(Account::Checking), Some(&100));
+/// assert_eq!(balances.get(Account::Savings), Some(&50));
+/// ```
+pub struct Snapshot<K, V>
+where
+    K: Key,
+{
+    map: Map<K, V>,
+}
+
+impl<K, V> Snapshot<K, V>
+where
+    K: Key,
+{
+    #[inline]
+    pub(crate) fn new(map: Map<K, V>) -> Self {
+        Self { map }
+    }
+
+    #[inline]
+    pub(crate) fn map(&self) -> &Map<K, V> {
+        &self.map
+    }
+
+    /// Convert the snapshot into a map with the saved entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::Map;
+    ///
+    /// let mut map = Map::from_array([(true, 'a')]);
+    /// let snapshot = map.snapshot();
+    /// map.clear();
+    ///
+    /// assert_eq!(snapshot.into_map(), Map::from_array([(true, 'a')]));
+    /// ```
+    #[inline]
+    pub fn into_map(self) -> Map<K, V> {
+        self.map
+    }
+}
+
+impl<K, V> Clone for Snapshot<K, V>
+where
+    K: Key,
+    K::MapStorage<V>: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            map: self.map.clone(),
+        }
+    }
+
+    #[inline]
+    fn clone_from(&mut self, source: &Self) {
+        self.map.clone_from(&source.map);
+    }
+}
+
+impl<K, V> fmt::Debug for Snapshot<K, V>
+where
+    K: Key + fmt::Debug,
+    V: fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Snapshot").field(&self.map).finish()
+    }
+}
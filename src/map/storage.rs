@@ -1,19 +1,42 @@
 //! Module that defines the [`Storage`] trait.
 
+mod array;
+pub use self::array::ArrayMapStorage;
+
 mod boolean;
 pub(crate) use self::boolean::BooleanMapStorage;
 
+mod byte;
+pub(crate) use self::byte::ByteMapStorage;
+
+#[cfg(feature = "alloc")]
+mod btree;
+#[cfg(feature = "alloc")]
+pub use self::btree::BTreeMapStorage;
+
 #[cfg(feature = "hashbrown")]
 mod hashbrown;
 #[cfg(feature = "hashbrown")]
-pub(crate) use self::hashbrown::HashbrownMapStorage;
+pub use self::hashbrown::HashbrownMapStorage;
 
 mod option;
 pub(crate) use self::option::OptionMapStorage;
 
+mod result;
+pub(crate) use self::result::ResultMapStorage;
+
 mod singleton;
 pub(crate) use self::singleton::SingletonMapStorage;
 
+mod tuple;
+pub(crate) use self::tuple::TupleMapStorage;
+
+use core::iter::FusedIterator;
+use core::mem;
+
+#[cfg(feature = "rayon")]
+use rayon::iter::ParallelIterator;
+
 use crate::map::Entry;
 
 /// The trait defining how storage works.
@@ -24,36 +47,36 @@ use crate::map::Entry;
 /// - `V` is the value being stored.
 pub trait MapStorage<K, V>: Sized {
     /// Immutable iterator over storage.
-    type Iter<'this>: Iterator<Item = (K, &'this V)>
+    type Iter<'this>: ExactSizeIterator<Item = (K, &'this V)> + FusedIterator
     where
         Self: 'this,
         V: 'this;
 
     /// Immutable iterator over keys in storage.
-    type Keys<'this>: Iterator<Item = K>
+    type Keys<'this>: ExactSizeIterator<Item = K> + FusedIterator
     where
         Self: 'this;
 
     /// Immutable iterator over values in storage.
-    type Values<'this>: Iterator<Item = &'this V>
+    type Values<'this>: ExactSizeIterator<Item = &'this V> + FusedIterator
     where
         Self: 'this,
         V: 'this;
 
     /// Mutable iterator over storage.
-    type IterMut<'this>: Iterator<Item = (K, &'this mut V)>
+    type IterMut<'this>: ExactSizeIterator<Item = (K, &'this mut V)> + FusedIterator
     where
         Self: 'this,
         V: 'this;
 
     /// Mutable iterator over values in storage.
-    type ValuesMut<'this>: Iterator<Item = &'this mut V>
+    type ValuesMut<'this>: ExactSizeIterator<Item = &'this mut V> + FusedIterator
     where
         Self: 'this,
         V: 'this;
 
     /// Consuming iterator.
-    type IntoIter: Iterator<Item = (K, V)>;
+    type IntoIter: ExactSizeIterator<Item = (K, V)> + FusedIterator;
 
     /// An occupied entry.
     type Occupied<'this>: OccupiedEntry<'this, K, V>
@@ -94,6 +117,36 @@ pub trait MapStorage<K, V>: Sized {
     where
         F: FnMut(K, &mut V) -> bool;
 
+    /// This is the storage abstraction for [`Map::retain_with_removed`][crate::Map::retain_with_removed].
+    ///
+    /// The default implementation moves every entry out of the storage and
+    /// inserts the ones which are kept back into it, which loses any memory the
+    /// storage has allocated. Storage which can remove entries in place should
+    /// override it.
+    #[inline]
+    fn retain_with_removed<F, R>(&mut self, mut keep: F, mut on_remove: R)
+    where
+        K: Clone,
+        F: FnMut(K, &mut V) -> bool,
+        R: FnMut(K, V),
+    {
+        let entries = mem::replace(self, Self::empty()).into_iter();
+        let mut rebuild = Rebuild {
+            storage: self,
+            entries,
+        };
+
+        rebuild.storage.reserve(rebuild.entries.len());
+
+        for (key, mut value) in rebuild.entries.by_ref() {
+            if keep(key.clone(), &mut value) {
+                rebuild.storage.insert(key, value);
+            } else {
+                on_remove(key, value);
+            }
+        }
+    }
+
     /// This is the storage abstraction for [`Map::clear`][crate::Map::clear].
     fn clear(&mut self);
 
@@ -117,6 +170,121 @@ pub trait MapStorage<K, V>: Sized {
 
     /// This is the storage abstraction for [`Map::entry`][crate::Map::entry].
     fn entry(&mut self, key: K) -> Entry<'_, Self, K, V>;
+
+    /// This is the storage abstraction for [`Map::get_or_insert_with_key`][crate::Map::get_or_insert_with_key].
+    ///
+    /// The default implementation goes through [`MapStorage::entry`], so the
+    /// key is only looked up once and `f` is only called if it is vacant.
+    #[inline]
+    fn get_or_insert_with_key<F>(&mut self, key: K, f: F) -> &mut V
+    where
+        F: FnOnce(K) -> V,
+    {
+        self.entry(key).or_insert_with_key(f)
+    }
+
+    /// This is the storage abstraction for [`Map::reserve`][crate::Map::reserve].
+    ///
+    /// The default implementation does nothing, which is appropriate for
+    /// storage that never allocates.
+    #[inline]
+    fn reserve(&mut self, additional: usize) {
+        let _ = additional;
+    }
+
+    /// This is the storage abstraction for [`Map::shrink_to_fit`][crate::Map::shrink_to_fit].
+    ///
+    /// The default implementation does nothing, which is appropriate for
+    /// storage that never allocates.
+    #[inline]
+    fn shrink_to_fit(&mut self) {}
+
+    /// This is the storage abstraction for [`Map::memory_footprint`][crate::Map::memory_footprint].
+    ///
+    /// Returns the usable capacity of the storage multiplied by the size of an
+    /// entry. The default implementation returns `0`, which is appropriate for
+    /// storage that never allocates.
+    #[inline]
+    fn heap_size(&self) -> usize {
+        0
+    }
+
+    /// This is the storage abstraction for [`Map::load_factor`][crate::Map::load_factor].
+    ///
+    /// Returns the number of entries the storage can hold before it next
+    /// grows. The default implementation returns [`MapStorage::len`],
+    /// which is appropriate for storage that never allocates.
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.len()
+    }
+}
+
+/// Puts the entries which haven't been visited yet back into the storage if
+/// the default implementation of [`MapStorage::retain_with_removed`] unwinds.
+struct Rebuild<'a, S, K, V>
+where
+    S: MapStorage<K, V>,
+{
+    storage: &'a mut S,
+    entries: S::IntoIter,
+}
+
+impl<S, K, V> Drop for Rebuild<'_, S, K, V>
+where
+    S: MapStorage<K, V>,
+{
+    #[inline]
+    fn drop(&mut self) {
+        for (key, value) in &mut self.entries {
+            self.storage.insert(key, value);
+        }
+    }
+}
+
+/// Storage which can be constructed in a constant context.
+///
+/// This is implemented for storage which doesn't allocate, and is what allows
+/// [`Map::EMPTY`][crate::Map::EMPTY] to be used in `const` and `static` items.
+pub trait ConstMapStorage<K, V>: MapStorage<K, V> {
+    /// Empty storage.
+    const EMPTY: Self;
+}
+
+/// Storage which supports parallel iteration with [`rayon`].
+///
+/// This is implemented for the array storage of unit enum keys,
+/// [`ArrayMapStorage`], [`u8`], and
+/// for dynamic keys backed by `hashbrown`.
+///
+/// [`rayon`]: https://docs.rs/rayon
+#[cfg(feature = "rayon")]
+pub trait ParMapStorage<K, V>: MapStorage<K, V> {
+    /// Parallel iterator over storage.
+    type ParIter<'this>: ParallelIterator<Item = (K, &'this V)>
+    where
+        Self: 'this,
+        V: 'this;
+
+    /// Parallel iterator over keys in storage.
+    type ParKeys<'this>: ParallelIterator<Item = K>
+    where
+        Self: 'this;
+
+    /// Parallel iterator over values in storage.
+    type ParValues<'this>: ParallelIterator<Item = &'this V>
+    where
+        Self: 'this,
+        V: 'this;
+
+    /// This is the storage abstraction for [`Map::par_iter`][crate::Map::par_iter].
+    fn par_iter(&self) -> Self::ParIter<'_>;
+
+    /// This is the storage abstraction for [`Map::par_keys`][crate::Map::par_keys].
+    fn par_keys(&self) -> Self::ParKeys<'_>;
+
+    /// This is the storage abstraction for [`Map::par_values`][crate::Map::par_values].
+    fn par_values(&self) -> Self::ParValues<'_>;
 }
 
 /// A view into an occupied entry in a [`Map`][crate::Map]. It is part of the
@@ -220,6 +388,33 @@ pub trait OccupiedEntry<'a, K, V> {
     /// ```
     fn get(&self) -> &V;
 
+    /// Gets a copy of the key stored in the map together with a reference to
+    /// the value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    /// use fixed_map::map::{Entry, OccupiedEntry};
+    ///
+    /// #[derive(Clone, Copy, Key, Debug, PartialEq)]
+    /// enum Key {
+    ///     First(Option<bool>),
+    ///     Second,
+    /// }
+    ///
+    /// let mut map: Map<Key, i32> = Map::new();
+    /// map.insert(Key::First(None), 12);
+    ///
+    /// if let Entry::Occupied(occupied) = map.entry(Key::First(None)) {
+    ///     assert_eq!(occupied.get_key_value(), (Key::First(None), &12));
+    /// }
+    /// ```
+    #[inline]
+    fn get_key_value(&self) -> (K, &V) {
+        (self.key(), self.get())
+    }
+
     /// Gets a mutable reference to the value in the entry.
     ///
     /// If you need a reference to the `OccupiedEntry` which may
@@ -419,6 +614,95 @@ pub trait OccupiedEntry<'a, K, V> {
     /// assert_eq!(map.contains_key(Key::First(true)), false);
     /// ```
     fn remove(self) -> V;
+
+    /// Replaces the key stored in the map with `key`, returning the key that
+    /// was previously stored.
+    ///
+    /// This is only meaningful for keys which compare equal while still
+    /// carrying different data, and is supported by storage backed by
+    /// `hashbrown`. `key` must be equal to the key of the entry, replacing it
+    /// with one that isn't is a logic error. Storage which can't replace its
+    /// keys, such as the storage of unit variants or of `#[key(ordered)]`
+    /// variants, leaves the entry unchanged and returns `key`. For tuple keys
+    /// only the second element of the key is replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    /// use fixed_map::map::{Entry, OccupiedEntry};
+    ///
+    /// #[derive(Clone, Copy, Key, Debug, PartialEq)]
+    /// enum Key {
+    ///     First(bool),
+    ///     Second,
+    /// }
+    ///
+    /// let mut map: Map<Key, i32> = Map::new();
+    /// map.insert(Key::First(true), 12);
+    ///
+    /// if let Entry::Occupied(occupied) = map.entry(Key::First(true)) {
+    ///     assert_eq!(occupied.replace_key(Key::First(true)), Key::First(true));
+    /// }
+    ///
+    /// assert_eq!(map.get(Key::First(true)), Some(&12));
+    /// ```
+    ///
+    /// Replacing a key which carries data that isn't compared:
+    ///
+    /// ```
+    /// # #[cfg(feature = "hashbrown")] {
+    /// use core::hash::{Hash, Hasher};
+    ///
+    /// use fixed_map::{Key, Map};
+    /// use fixed_map::map::{Entry, OccupiedEntry};
+    /// use fixed_map::map::storage::HashbrownMapStorage;
+    /// use fixed_map::set::storage::HashbrownSetStorage;
+    ///
+    /// /// A user id, which remembers when it was last seen.
+    /// #[derive(Debug, Clone, Copy)]
+    /// struct User {
+    ///     id: u32,
+    ///     seen: u32,
+    /// }
+    ///
+    /// impl fixed_map::key::Key for User {
+    ///     type MapStorage<V> = HashbrownMapStorage<User, V>;
+    ///     type SetStorage = HashbrownSetStorage<User>;
+    /// }
+    ///
+    /// impl PartialEq for User {
+    ///     fn eq(&self, other: &Self) -> bool {
+    ///         self.id == other.id
+    ///     }
+    /// }
+    ///
+    /// impl Eq for User {}
+    ///
+    /// impl Hash for User {
+    ///     fn hash<H: Hasher>(&self, state: &mut H) {
+    ///         self.id.hash(state);
+    ///     }
+    /// }
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum Visitor {
+    ///     User(User),
+    ///     Guest,
+    /// }
+    ///
+    /// let mut map = Map::new();
+    /// map.insert(Visitor::User(User { id: 1, seen: 10 }), "alice");
+    ///
+    /// if let Entry::Occupied(occupied) = map.entry(Visitor::User(User { id: 1, seen: 0 })) {
+    ///     let old = occupied.replace_key(Visitor::User(User { id: 1, seen: 20 }));
+    ///     assert!(matches!(old, Visitor::User(User { seen: 10, .. })));
+    /// }
+    ///
+    /// assert!(matches!(map.keys().next(), Some(Visitor::User(User { seen: 20, .. }))));
+    /// # }
+    /// ```
+    fn replace_key(self, key: K) -> K;
 }
 
 /// A view into a vacant entry in a [`Map`][crate::Map].
@@ -470,6 +754,30 @@ pub trait VacantEntry<'a, K, V> {
     /// ```
     fn key(&self) -> K;
 
+    /// Take ownership of the key without inserting a value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Map};
+    /// use fixed_map::map::{Entry, VacantEntry};
+    ///
+    /// #[derive(Clone, Copy, Key, Debug, PartialEq)]
+    /// enum Key {
+    ///     First(bool),
+    ///     Second,
+    /// }
+    ///
+    /// let mut map: Map<Key, i32> = Map::new();
+    ///
+    /// if let Entry::Vacant(vacant) = map.entry(Key::First(true)) {
+    ///     assert_eq!(vacant.into_key(), Key::First(true));
+    /// }
+    ///
+    /// assert!(map.is_empty());
+    /// ```
+    fn into_key(self) -> K;
+
     /// Sets the value of the entry with the `VacantEntry`’s key,
     /// and returns a mutable reference to it.
     ///
@@ -0,0 +1,519 @@
+use core::array;
+use core::cmp::Ordering;
+use core::iter;
+use core::slice;
+
+#[cfg(feature = "rayon")]
+use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
+
+use crate::iter::ExactSize;
+use crate::key::FixedKey;
+use crate::macro_support::{__storage_iterator_cmp, __storage_iterator_partial_cmp};
+#[cfg(feature = "rayon")]
+use crate::map::ParMapStorage;
+use crate::map::{ConstMapStorage, Entry, MapStorage, OccupiedEntry, VacantEntry};
+use crate::option_bucket::{NoneBucket, OptionBucket, SomeBucket};
+
+type Iter<'a, K, V> = ExactSize<
+    iter::FilterMap<
+        iter::Enumerate<slice::Iter<'a, Option<V>>>,
+        fn((usize, &'a Option<V>)) -> Option<(K, &'a V)>,
+    >,
+>;
+type Keys<'a, K, V> = ExactSize<
+    iter::FilterMap<
+        iter::Enumerate<slice::Iter<'a, Option<V>>>,
+        fn((usize, &'a Option<V>)) -> Option<K>,
+    >,
+>;
+type Values<'a, V> = ExactSize<iter::Flatten<slice::Iter<'a, Option<V>>>>;
+type IterMut<'a, K, V> = ExactSize<
+    iter::FilterMap<
+        iter::Enumerate<slice::IterMut<'a, Option<V>>>,
+        fn((usize, &'a mut Option<V>)) -> Option<(K, &'a mut V)>,
+    >,
+>;
+type ValuesMut<'a, V> = ExactSize<iter::Flatten<slice::IterMut<'a, Option<V>>>>;
+type IntoIter<K, V, const N: usize> = ExactSize<
+    iter::FilterMap<
+        iter::Enumerate<array::IntoIter<Option<V>, N>>,
+        fn((usize, Option<V>)) -> Option<(K, V)>,
+    >,
+>;
+#[cfg(feature = "rayon")]
+type ParIter<'a, K, V> = rayon::iter::FilterMap<
+    rayon::iter::Enumerate<rayon::slice::Iter<'a, Option<V>>>,
+    fn((usize, &'a Option<V>)) -> Option<(K, &'a V)>,
+>;
+#[cfg(feature = "rayon")]
+type ParKeys<'a, K, V> = rayon::iter::FilterMap<
+    rayon::iter::Enumerate<rayon::slice::Iter<'a, Option<V>>>,
+    fn((usize, &'a Option<V>)) -> Option<K>,
+>;
+#[cfg(feature = "rayon")]
+type ParValues<'a, V> = rayon::iter::Flatten<rayon::slice::Iter<'a, Option<V>>>;
+
+/// [`MapStorage`] for keys with a fixed number of values, stored as an array
+/// with `N` slots.
+///
+/// This is the same layout `#[derive(Key)]` uses for enums with only unit
+/// variants, made available so that it can be used with a hand-written
+/// [`Key`][crate::Key] implementation. The key must implement [`FixedKey`],
+/// and each key is stored in the slot given by [`FixedKey::index`].
+///
+/// # Contract
+///
+/// For every value of the key, [`FixedKey::index`] must return a number in
+/// `0..N` and [`FixedKey::from_index`] must return the same key when given
+/// that number. `N` is usually equal to [`FixedKey::COUNT`], but keys whose
+/// indexes are sparse need one slot more than their largest index.
+///
+/// Breaking this contract doesn't cause undefined behavior, but operations on
+/// the map may panic or skip entries.
+///
+/// # Examples
+///
+/// A key for the sixteen values a nibble can take, which pairs this with
+/// [`BitsetSetStorage`][crate::set::storage::BitsetSetStorage] for sets:
+///
+/// ```
+/// use core::iter::Copied;
+/// use core::slice;
+///
+/// use fixed_map::{Map, Set};
+/// use fixed_map::key::{FixedKey, Key};
+/// use fixed_map::map::storage::ArrayMapStorage;
+/// use fixed_map::set::storage::BitsetSetStorage;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// struct Nibble(u8);
+///
+/// impl Nibble {
+///     fn new(value: u8) -> Option<Self> {
+///         (value < 16).then_some(Nibble(value))
+///     }
+/// }
+///
+/// static NIBBLES: [Nibble; 16] = {
+///     let mut all = [Nibble(0); 16];
+///     let mut n = 0;
+///
+///     while n < 16 {
+///         all[n] = Nibble(n as u8);
+///         n += 1;
+///     }
+///
+///     all
+/// };
+///
+/// impl Key for Nibble {
+///     type MapStorage<V> = ArrayMapStorage<V, 16>;
+///     type SetStorage = BitsetSetStorage<1>;
+/// }
+///
+/// impl FixedKey for Nibble {
+///     const COUNT: usize = 16;
+///
+///     fn keys() -> Copied<slice::Iter<'static, Self>> {
+///         NIBBLES.iter().copied()
+///     }
+///
+///     fn index(self) -> usize {
+///         usize::from(self.0)
+///     }
+///
+///     fn from_index(index: usize) -> Option<Self> {
+///         Nibble::new(u8::try_from(index).ok()?)
+///     }
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(Nibble(0xf), "high");
+/// map.insert(Nibble(0x0), "low");
+///
+/// assert_eq!(map.get(Nibble(0xf)), Some(&"high"));
+/// assert!(map.keys().eq([Nibble(0x0), Nibble(0xf)]));
+///
+/// let set = Set::from_array([Nibble(3), Nibble(1)]);
+/// assert!(set.iter().eq([Nibble(1), Nibble(3)]));
+/// ```
+#[derive(Copy, PartialEq, Eq, Hash)]
+pub struct ArrayMapStorage<V, const N: usize> {
+    data: [Option<V>; N],
+    len: usize,
+}
+
+impl<V, const N: usize> Clone for ArrayMapStorage<V, N>
+where
+    V: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            len: self.len,
+        }
+    }
+
+    #[inline]
+    fn clone_from(&mut self, source: &Self) {
+        self.data.clone_from(&source.data);
+        self.len = source.len;
+    }
+}
+
+impl<V, const N: usize> PartialOrd for ArrayMapStorage<V, N>
+where
+    V: PartialOrd,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        __storage_iterator_partial_cmp(&self.data, &other.data)
+    }
+}
+
+impl<V, const N: usize> Ord for ArrayMapStorage<V, N>
+where
+    V: Ord,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        __storage_iterator_cmp(&self.data, &other.data)
+    }
+}
+
+pub struct Vacant<'a, K, V> {
+    key: K,
+    inner: NoneBucket<'a, V>,
+    len: &'a mut usize,
+}
+
+pub struct Occupied<'a, K, V> {
+    key: K,
+    inner: SomeBucket<'a, V>,
+    len: &'a mut usize,
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V> for Vacant<'a, K, V>
+where
+    K: FixedKey,
+{
+    #[inline]
+    fn key(&self) -> K {
+        self.key
+    }
+
+    #[inline]
+    fn into_key(self) -> K {
+        self.key
+    }
+
+    #[inline]
+    fn insert(self, value: V) -> &'a mut V {
+        *self.len += 1;
+        self.inner.insert(value)
+    }
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> for Occupied<'a, K, V>
+where
+    K: FixedKey,
+{
+    #[inline]
+    fn key(&self) -> K {
+        self.key
+    }
+
+    #[inline]
+    fn get(&self) -> &V {
+        self.inner.as_ref()
+    }
+
+    #[inline]
+    fn get_mut(&mut self) -> &mut V {
+        self.inner.as_mut()
+    }
+
+    #[inline]
+    fn into_mut(self) -> &'a mut V {
+        self.inner.into_mut()
+    }
+
+    #[inline]
+    fn insert(&mut self, value: V) -> V {
+        self.inner.replace(value)
+    }
+
+    #[inline]
+    fn remove(self) -> V {
+        *self.len -= 1;
+        self.inner.take()
+    }
+
+    #[inline]
+    fn replace_key(self, key: K) -> K {
+        key
+    }
+}
+
+impl<V, const N: usize> ArrayMapStorage<V, N> {
+    const NONE: Option<V> = None;
+}
+
+impl<K, V, const N: usize> ConstMapStorage<K, V> for ArrayMapStorage<V, N>
+where
+    K: FixedKey,
+{
+    const EMPTY: Self = Self {
+        data: [Self::NONE; N],
+        len: 0,
+    };
+}
+
+impl<K, V, const N: usize> MapStorage<K, V> for ArrayMapStorage<V, N>
+where
+    K: FixedKey,
+{
+    type Iter<'this>
+        = Iter<'this, K, V>
+    where
+        V: 'this;
+    type Keys<'this>
+        = Keys<'this, K, V>
+    where
+        V: 'this;
+    type Values<'this>
+        = Values<'this, V>
+    where
+        V: 'this;
+    type IterMut<'this>
+        = IterMut<'this, K, V>
+    where
+        V: 'this;
+    type ValuesMut<'this>
+        = ValuesMut<'this, V>
+    where
+        V: 'this;
+    type IntoIter = IntoIter<K, V, N>;
+    type Occupied<'this>
+        = Occupied<'this, K, V>
+    where
+        V: 'this;
+    type Vacant<'this>
+        = Vacant<'this, K, V>
+    where
+        V: 'this;
+
+    #[inline]
+    fn empty() -> Self {
+        Self {
+            data: array::from_fn(|_| None),
+            len: 0,
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let old = self.data[key.index()].replace(value);
+
+        if old.is_none() {
+            self.len += 1;
+        }
+
+        old
+    }
+
+    #[inline]
+    fn contains_key(&self, key: K) -> bool {
+        self.data[key.index()].is_some()
+    }
+
+    #[inline]
+    fn get(&self, key: K) -> Option<&V> {
+        self.data[key.index()].as_ref()
+    }
+
+    #[inline]
+    fn get_mut(&mut self, key: K) -> Option<&mut V> {
+        self.data[key.index()].as_mut()
+    }
+
+    #[inline]
+    fn remove(&mut self, key: K) -> Option<V> {
+        let old = self.data[key.index()].take();
+
+        if old.is_some() {
+            self.len -= 1;
+        }
+
+        old
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, mut func: F)
+    where
+        F: FnMut(K, &mut V) -> bool,
+    {
+        for (index, entry) in self.data.iter_mut().enumerate() {
+            if let (Some(value), Some(key)) = (entry.as_mut(), K::from_index(index)) {
+                if !func(key, value) {
+                    *entry = None;
+                    self.len -= 1;
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn retain_with_removed<F, R>(&mut self, mut keep: F, mut on_remove: R)
+    where
+        F: FnMut(K, &mut V) -> bool,
+        R: FnMut(K, V),
+    {
+        for (index, entry) in self.data.iter_mut().enumerate() {
+            if let (OptionBucket::Some(mut bucket), Some(key)) =
+                (OptionBucket::new(entry), K::from_index(index))
+            {
+                if !keep(key, bucket.as_mut()) {
+                    self.len -= 1;
+                    on_remove(key, bucket.take());
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        for entry in &mut self.data {
+            *entry = None;
+        }
+
+        self.len = 0;
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        let map: fn(_) -> _ = |(index, value): (usize, &Option<V>)| {
+            let value = value.as_ref()?;
+            Some((K::from_index(index)?, value))
+        };
+
+        ExactSize::new(self.data.iter().enumerate().filter_map(map), self.len)
+    }
+
+    #[inline]
+    fn keys(&self) -> Self::Keys<'_> {
+        let map: fn(_) -> _ = |(index, value): (usize, &Option<V>)| {
+            value.as_ref()?;
+            K::from_index(index)
+        };
+
+        ExactSize::new(self.data.iter().enumerate().filter_map(map), self.len)
+    }
+
+    #[inline]
+    fn values(&self) -> Self::Values<'_> {
+        ExactSize::new(self.data.iter().flatten(), self.len)
+    }
+
+    #[inline]
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        let len = self.len;
+
+        let map: fn(_) -> _ = |(index, value): (usize, &mut Option<V>)| {
+            let value = value.as_mut()?;
+            Some((K::from_index(index)?, value))
+        };
+
+        ExactSize::new(self.data.iter_mut().enumerate().filter_map(map), len)
+    }
+
+    #[inline]
+    fn values_mut(&mut self) -> Self::ValuesMut<'_> {
+        let len = self.len;
+        ExactSize::new(self.data.iter_mut().flatten(), len)
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        let len = self.len;
+
+        let map: fn(_) -> _ = |(index, value): (usize, Option<V>)| {
+            let value = value?;
+            Some((K::from_index(index)?, value))
+        };
+
+        ExactSize::new(self.data.into_iter().enumerate().filter_map(map), len)
+    }
+
+    #[inline]
+    fn entry(&mut self, key: K) -> Entry<'_, Self, K, V> {
+        match OptionBucket::new(&mut self.data[key.index()]) {
+            OptionBucket::Some(inner) => Entry::Occupied(Occupied {
+                key,
+                inner,
+                len: &mut self.len,
+            }),
+            OptionBucket::None(inner) => Entry::Vacant(Vacant {
+                key,
+                inner,
+                len: &mut self.len,
+            }),
+        }
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<K, V, const N: usize> ParMapStorage<K, V> for ArrayMapStorage<V, N>
+where
+    K: FixedKey + Send,
+    V: Sync,
+{
+    type ParIter<'this>
+        = ParIter<'this, K, V>
+    where
+        V: 'this;
+    type ParKeys<'this>
+        = ParKeys<'this, K, V>
+    where
+        V: 'this;
+    type ParValues<'this>
+        = ParValues<'this, V>
+    where
+        V: 'this;
+
+    #[inline]
+    fn par_iter(&self) -> Self::ParIter<'_> {
+        let map: fn(_) -> _ = |(index, value): (usize, &Option<V>)| {
+            let value = value.as_ref()?;
+            Some((K::from_index(index)?, value))
+        };
+
+        self.data.par_iter().enumerate().filter_map(map)
+    }
+
+    #[inline]
+    fn par_keys(&self) -> Self::ParKeys<'_> {
+        let map: fn(_) -> _ = |(index, value): (usize, &Option<V>)| {
+            value.as_ref()?;
+            K::from_index(index)
+        };
+
+        self.data.par_iter().enumerate().filter_map(map)
+    }
+
+    #[inline]
+    fn par_values(&self) -> Self::ParValues<'_> {
+        self.data.par_iter().flatten()
+    }
+}
@@ -1,33 +1,42 @@
 // Iterators are confusing if they impl `Copy`.
 #![allow(missing_copy_implementations)]
 
-use core::iter;
-use core::mem;
+use core::iter::{self, FusedIterator};
 use core::option;
 
-use crate::map::{Entry, MapStorage, OccupiedEntry, VacantEntry};
+use crate::iter::ExactSize;
+use crate::map::{ConstMapStorage, Entry, MapStorage, OccupiedEntry, VacantEntry};
 use crate::option_bucket::{NoneBucket, OptionBucket, SomeBucket};
 
 const TRUE_BIT: u8 = 0b10;
 const FALSE_BIT: u8 = 0b01;
 
-type Iter<'a, V> = iter::Chain<
-    iter::Map<option::Iter<'a, V>, fn(&'a V) -> (bool, &'a V)>,
-    iter::Map<option::Iter<'a, V>, fn(&'a V) -> (bool, &'a V)>,
+type Iter<'a, V> = ExactSize<
+    iter::Chain<
+        iter::Map<option::Iter<'a, V>, fn(&'a V) -> (bool, &'a V)>,
+        iter::Map<option::Iter<'a, V>, fn(&'a V) -> (bool, &'a V)>,
+    >,
 >;
-type Values<'a, V> = iter::Chain<option::Iter<'a, V>, option::Iter<'a, V>>;
-type IterMut<'a, V> = iter::Chain<
-    iter::Map<option::IterMut<'a, V>, fn(&'a mut V) -> (bool, &'a mut V)>,
-    iter::Map<option::IterMut<'a, V>, fn(&'a mut V) -> (bool, &'a mut V)>,
+type Values<'a, V> = ExactSize<iter::Chain<option::Iter<'a, V>, option::Iter<'a, V>>>;
+type IterMut<'a, V> = ExactSize<
+    iter::Chain<
+        iter::Map<option::IterMut<'a, V>, fn(&'a mut V) -> (bool, &'a mut V)>,
+        iter::Map<option::IterMut<'a, V>, fn(&'a mut V) -> (bool, &'a mut V)>,
+    >,
 >;
-type ValuesMut<'a, V> = iter::Chain<option::IterMut<'a, V>, option::IterMut<'a, V>>;
-type IntoIter<V> = iter::Chain<
-    iter::Map<option::IntoIter<V>, fn(V) -> (bool, V)>,
-    iter::Map<option::IntoIter<V>, fn(V) -> (bool, V)>,
+type ValuesMut<'a, V> = ExactSize<iter::Chain<option::IterMut<'a, V>, option::IterMut<'a, V>>>;
+type IntoIter<V> = ExactSize<
+    iter::Chain<
+        iter::Map<option::IntoIter<V>, fn(V) -> (bool, V)>,
+        iter::Map<option::IntoIter<V>, fn(V) -> (bool, V)>,
+    >,
 >;
 
 /// [`MapStorage`] for [`bool`] types.
 ///
+/// Entries are iterated over with `false` before `true`, matching the ordering
+/// of [`bool`].
+///
 /// # Examples
 ///
 /// ```
@@ -66,14 +75,33 @@ type IntoIter<V> = iter::Chain<
 /// a.insert(Key::Bool(true), 1);
 /// a.insert(Key::Bool(false), 2);
 ///
-/// assert!(a.iter().eq([(Key::Bool(true), &1), (Key::Bool(false), &2)]));
-/// assert_eq!(a.iter().rev().collect::<Vec<_>>(), vec![(Key::Bool(false), &2), (Key::Bool(true), &1)]);
+/// assert!(a.iter().eq([(Key::Bool(false), &2), (Key::Bool(true), &1)]));
+/// assert_eq!(a.iter().rev().collect::<Vec<_>>(), vec![(Key::Bool(true), &1), (Key::Bool(false), &2)]);
 /// ```
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Copy, PartialEq, Eq)]
 pub struct BooleanMapStorage<V> {
-    t: Option<V>,
     f: Option<V>,
+    t: Option<V>,
+}
+
+impl<V> Clone for BooleanMapStorage<V>
+where
+    V: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            f: self.f.clone(),
+            t: self.t.clone(),
+        }
+    }
+
+    #[inline]
+    fn clone_from(&mut self, source: &Self) {
+        self.f.clone_from(&source.f);
+        self.t.clone_from(&source.t);
+    }
 }
 
 /// See [`BooleanMapStorage::keys`].
@@ -93,16 +121,16 @@ impl Iterator for Keys {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        if self.bits & TRUE_BIT != 0 {
-            self.bits &= !TRUE_BIT;
-            return Some(true);
-        }
-
         if self.bits & FALSE_BIT != 0 {
             self.bits &= !FALSE_BIT;
             return Some(false);
         }
 
+        if self.bits & TRUE_BIT != 0 {
+            self.bits &= !TRUE_BIT;
+            return Some(true);
+        }
+
         None
     }
 
@@ -116,16 +144,16 @@ impl Iterator for Keys {
 impl DoubleEndedIterator for Keys {
     #[inline]
     fn next_back(&mut self) -> Option<Self::Item> {
-        if self.bits & FALSE_BIT != 0 {
-            self.bits &= !FALSE_BIT;
-            return Some(false);
-        }
-
         if self.bits & TRUE_BIT != 0 {
             self.bits &= !TRUE_BIT;
             return Some(true);
         }
 
+        if self.bits & FALSE_BIT != 0 {
+            self.bits &= !FALSE_BIT;
+            return Some(false);
+        }
+
         None
     }
 }
@@ -137,6 +165,8 @@ impl ExactSizeIterator for Keys {
     }
 }
 
+impl FusedIterator for Keys {}
+
 pub struct Vacant<'a, V> {
     key: bool,
     inner: NoneBucket<'a, V>,
@@ -153,6 +183,11 @@ impl<'a, V> VacantEntry<'a, bool, V> for Vacant<'a, V> {
         self.key
     }
 
+    #[inline]
+    fn into_key(self) -> bool {
+        self.key
+    }
+
     #[inline]
     fn insert(self, value: V) -> &'a mut V {
         self.inner.insert(value)
@@ -189,23 +224,53 @@ impl<'a, V> OccupiedEntry<'a, bool, V> for Occupied<'a, V> {
     fn remove(self) -> V {
         self.inner.take()
     }
+
+    #[inline]
+    fn replace_key(self, key: bool) -> bool {
+        key
+    }
+}
+
+impl<V> ConstMapStorage<bool, V> for BooleanMapStorage<V> {
+    const EMPTY: Self = Self { f: None, t: None };
 }
 
 impl<V> MapStorage<bool, V> for BooleanMapStorage<V> {
-    type Iter<'this> = Iter<'this, V> where V: 'this;
-    type Keys<'this> = Keys where V: 'this;
-    type Values<'this> = Values<'this, V> where V: 'this;
-    type IterMut<'this> = IterMut<'this, V> where V: 'this;
-    type ValuesMut<'this> = ValuesMut<'this, V> where V: 'this;
+    type Iter<'this>
+        = Iter<'this, V>
+    where
+        V: 'this;
+    type Keys<'this>
+        = Keys
+    where
+        V: 'this;
+    type Values<'this>
+        = Values<'this, V>
+    where
+        V: 'this;
+    type IterMut<'this>
+        = IterMut<'this, V>
+    where
+        V: 'this;
+    type ValuesMut<'this>
+        = ValuesMut<'this, V>
+    where
+        V: 'this;
     type IntoIter = IntoIter<V>;
-    type Occupied<'this> = Occupied<'this, V> where V: 'this;
-    type Vacant<'this> = Vacant<'this, V> where V: 'this;
+    type Occupied<'this>
+        = Occupied<'this, V>
+    where
+        V: 'this;
+    type Vacant<'this>
+        = Vacant<'this, V>
+    where
+        V: 'this;
 
     #[inline]
     fn empty() -> Self {
         Self {
-            t: Option::default(),
             f: Option::default(),
+            t: Option::default(),
         }
     }
 
@@ -222,9 +287,9 @@ impl<V> MapStorage<bool, V> for BooleanMapStorage<V> {
     #[inline]
     fn insert(&mut self, key: bool, value: V) -> Option<V> {
         if key {
-            mem::replace(&mut self.t, Some(value))
+            self.t.replace(value)
         } else {
-            mem::replace(&mut self.f, Some(value))
+            self.f.replace(value)
         }
     }
 
@@ -258,9 +323,9 @@ impl<V> MapStorage<bool, V> for BooleanMapStorage<V> {
     #[inline]
     fn remove(&mut self, key: bool) -> Option<V> {
         if key {
-            mem::replace(&mut self.t, None)
+            self.t.take()
         } else {
-            mem::replace(&mut self.f, None)
+            self.f.take()
         }
     }
 
@@ -269,14 +334,29 @@ impl<V> MapStorage<bool, V> for BooleanMapStorage<V> {
     where
         F: FnMut(bool, &mut V) -> bool,
     {
+        if let Some(f) = self.f.as_mut() {
+            if !func(false, f) {
+                self.f = None;
+            }
+        }
         if let Some(t) = self.t.as_mut() {
             if !func(true, t) {
                 self.t = None;
             }
         }
-        if let Some(f) = self.f.as_mut() {
-            if !func(false, f) {
-                self.f = None;
+    }
+
+    #[inline]
+    fn retain_with_removed<F, R>(&mut self, mut keep: F, mut on_remove: R)
+    where
+        F: FnMut(bool, &mut V) -> bool,
+        R: FnMut(bool, V),
+    {
+        for (key, entry) in [(false, &mut self.f), (true, &mut self.t)] {
+            if let OptionBucket::Some(mut bucket) = OptionBucket::new(entry) {
+                if !keep(key, bucket.as_mut()) {
+                    on_remove(key, bucket.take());
+                }
             }
         }
     }
@@ -289,11 +369,11 @@ impl<V> MapStorage<bool, V> for BooleanMapStorage<V> {
 
     #[inline]
     fn iter(&self) -> Self::Iter<'_> {
-        let map: fn(_) -> _ = |v| (true, v);
-        let a = self.t.iter().map(map);
         let map: fn(_) -> _ = |v| (false, v);
-        let b = self.f.iter().map(map);
-        a.chain(b)
+        let a = self.f.iter().map(map);
+        let map: fn(_) -> _ = |v| (true, v);
+        let b = self.t.iter().map(map);
+        ExactSize::new(a.chain(b), self.len())
     }
 
     #[inline]
@@ -306,30 +386,33 @@ impl<V> MapStorage<bool, V> for BooleanMapStorage<V> {
 
     #[inline]
     fn values(&self) -> Self::Values<'_> {
-        self.t.iter().chain(self.f.iter())
+        ExactSize::new(self.f.iter().chain(self.t.iter()), self.len())
     }
 
     #[inline]
     fn iter_mut(&mut self) -> Self::IterMut<'_> {
-        let map: fn(_) -> _ = |v| (true, v);
-        let a = self.t.iter_mut().map(map);
+        let len = self.len();
         let map: fn(_) -> _ = |v| (false, v);
-        let b = self.f.iter_mut().map(map);
-        a.chain(b)
+        let a = self.f.iter_mut().map(map);
+        let map: fn(_) -> _ = |v| (true, v);
+        let b = self.t.iter_mut().map(map);
+        ExactSize::new(a.chain(b), len)
     }
 
     #[inline]
     fn values_mut(&mut self) -> Self::ValuesMut<'_> {
-        self.t.iter_mut().chain(self.f.iter_mut())
+        let len = self.len();
+        ExactSize::new(self.f.iter_mut().chain(self.t.iter_mut()), len)
     }
 
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
-        let map: fn(_) -> _ = |v| (true, v);
-        let a = self.t.into_iter().map(map);
+        let len = self.len();
         let map: fn(_) -> _ = |v| (false, v);
-        let b = self.f.into_iter().map(map);
-        a.chain(b)
+        let a = self.f.into_iter().map(map);
+        let map: fn(_) -> _ = |v| (true, v);
+        let b = self.t.into_iter().map(map);
+        ExactSize::new(a.chain(b), len)
     }
 
     #[inline]
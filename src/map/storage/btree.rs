@@ -0,0 +1,314 @@
+use core::iter;
+use core::mem::size_of;
+use core::ops::Bound;
+
+use alloc::collections::btree_map;
+use alloc::collections::BTreeMap;
+
+use crate::map::{Entry, MapStorage, OccupiedEntry, VacantEntry};
+
+/// [`MapStorage`] for dynamic types, using [`BTreeMap`].
+///
+/// Unlike the storage used for `hashbrown`-backed keys, this iterates over keys
+/// in ascending order. It is used for variants which are marked with
+/// `#[key(ordered)]`.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Key {
+///     #[key(ordered)]
+///     Score(u32),
+///     Unranked,
+/// }
+///
+/// let mut map = Map::new();
+/// map.insert(Key::Score(30), "c");
+/// map.insert(Key::Unranked, "d");
+/// map.insert(Key::Score(10), "a");
+/// map.insert(Key::Score(20), "b");
+///
+/// assert!(map.iter().eq([
+///     (Key::Score(10), &"a"),
+///     (Key::Score(20), &"b"),
+///     (Key::Score(30), &"c"),
+///     (Key::Unranked, &"d"),
+/// ]));
+/// ```
+#[repr(transparent)]
+pub struct BTreeMapStorage<K, V> {
+    inner: BTreeMap<K, V>,
+}
+
+impl<K, V> Clone for BTreeMapStorage<K, V>
+where
+    K: Clone,
+    V: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+
+    #[inline]
+    fn clone_from(&mut self, source: &Self) {
+        self.inner.clone_from(&source.inner);
+    }
+}
+
+impl<K, V> PartialEq for BTreeMapStorage<K, V>
+where
+    K: PartialEq,
+    V: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.inner.eq(&other.inner)
+    }
+}
+
+impl<K, V> Eq for BTreeMapStorage<K, V>
+where
+    K: Eq,
+    V: Eq,
+{
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> for btree_map::OccupiedEntry<'a, K, V>
+where
+    K: Clone + Ord,
+{
+    #[inline]
+    fn key(&self) -> K {
+        self.key().clone()
+    }
+
+    #[inline]
+    fn get(&self) -> &V {
+        self.get()
+    }
+
+    #[inline]
+    fn get_mut(&mut self) -> &mut V {
+        self.get_mut()
+    }
+
+    #[inline]
+    fn into_mut(self) -> &'a mut V {
+        self.into_mut()
+    }
+
+    #[inline]
+    fn insert(&mut self, value: V) -> V {
+        self.insert(value)
+    }
+
+    #[inline]
+    fn remove(self) -> V {
+        self.remove()
+    }
+
+    #[inline]
+    fn replace_key(self, key: K) -> K {
+        key
+    }
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V> for btree_map::VacantEntry<'a, K, V>
+where
+    K: Clone + Ord,
+{
+    #[inline]
+    fn key(&self) -> K {
+        self.key().clone()
+    }
+
+    #[inline]
+    fn into_key(self) -> K {
+        self.into_key()
+    }
+
+    #[inline]
+    fn insert(self, value: V) -> &'a mut V {
+        self.insert(value)
+    }
+}
+
+impl<K, V> MapStorage<K, V> for BTreeMapStorage<K, V>
+where
+    K: Clone + Ord,
+{
+    type Iter<'this>
+        = iter::Map<btree_map::Iter<'this, K, V>, fn((&'this K, &'this V)) -> (K, &'this V)>
+    where
+        K: 'this,
+        V: 'this;
+    type Keys<'this>
+        = iter::Cloned<btree_map::Keys<'this, K, V>>
+    where
+        K: 'this,
+        V: 'this;
+    type Values<'this>
+        = btree_map::Values<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type IterMut<'this>
+        = iter::Map<
+        btree_map::IterMut<'this, K, V>,
+        fn((&'this K, &'this mut V)) -> (K, &'this mut V),
+    >
+    where
+        K: 'this,
+        V: 'this;
+    type ValuesMut<'this>
+        = btree_map::ValuesMut<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type IntoIter = btree_map::IntoIter<K, V>;
+    type Occupied<'this>
+        = btree_map::OccupiedEntry<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type Vacant<'this>
+        = btree_map::VacantEntry<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+
+    #[inline]
+    fn empty() -> Self {
+        Self {
+            inner: BTreeMap::new(),
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    #[inline]
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.inner.insert(key, value)
+    }
+
+    #[inline]
+    fn contains_key(&self, key: K) -> bool {
+        self.inner.contains_key(&key)
+    }
+
+    #[inline]
+    fn get(&self, key: K) -> Option<&V> {
+        self.inner.get(&key)
+    }
+
+    #[inline]
+    fn get_mut(&mut self, key: K) -> Option<&mut V> {
+        self.inner.get_mut(&key)
+    }
+
+    #[inline]
+    fn remove(&mut self, key: K) -> Option<V> {
+        self.inner.remove(&key)
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, mut func: F)
+    where
+        F: FnMut(K, &mut V) -> bool,
+    {
+        self.inner.retain(|k, v| func(k.clone(), v));
+    }
+
+    #[inline]
+    fn retain_with_removed<F, R>(&mut self, mut keep: F, mut on_remove: R)
+    where
+        F: FnMut(K, &mut V) -> bool,
+        R: FnMut(K, V),
+    {
+        let mut next = self.inner.keys().next().cloned();
+
+        while let Some(key) = next {
+            let Some(value) = self.inner.get_mut(&key) else {
+                break;
+            };
+
+            let kept = keep(key.clone(), value);
+
+            next = self
+                .inner
+                .range((Bound::Excluded(&key), Bound::Unbounded))
+                .next()
+                .map(|(k, _)| k.clone());
+
+            if !kept {
+                if let Some(value) = self.inner.remove(&key) {
+                    on_remove(key, value);
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        let map: fn(_) -> _ = |(k, v): (&K, &V)| (k.clone(), v);
+        self.inner.iter().map(map)
+    }
+
+    #[inline]
+    fn keys(&self) -> Self::Keys<'_> {
+        self.inner.keys().cloned()
+    }
+
+    #[inline]
+    fn values(&self) -> Self::Values<'_> {
+        self.inner.values()
+    }
+
+    #[inline]
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        let map: fn(_) -> _ = |(k, v): (&K, &mut V)| (k.clone(), v);
+        self.inner.iter_mut().map(map)
+    }
+
+    #[inline]
+    fn values_mut(&mut self) -> Self::ValuesMut<'_> {
+        self.inner.values_mut()
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.inner.into_iter()
+    }
+
+    #[inline]
+    fn entry(&mut self, key: K) -> Entry<'_, Self, K, V> {
+        match self.inner.entry(key) {
+            btree_map::Entry::Occupied(entry) => Entry::Occupied(entry),
+            btree_map::Entry::Vacant(entry) => Entry::Vacant(entry),
+        }
+    }
+
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.inner.len() * size_of::<(K, V)>()
+    }
+}
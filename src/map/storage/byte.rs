@@ -0,0 +1,409 @@
+use core::array;
+use core::iter;
+use core::ops::RangeInclusive;
+use core::slice;
+
+#[cfg(feature = "rayon")]
+use rayon::iter::{
+    IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator, ParallelIterator,
+};
+
+use crate::iter::ExactSize;
+#[cfg(feature = "rayon")]
+use crate::map::ParMapStorage;
+use crate::map::{ConstMapStorage, Entry, MapStorage, OccupiedEntry, VacantEntry};
+use crate::option_bucket::{NoneBucket, OptionBucket, SomeBucket};
+
+const LEN: usize = 1 << u8::BITS;
+
+type Iter<'a, V> = ExactSize<
+    iter::FilterMap<
+        iter::Zip<RangeInclusive<u8>, slice::Iter<'a, Option<V>>>,
+        fn((u8, &'a Option<V>)) -> Option<(u8, &'a V)>,
+    >,
+>;
+type Keys<'a, V> = ExactSize<
+    iter::FilterMap<
+        iter::Zip<RangeInclusive<u8>, slice::Iter<'a, Option<V>>>,
+        fn((u8, &'a Option<V>)) -> Option<u8>,
+    >,
+>;
+type Values<'a, V> = ExactSize<iter::Flatten<slice::Iter<'a, Option<V>>>>;
+type IterMut<'a, V> = ExactSize<
+    iter::FilterMap<
+        iter::Zip<RangeInclusive<u8>, slice::IterMut<'a, Option<V>>>,
+        fn((u8, &'a mut Option<V>)) -> Option<(u8, &'a mut V)>,
+    >,
+>;
+type ValuesMut<'a, V> = ExactSize<iter::Flatten<slice::IterMut<'a, Option<V>>>>;
+type IntoIter<V> = ExactSize<
+    iter::FilterMap<
+        iter::Zip<RangeInclusive<u8>, array::IntoIter<Option<V>, LEN>>,
+        fn((u8, Option<V>)) -> Option<(u8, V)>,
+    >,
+>;
+#[cfg(feature = "rayon")]
+type ParIter<'a, V> = rayon::iter::FilterMap<
+    rayon::iter::Zip<rayon::range_inclusive::Iter<u8>, rayon::slice::Iter<'a, Option<V>>>,
+    fn((u8, &'a Option<V>)) -> Option<(u8, &'a V)>,
+>;
+#[cfg(feature = "rayon")]
+type ParKeys<'a, V> = rayon::iter::FilterMap<
+    rayon::iter::Zip<rayon::range_inclusive::Iter<u8>, rayon::slice::Iter<'a, Option<V>>>,
+    fn((u8, &'a Option<V>)) -> Option<u8>,
+>;
+#[cfg(feature = "rayon")]
+type ParValues<'a, V> = rayon::iter::Flatten<rayon::slice::Iter<'a, Option<V>>>;
+
+/// [`MapStorage`] for [`u8`] types.
+///
+/// This is stored as an array with one slot for each possible byte, so lookups
+/// are constant-time and iteration happens in ascending byte order.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::Map;
+///
+/// let mut map = Map::new();
+/// map.insert(b'z', "letter");
+/// map.insert(b'0', "digit");
+/// map.insert(b' ', "space");
+///
+/// assert_eq!(map.get(b'0'), Some(&"digit"));
+/// assert_eq!(map.get(b'1'), None);
+///
+/// assert!(map.keys().eq([b' ', b'0', b'z']));
+/// assert!(map.values().rev().eq([&"letter", &"digit", &"space"]));
+/// ```
+#[derive(Copy, PartialEq, Eq)]
+pub struct ByteMapStorage<V> {
+    data: [Option<V>; LEN],
+    len: usize,
+}
+
+impl<V> Clone for ByteMapStorage<V>
+where
+    V: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            len: self.len,
+        }
+    }
+
+    #[inline]
+    fn clone_from(&mut self, source: &Self) {
+        self.data.clone_from(&source.data);
+        self.len = source.len;
+    }
+}
+
+pub struct Vacant<'a, V> {
+    key: u8,
+    inner: NoneBucket<'a, V>,
+    len: &'a mut usize,
+}
+
+pub struct Occupied<'a, V> {
+    key: u8,
+    inner: SomeBucket<'a, V>,
+    len: &'a mut usize,
+}
+
+impl<'a, V> VacantEntry<'a, u8, V> for Vacant<'a, V> {
+    #[inline]
+    fn key(&self) -> u8 {
+        self.key
+    }
+
+    #[inline]
+    fn into_key(self) -> u8 {
+        self.key
+    }
+
+    #[inline]
+    fn insert(self, value: V) -> &'a mut V {
+        *self.len += 1;
+        self.inner.insert(value)
+    }
+}
+
+impl<'a, V> OccupiedEntry<'a, u8, V> for Occupied<'a, V> {
+    #[inline]
+    fn key(&self) -> u8 {
+        self.key
+    }
+
+    #[inline]
+    fn get(&self) -> &V {
+        self.inner.as_ref()
+    }
+
+    #[inline]
+    fn get_mut(&mut self) -> &mut V {
+        self.inner.as_mut()
+    }
+
+    #[inline]
+    fn into_mut(self) -> &'a mut V {
+        self.inner.into_mut()
+    }
+
+    #[inline]
+    fn insert(&mut self, value: V) -> V {
+        self.inner.replace(value)
+    }
+
+    #[inline]
+    fn remove(self) -> V {
+        *self.len -= 1;
+        self.inner.take()
+    }
+
+    #[inline]
+    fn replace_key(self, key: u8) -> u8 {
+        key
+    }
+}
+
+impl<V> ByteMapStorage<V> {
+    const NONE: Option<V> = None;
+}
+
+impl<V> ConstMapStorage<u8, V> for ByteMapStorage<V> {
+    const EMPTY: Self = Self {
+        data: [Self::NONE; LEN],
+        len: 0,
+    };
+}
+
+impl<V> MapStorage<u8, V> for ByteMapStorage<V> {
+    type Iter<'this>
+        = Iter<'this, V>
+    where
+        V: 'this;
+    type Keys<'this>
+        = Keys<'this, V>
+    where
+        V: 'this;
+    type Values<'this>
+        = Values<'this, V>
+    where
+        V: 'this;
+    type IterMut<'this>
+        = IterMut<'this, V>
+    where
+        V: 'this;
+    type ValuesMut<'this>
+        = ValuesMut<'this, V>
+    where
+        V: 'this;
+    type IntoIter = IntoIter<V>;
+    type Occupied<'this>
+        = Occupied<'this, V>
+    where
+        V: 'this;
+    type Vacant<'this>
+        = Vacant<'this, V>
+    where
+        V: 'this;
+
+    #[inline]
+    fn empty() -> Self {
+        Self {
+            data: array::from_fn(|_| None),
+            len: 0,
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    fn insert(&mut self, key: u8, value: V) -> Option<V> {
+        let old = self.data[usize::from(key)].replace(value);
+
+        if old.is_none() {
+            self.len += 1;
+        }
+
+        old
+    }
+
+    #[inline]
+    fn contains_key(&self, key: u8) -> bool {
+        self.data[usize::from(key)].is_some()
+    }
+
+    #[inline]
+    fn get(&self, key: u8) -> Option<&V> {
+        self.data[usize::from(key)].as_ref()
+    }
+
+    #[inline]
+    fn get_mut(&mut self, key: u8) -> Option<&mut V> {
+        self.data[usize::from(key)].as_mut()
+    }
+
+    #[inline]
+    fn remove(&mut self, key: u8) -> Option<V> {
+        let old = self.data[usize::from(key)].take();
+
+        if old.is_some() {
+            self.len -= 1;
+        }
+
+        old
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, mut func: F)
+    where
+        F: FnMut(u8, &mut V) -> bool,
+    {
+        for (key, entry) in (0..=u8::MAX).zip(&mut self.data) {
+            if let Some(value) = entry.as_mut() {
+                if !func(key, value) {
+                    *entry = None;
+                    self.len -= 1;
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn retain_with_removed<F, R>(&mut self, mut keep: F, mut on_remove: R)
+    where
+        F: FnMut(u8, &mut V) -> bool,
+        R: FnMut(u8, V),
+    {
+        for (key, entry) in (0..=u8::MAX).zip(&mut self.data) {
+            if let OptionBucket::Some(mut bucket) = OptionBucket::new(entry) {
+                if !keep(key, bucket.as_mut()) {
+                    self.len -= 1;
+                    on_remove(key, bucket.take());
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        for entry in &mut self.data {
+            *entry = None;
+        }
+
+        self.len = 0;
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        let map: fn(_) -> _ = |(k, v): (u8, &Option<V>)| Some((k, v.as_ref()?));
+        ExactSize::new(
+            (0..=u8::MAX).zip(self.data.iter()).filter_map(map),
+            self.len,
+        )
+    }
+
+    #[inline]
+    fn keys(&self) -> Self::Keys<'_> {
+        let map: fn(_) -> _ = |(k, v): (u8, &Option<V>)| v.is_some().then_some(k);
+        ExactSize::new(
+            (0..=u8::MAX).zip(self.data.iter()).filter_map(map),
+            self.len,
+        )
+    }
+
+    #[inline]
+    fn values(&self) -> Self::Values<'_> {
+        ExactSize::new(self.data.iter().flatten(), self.len)
+    }
+
+    #[inline]
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        let len = self.len;
+        let map: fn(_) -> _ = |(k, v): (u8, &mut Option<V>)| Some((k, v.as_mut()?));
+        ExactSize::new((0..=u8::MAX).zip(self.data.iter_mut()).filter_map(map), len)
+    }
+
+    #[inline]
+    fn values_mut(&mut self) -> Self::ValuesMut<'_> {
+        let len = self.len;
+        ExactSize::new(self.data.iter_mut().flatten(), len)
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        let len = self.len;
+        let map: fn(_) -> _ = |(k, v): (u8, Option<V>)| Some((k, v?));
+        ExactSize::new((0..=u8::MAX).zip(self.data).filter_map(map), len)
+    }
+
+    #[inline]
+    fn entry(&mut self, key: u8) -> Entry<'_, Self, u8, V> {
+        match OptionBucket::new(&mut self.data[usize::from(key)]) {
+            OptionBucket::Some(inner) => Entry::Occupied(Occupied {
+                key,
+                inner,
+                len: &mut self.len,
+            }),
+            OptionBucket::None(inner) => Entry::Vacant(Vacant {
+                key,
+                inner,
+                len: &mut self.len,
+            }),
+        }
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<V> ParMapStorage<u8, V> for ByteMapStorage<V>
+where
+    V: Sync,
+{
+    type ParIter<'this>
+        = ParIter<'this, V>
+    where
+        V: 'this;
+    type ParKeys<'this>
+        = ParKeys<'this, V>
+    where
+        V: 'this;
+    type ParValues<'this>
+        = ParValues<'this, V>
+    where
+        V: 'this;
+
+    #[inline]
+    fn par_iter(&self) -> Self::ParIter<'_> {
+        let map: fn(_) -> _ = |(k, v): (u8, &Option<V>)| Some((k, v.as_ref()?));
+        (0..=u8::MAX)
+            .into_par_iter()
+            .zip(self.data.par_iter())
+            .filter_map(map)
+    }
+
+    #[inline]
+    fn par_keys(&self) -> Self::ParKeys<'_> {
+        let map: fn(_) -> _ = |(k, v): (u8, &Option<V>)| v.is_some().then_some(k);
+        (0..=u8::MAX)
+            .into_par_iter()
+            .zip(self.data.par_iter())
+            .filter_map(map)
+    }
+
+    #[inline]
+    fn par_values(&self) -> Self::ParValues<'_> {
+        self.data.par_iter().flatten()
+    }
+}
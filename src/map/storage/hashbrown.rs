@@ -1,12 +1,19 @@
-use core::hash::Hash;
+use core::borrow::Borrow;
+use core::hash::{BuildHasher, Hash, Hasher};
 use core::iter;
+use core::mem::{self, size_of};
 
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+#[cfg(feature = "rayon")]
+use crate::map::ParMapStorage;
 use crate::map::{Entry, MapStorage, OccupiedEntry, VacantEntry};
 
 type S = ::hashbrown::hash_map::DefaultHashBuilder;
-type Occupied<'a, K, V> = ::hashbrown::hash_map::OccupiedEntry<'a, K, V, S>;
-type Vacant<'a, K, V> = ::hashbrown::hash_map::VacantEntry<'a, K, V, S>;
-type HashMapEntry<'a, K, V> = ::hashbrown::hash_map::Entry<'a, K, V, S>;
+type RawEntryMut<'a, K, V> = ::hashbrown::hash_map::RawEntryMut<'a, K, V, S>;
+type RawOccupiedEntryMut<'a, K, V> = ::hashbrown::hash_map::RawOccupiedEntryMut<'a, K, V, S>;
+type RawVacantEntryMut<'a, K, V> = ::hashbrown::hash_map::RawVacantEntryMut<'a, K, V, S>;
 
 /// [`MapStorage`] for dynamic types, using [`hashbrown::HashMap`].
 ///
@@ -46,6 +53,11 @@ where
             inner: self.inner.clone(),
         }
     }
+
+    #[inline]
+    fn clone_from(&mut self, source: &Self) {
+        self.inner.clone_from(&source.inner);
+    }
 }
 
 impl<K, V> PartialEq for HashbrownMapStorage<K, V>
@@ -66,68 +78,145 @@ where
 {
 }
 
+/// Entries are looked up through the raw entry API of `hashbrown`, since it
+/// allows the stored key to be replaced with a different one.
+pub struct Occupied<'a, K, V> {
+    inner: RawOccupiedEntryMut<'a, K, V>,
+}
+
+pub struct Vacant<'a, K, V> {
+    hash: u64,
+    key: K,
+    inner: RawVacantEntryMut<'a, K, V>,
+}
+
 impl<'a, K, V> OccupiedEntry<'a, K, V> for Occupied<'a, K, V>
 where
-    K: Copy,
+    K: Clone + Eq,
 {
     #[inline]
     fn key(&self) -> K {
-        *self.key()
+        self.inner.key().clone()
     }
 
     #[inline]
     fn get(&self) -> &V {
-        self.get()
+        self.inner.get()
     }
 
     #[inline]
     fn get_mut(&mut self) -> &mut V {
-        self.get_mut()
+        self.inner.get_mut()
     }
 
     #[inline]
     fn into_mut(self) -> &'a mut V {
-        self.into_mut()
+        self.inner.into_mut()
     }
 
     #[inline]
     fn insert(&mut self, value: V) -> V {
-        self.insert(value)
+        self.inner.insert(value)
     }
 
     #[inline]
     fn remove(self) -> V {
-        self.remove()
+        self.inner.remove()
+    }
+
+    #[inline]
+    fn replace_key(mut self, key: K) -> K {
+        debug_assert!(
+            *self.inner.key() == key,
+            "replacement key must be equal to the key of the entry"
+        );
+        self.inner.insert_key(key)
     }
 }
 
 impl<'a, K, V> VacantEntry<'a, K, V> for Vacant<'a, K, V>
 where
-    K: Copy + Hash,
+    K: Clone + Hash,
 {
     #[inline]
     fn key(&self) -> K {
-        *self.key()
+        self.key.clone()
+    }
+
+    #[inline]
+    fn into_key(self) -> K {
+        self.key
     }
 
     #[inline]
     fn insert(self, value: V) -> &'a mut V {
-        self.insert(value)
+        self.inner
+            .insert_hashed_nocheck(self.hash, self.key, value)
+            .1
+    }
+}
+
+impl<K, V> HashbrownMapStorage<K, V>
+where
+    K: Eq + Hash,
+{
+    /// Look up a value using a borrowed form of the key.
+    #[inline]
+    pub(crate) fn get_borrowed<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        self.inner.get(key)
     }
 }
 
 impl<K, V> MapStorage<K, V> for HashbrownMapStorage<K, V>
 where
-    K: Copy + Eq + Hash,
+    K: Clone + Eq + Hash,
 {
-    type Iter<'this> = iter::Map<::hashbrown::hash_map::Iter<'this, K, V>, fn((&'this K, &'this V)) -> (K, &'this V)> where K: 'this, V: 'this;
-    type Keys<'this> = iter::Copied<::hashbrown::hash_map::Keys<'this, K, V>> where K: 'this, V: 'this;
-    type Values<'this> = ::hashbrown::hash_map::Values<'this, K, V> where K: 'this, V: 'this;
-    type IterMut<'this> = iter::Map<::hashbrown::hash_map::IterMut<'this, K, V>, fn((&'this K, &'this mut V)) -> (K, &'this mut V)> where K: 'this, V: 'this;
-    type ValuesMut<'this> = ::hashbrown::hash_map::ValuesMut<'this, K, V> where K: 'this, V: 'this;
+    type Iter<'this>
+        = iter::Map<
+        ::hashbrown::hash_map::Iter<'this, K, V>,
+        fn((&'this K, &'this V)) -> (K, &'this V),
+    >
+    where
+        K: 'this,
+        V: 'this;
+    type Keys<'this>
+        = iter::Cloned<::hashbrown::hash_map::Keys<'this, K, V>>
+    where
+        K: 'this,
+        V: 'this;
+    type Values<'this>
+        = ::hashbrown::hash_map::Values<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type IterMut<'this>
+        = iter::Map<
+        ::hashbrown::hash_map::IterMut<'this, K, V>,
+        fn((&'this K, &'this mut V)) -> (K, &'this mut V),
+    >
+    where
+        K: 'this,
+        V: 'this;
+    type ValuesMut<'this>
+        = ::hashbrown::hash_map::ValuesMut<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
     type IntoIter = ::hashbrown::hash_map::IntoIter<K, V>;
-    type Occupied<'this> = Occupied<'this, K, V> where K: 'this, V: 'this;
-    type Vacant<'this> = Vacant<'this, K, V> where K: 'this, V: 'this;
+    type Occupied<'this>
+        = Occupied<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type Vacant<'this>
+        = Vacant<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
 
     #[inline]
     fn empty() -> Self {
@@ -176,7 +265,32 @@ where
     where
         F: FnMut(K, &mut V) -> bool,
     {
-        self.inner.retain(|&k, v| func(k, v));
+        self.inner.retain(|k, v| func(k.clone(), v));
+    }
+
+    #[inline]
+    fn retain_with_removed<F, R>(&mut self, mut keep: F, mut on_remove: R)
+    where
+        F: FnMut(K, &mut V) -> bool,
+        R: FnMut(K, V),
+    {
+        // Set while `keep` is running, so that if it panics the filter which
+        // is run again as the iterator is dropped doesn't remove anything else.
+        let mut visiting = false;
+
+        let removed = self.inner.drain_filter(|k, v| {
+            if mem::replace(&mut visiting, true) {
+                return false;
+            }
+
+            let remove = !keep(k.clone(), v);
+            visiting = false;
+            remove
+        });
+
+        for (key, value) in removed {
+            on_remove(key, value);
+        }
     }
 
     #[inline]
@@ -186,13 +300,13 @@ where
 
     #[inline]
     fn iter(&self) -> Self::Iter<'_> {
-        let map: fn(_) -> _ = |(k, v): (&K, &V)| (*k, v);
+        let map: fn(_) -> _ = |(k, v): (&K, &V)| (k.clone(), v);
         self.inner.iter().map(map)
     }
 
     #[inline]
     fn keys(&self) -> Self::Keys<'_> {
-        self.inner.keys().copied()
+        self.inner.keys().cloned()
     }
 
     #[inline]
@@ -202,7 +316,7 @@ where
 
     #[inline]
     fn iter_mut(&mut self) -> Self::IterMut<'_> {
-        let map: fn(_) -> _ = |(k, v): (&K, &mut V)| (*k, v);
+        let map: fn(_) -> _ = |(k, v): (&K, &mut V)| (k.clone(), v);
         self.inner.iter_mut().map(map)
     }
 
@@ -218,9 +332,78 @@ where
 
     #[inline]
     fn entry(&mut self, key: K) -> Entry<'_, Self, K, V> {
-        match self.inner.entry(key) {
-            HashMapEntry::Occupied(entry) => Entry::Occupied(entry),
-            HashMapEntry::Vacant(entry) => Entry::Vacant(entry),
+        let mut state = self.inner.hasher().build_hasher();
+        key.hash(&mut state);
+        let hash = state.finish();
+
+        match self
+            .inner
+            .raw_entry_mut()
+            .from_key_hashed_nocheck(hash, &key)
+        {
+            RawEntryMut::Occupied(inner) => Entry::Occupied(Occupied { inner }),
+            RawEntryMut::Vacant(inner) => Entry::Vacant(Vacant { hash, key, inner }),
         }
     }
+
+    #[inline]
+    fn reserve(&mut self, additional: usize) {
+        self.inner.reserve(additional);
+    }
+
+    #[inline]
+    fn shrink_to_fit(&mut self) {
+        self.inner.shrink_to_fit();
+    }
+
+    #[inline]
+    fn heap_size(&self) -> usize {
+        // The bucket count is only reachable through `&mut self`, so this
+        // counts usable capacity as documented on `Map::memory_footprint`.
+        self.inner.capacity() * size_of::<(K, V)>()
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<K, V> ParMapStorage<K, V> for HashbrownMapStorage<K, V>
+where
+    K: Clone + Eq + Hash + Send + Sync,
+    V: Sync,
+{
+    type ParIter<'this>
+        = rayon::iter::Map<
+        ::hashbrown::hash_map::rayon::ParIter<'this, K, V>,
+        fn((&'this K, &'this V)) -> (K, &'this V),
+    >
+    where
+        Self: 'this;
+    type ParKeys<'this>
+        = rayon::iter::Cloned<::hashbrown::hash_map::rayon::ParKeys<'this, K, V>>
+    where
+        Self: 'this;
+    type ParValues<'this>
+        = ::hashbrown::hash_map::rayon::ParValues<'this, K, V>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn par_iter(&self) -> Self::ParIter<'_> {
+        let map: fn(_) -> _ = |(k, v): (&K, &V)| (k.clone(), v);
+        self.inner.par_iter().map(map)
+    }
+
+    #[inline]
+    fn par_keys(&self) -> Self::ParKeys<'_> {
+        self.inner.par_keys().cloned()
+    }
+
+    #[inline]
+    fn par_values(&self) -> Self::ParValues<'_> {
+        self.inner.par_values()
+    }
 }
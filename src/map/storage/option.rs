@@ -1,42 +1,90 @@
-use core::iter;
-use core::mem;
 use core::option;
 
+use crate::iter::{Halves, MapHalves};
 use crate::key::Key;
-use crate::map::{Entry, MapStorage, OccupiedEntry, VacantEntry};
+use crate::map::{ConstMapStorage, Entry, MapStorage, OccupiedEntry, VacantEntry};
 use crate::option_bucket::{NoneBucket, OptionBucket, SomeBucket};
 
-type Iter<'a, K, V> = iter::Chain<
-    iter::Map<
-        <<K as Key>::MapStorage<V> as MapStorage<K, V>>::Iter<'a>,
-        fn((K, &'a V)) -> (Option<K>, &'a V),
-    >,
-    iter::Map<option::Iter<'a, V>, fn(&'a V) -> (Option<K>, &'a V)>,
+/// Storage for the `Some` keys.
+type SomeStorage<K, V> = <K as Key>::MapStorage<V>;
+
+type Iter<'a, K, V> =
+    Halves<<SomeStorage<K, V> as MapStorage<K, V>>::Iter<'a>, option::IntoIter<&'a V>, EntryHalves>;
+type Keys<'a, K, V> =
+    Halves<<SomeStorage<K, V> as MapStorage<K, V>>::Keys<'a>, option::IntoIter<()>, KeyHalves>;
+type Values<'a, K, V> = Halves<
+    <SomeStorage<K, V> as MapStorage<K, V>>::Values<'a>,
+    option::IntoIter<&'a V>,
+    ValueHalves,
 >;
-type Keys<'a, K, V> = iter::Chain<
-    iter::Map<<<K as Key>::MapStorage<V> as MapStorage<K, V>>::Keys<'a>, fn(K) -> Option<K>>,
-    option::IntoIter<Option<K>>,
+type IterMut<'a, K, V> = Halves<
+    <SomeStorage<K, V> as MapStorage<K, V>>::IterMut<'a>,
+    option::IntoIter<&'a mut V>,
+    EntryHalves,
 >;
-type Values<'a, K, V> =
-    iter::Chain<<<K as Key>::MapStorage<V> as MapStorage<K, V>>::Values<'a>, option::Iter<'a, V>>;
-type IterMut<'a, K, V> = iter::Chain<
-    iter::Map<
-        <<K as Key>::MapStorage<V> as MapStorage<K, V>>::IterMut<'a>,
-        fn((K, &'a mut V)) -> (Option<K>, &'a mut V),
-    >,
-    iter::Map<option::IterMut<'a, V>, fn(&'a mut V) -> (Option<K>, &'a mut V)>,
->;
-type ValuesMut<'a, K, V> = iter::Chain<
-    <<K as Key>::MapStorage<V> as MapStorage<K, V>>::ValuesMut<'a>,
-    option::IterMut<'a, V>,
->;
-type IntoIter<K, V> = iter::Chain<
-    iter::Map<
-        <<K as Key>::MapStorage<V> as MapStorage<K, V>>::IntoIter,
-        fn((K, V)) -> (Option<K>, V),
-    >,
-    iter::Map<option::IntoIter<V>, fn(V) -> (Option<K>, V)>,
+type ValuesMut<'a, K, V> = Halves<
+    <SomeStorage<K, V> as MapStorage<K, V>>::ValuesMut<'a>,
+    option::IntoIter<&'a mut V>,
+    ValueHalves,
 >;
+type IntoIter<K, V> =
+    Halves<<SomeStorage<K, V> as MapStorage<K, V>>::IntoIter, option::IntoIter<V>, EntryHalves>;
+
+/// Maps entries of the `Some` storage and the value of the `None` entry into
+/// entries of the option storage.
+#[derive(Clone, Copy)]
+pub enum EntryHalves {}
+
+impl<K, V> MapHalves<(K, V), V> for EntryHalves {
+    type Item = (Option<K>, V);
+
+    #[inline]
+    fn first((key, value): (K, V)) -> Self::Item {
+        (Some(key), value)
+    }
+
+    #[inline]
+    fn second(value: V) -> Self::Item {
+        (None, value)
+    }
+}
+
+/// Maps keys of the `Some` storage and the presence of the `None` entry into
+/// keys of the option storage.
+#[derive(Clone, Copy)]
+pub enum KeyHalves {}
+
+impl<K> MapHalves<K, ()> for KeyHalves {
+    type Item = Option<K>;
+
+    #[inline]
+    fn first(key: K) -> Self::Item {
+        Some(key)
+    }
+
+    #[inline]
+    fn second((): ()) -> Self::Item {
+        None
+    }
+}
+
+/// Passes values of either half through unchanged.
+#[derive(Clone, Copy)]
+pub enum ValueHalves {}
+
+impl<V> MapHalves<V, V> for ValueHalves {
+    type Item = V;
+
+    #[inline]
+    fn first(value: V) -> Self::Item {
+        value
+    }
+
+    #[inline]
+    fn second(value: V) -> Self::Item {
+        value
+    }
+}
 
 /// [`MapStorage`] for [`Option`] types.
 ///
@@ -91,6 +139,12 @@ where
             none: self.none.clone(),
         }
     }
+
+    #[inline]
+    fn clone_from(&mut self, source: &Self) {
+        self.some.clone_from(&source.some);
+        self.none.clone_from(&source.none);
+    }
 }
 
 impl<K, V> Copy for OptionMapStorage<K, V>
@@ -149,6 +203,14 @@ where
         }
     }
 
+    #[inline]
+    fn into_key(self) -> Option<K> {
+        match self {
+            Vacant::None(_) => None,
+            Vacant::Some(entry) => Some(entry.into_key()),
+        }
+    }
+
     #[inline]
     fn insert(self, value: V) -> &'a mut V {
         match self {
@@ -209,20 +271,93 @@ where
             Occupied::Some(entry) => entry.remove(),
         }
     }
+
+    #[inline]
+    fn replace_key(self, key: Option<K>) -> Option<K> {
+        match (self, key) {
+            (Occupied::Some(entry), Some(key)) => Some(entry.replace_key(key)),
+            (_, key) => key,
+        }
+    }
+}
+
+impl<K, V> OptionMapStorage<K, V>
+where
+    K: Key,
+{
+    /// See [`Map::occupancy`][crate::Map::occupancy].
+    #[inline]
+    pub(crate) fn occupancy(&self) -> (bool, usize) {
+        (self.none.is_some(), self.some.len())
+    }
+
+    /// Check in debug builds that the reported length of the storage agrees
+    /// with the number of entries it actually holds.
+    ///
+    /// This is called after every modification of the storage. Entries only
+    /// borrow one half of the storage, so changes made through them are
+    /// checked when the next entry is created.
+    #[inline]
+    fn debug_assert_len(&self) {
+        debug_assert_eq!(
+            self.len(),
+            self.iter().count(),
+            "length of option storage disagrees with its entries"
+        );
+    }
+}
+
+impl<K, V> ConstMapStorage<Option<K>, V> for OptionMapStorage<K, V>
+where
+    K: Key,
+    K::MapStorage<V>: ConstMapStorage<K, V>,
+{
+    const EMPTY: Self = Self {
+        some: <K::MapStorage<V> as ConstMapStorage<K, V>>::EMPTY,
+        none: None,
+    };
 }
 
 impl<K, V> MapStorage<Option<K>, V> for OptionMapStorage<K, V>
 where
     K: Key,
 {
-    type Iter<'this> = Iter<'this, K, V> where K: 'this, V: 'this;
-    type Keys<'this> = Keys<'this, K, V> where K: 'this, V: 'this;
-    type Values<'this> = Values<'this, K, V> where K: 'this, V: 'this;
-    type IterMut<'this> = IterMut<'this, K, V> where K: 'this, V: 'this;
-    type ValuesMut<'this> = ValuesMut<'this, K, V> where K: 'this, V: 'this;
+    type Iter<'this>
+        = Iter<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type Keys<'this>
+        = Keys<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type Values<'this>
+        = Values<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type IterMut<'this>
+        = IterMut<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type ValuesMut<'this>
+        = ValuesMut<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
     type IntoIter = IntoIter<K, V>;
-    type Occupied<'this> = Occupied<'this, K, V> where K: 'this, V: 'this;
-    type Vacant<'this> = Vacant<'this, K, V> where K: 'this, V: 'this;
+    type Occupied<'this>
+        = Occupied<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
+    type Vacant<'this>
+        = Vacant<'this, K, V>
+    where
+        K: 'this,
+        V: 'this;
 
     #[inline]
     fn empty() -> Self {
@@ -244,10 +379,13 @@ where
 
     #[inline]
     fn insert(&mut self, key: Option<K>, value: V) -> Option<V> {
-        match key {
+        let old = match key {
             Some(key) => self.some.insert(key, value),
-            None => mem::replace(&mut self.none, Some(value)),
-        }
+            None => self.none.replace(value),
+        };
+
+        self.debug_assert_len();
+        old
     }
 
     #[inline]
@@ -276,10 +414,13 @@ where
 
     #[inline]
     fn remove(&mut self, key: Option<K>) -> Option<V> {
-        match key {
+        let old = match key {
             Some(key) => self.some.remove(key),
-            None => mem::replace(&mut self.none, None),
-        }
+            None => self.none.take(),
+        };
+
+        self.debug_assert_len();
+        old
     }
 
     #[inline]
@@ -293,62 +434,69 @@ where
                 self.none = None;
             }
         }
+
+        self.debug_assert_len();
+    }
+
+    #[inline]
+    fn retain_with_removed<F, R>(&mut self, mut keep: F, mut on_remove: R)
+    where
+        F: FnMut(Option<K>, &mut V) -> bool,
+        R: FnMut(Option<K>, V),
+    {
+        self.some
+            .retain_with_removed(|k, v| keep(Some(k), v), |k, v| on_remove(Some(k), v));
+
+        if let OptionBucket::Some(mut bucket) = OptionBucket::new(&mut self.none) {
+            if !keep(None, bucket.as_mut()) {
+                on_remove(None, bucket.take());
+            }
+        }
+
+        self.debug_assert_len();
     }
 
     #[inline]
     fn clear(&mut self) {
         self.some.clear();
         self.none = None;
+        self.debug_assert_len();
     }
 
     #[inline]
     fn iter(&self) -> Self::Iter<'_> {
-        let map: fn(_) -> _ = |(k, b)| (Some(k), b);
-        let a = self.some.iter().map(map);
-        let map: fn(_) -> _ = |v| (None, v);
-        let b = self.none.iter().map(map);
-        a.chain(b)
+        Halves::new(self.some.iter(), self.none.as_ref().into_iter())
     }
 
     #[inline]
     fn keys(&self) -> Self::Keys<'_> {
-        let map: fn(_) -> _ = |k| Some(k);
-        self.some
-            .keys()
-            .map(map)
-            .chain(self.none.is_some().then_some(None::<K>))
+        Halves::new(self.some.keys(), self.none.as_ref().map(|_| ()).into_iter())
     }
 
     #[inline]
     fn values(&self) -> Self::Values<'_> {
-        self.some.values().chain(self.none.iter())
+        Halves::new(self.some.values(), self.none.as_ref().into_iter())
     }
 
     #[inline]
     fn iter_mut(&mut self) -> Self::IterMut<'_> {
-        let map: fn(_) -> _ = |(k, b)| (Some(k), b);
-        let a = self.some.iter_mut().map(map);
-        let map: fn(_) -> _ = |v| (None, v);
-        let b = self.none.iter_mut().map(map);
-        a.chain(b)
+        Halves::new(self.some.iter_mut(), self.none.as_mut().into_iter())
     }
 
     #[inline]
     fn values_mut(&mut self) -> Self::ValuesMut<'_> {
-        self.some.values_mut().chain(self.none.iter_mut())
+        Halves::new(self.some.values_mut(), self.none.as_mut().into_iter())
     }
 
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
-        let map: fn(_) -> _ = |(k, b)| (Some(k), b);
-        let a = self.some.into_iter().map(map);
-        let map: fn(_) -> _ = |v| (None, v);
-        let b = self.none.into_iter().map(map);
-        a.chain(b)
+        Halves::new(self.some.into_iter(), self.none.into_iter())
     }
 
     #[inline]
     fn entry(&mut self, key: Option<K>) -> Entry<'_, Self, Option<K>, V> {
+        self.debug_assert_len();
+
         match key {
             Some(key) => match self.some.entry(key) {
                 Entry::Occupied(entry) => Entry::Occupied(Occupied::Some(entry)),
@@ -360,4 +508,24 @@ where
             },
         }
     }
+
+    #[inline]
+    fn reserve(&mut self, additional: usize) {
+        self.some.reserve(additional);
+    }
+
+    #[inline]
+    fn shrink_to_fit(&mut self) {
+        self.some.shrink_to_fit();
+    }
+
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.some.heap_size()
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.some.capacity() + usize::from(self.none.is_some())
+    }
 }
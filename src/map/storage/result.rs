@@ -0,0 +1,515 @@
+use crate::iter::{Halves, MapHalves};
+use crate::key::Key;
+use crate::map::{ConstMapStorage, Entry, MapStorage, OccupiedEntry, VacantEntry};
+
+/// Storage for the `Ok` keys.
+type OkStorage<T, V> = <T as Key>::MapStorage<V>;
+/// Storage for the `Err` keys.
+type ErrStorage<E, V> = <E as Key>::MapStorage<V>;
+
+type Iter<'a, T, E, V> = Halves<
+    <OkStorage<T, V> as MapStorage<T, V>>::Iter<'a>,
+    <ErrStorage<E, V> as MapStorage<E, V>>::Iter<'a>,
+    EntryHalves,
+>;
+type Keys<'a, T, E, V> = Halves<
+    <OkStorage<T, V> as MapStorage<T, V>>::Keys<'a>,
+    <ErrStorage<E, V> as MapStorage<E, V>>::Keys<'a>,
+    KeyHalves,
+>;
+type Values<'a, T, E, V> = Halves<
+    <OkStorage<T, V> as MapStorage<T, V>>::Values<'a>,
+    <ErrStorage<E, V> as MapStorage<E, V>>::Values<'a>,
+    ValueHalves,
+>;
+type IterMut<'a, T, E, V> = Halves<
+    <OkStorage<T, V> as MapStorage<T, V>>::IterMut<'a>,
+    <ErrStorage<E, V> as MapStorage<E, V>>::IterMut<'a>,
+    EntryHalves,
+>;
+type ValuesMut<'a, T, E, V> = Halves<
+    <OkStorage<T, V> as MapStorage<T, V>>::ValuesMut<'a>,
+    <ErrStorage<E, V> as MapStorage<E, V>>::ValuesMut<'a>,
+    ValueHalves,
+>;
+type IntoIter<T, E, V> = Halves<
+    <OkStorage<T, V> as MapStorage<T, V>>::IntoIter,
+    <ErrStorage<E, V> as MapStorage<E, V>>::IntoIter,
+    EntryHalves,
+>;
+
+/// Maps entries of the `Ok` and `Err` storage into entries of the result
+/// storage.
+#[derive(Clone, Copy)]
+pub enum EntryHalves {}
+
+impl<T, E, V> MapHalves<(T, V), (E, V)> for EntryHalves {
+    type Item = (Result<T, E>, V);
+
+    #[inline]
+    fn first((key, value): (T, V)) -> Self::Item {
+        (Ok(key), value)
+    }
+
+    #[inline]
+    fn second((key, value): (E, V)) -> Self::Item {
+        (Err(key), value)
+    }
+}
+
+/// Maps keys of the `Ok` and `Err` storage into keys of the result storage.
+#[derive(Clone, Copy)]
+pub enum KeyHalves {}
+
+impl<T, E> MapHalves<T, E> for KeyHalves {
+    type Item = Result<T, E>;
+
+    #[inline]
+    fn first(key: T) -> Self::Item {
+        Ok(key)
+    }
+
+    #[inline]
+    fn second(key: E) -> Self::Item {
+        Err(key)
+    }
+}
+
+/// Passes values of either half through unchanged.
+#[derive(Clone, Copy)]
+pub enum ValueHalves {}
+
+impl<V> MapHalves<V, V> for ValueHalves {
+    type Item = V;
+
+    #[inline]
+    fn first(value: V) -> Self::Item {
+        value
+    }
+
+    #[inline]
+    fn second(value: V) -> Self::Item {
+        value
+    }
+}
+
+/// [`MapStorage`] for [`Result`] types.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Success {
+///     Created,
+///     Updated,
+/// }
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Failure {
+///     NotFound,
+///     Conflict,
+/// }
+///
+/// let mut a = Map::new();
+/// a.insert(Err(Failure::Conflict), 1);
+/// a.insert(Ok(Success::Updated), 2);
+///
+/// assert_eq!(a.get(Ok(Success::Updated)), Some(&2));
+/// assert_eq!(a.get(Ok(Success::Created)), None);
+/// assert_eq!(a.get(Err(Failure::Conflict)), Some(&1));
+/// assert_eq!(a.get(Err(Failure::NotFound)), None);
+///
+/// assert!(a.iter().eq([(Ok(Success::Updated), &2), (Err(Failure::Conflict), &1)]));
+/// assert!(a.values().copied().eq([2, 1]));
+/// assert!(a.keys().eq([Ok(Success::Updated), Err(Failure::Conflict)]));
+/// ```
+pub struct ResultMapStorage<T, E, V>
+where
+    T: Key,
+    E: Key,
+{
+    ok: T::MapStorage<V>,
+    err: E::MapStorage<V>,
+}
+
+impl<T, E, V> Clone for ResultMapStorage<T, E, V>
+where
+    T: Key,
+    E: Key,
+    T::MapStorage<V>: Clone,
+    E::MapStorage<V>: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            ok: self.ok.clone(),
+            err: self.err.clone(),
+        }
+    }
+
+    #[inline]
+    fn clone_from(&mut self, source: &Self) {
+        self.ok.clone_from(&source.ok);
+        self.err.clone_from(&source.err);
+    }
+}
+
+impl<T, E, V> Copy for ResultMapStorage<T, E, V>
+where
+    T: Key,
+    E: Key,
+    T::MapStorage<V>: Copy,
+    E::MapStorage<V>: Copy,
+{
+}
+
+impl<T, E, V> PartialEq for ResultMapStorage<T, E, V>
+where
+    T: Key,
+    E: Key,
+    T::MapStorage<V>: PartialEq,
+    E::MapStorage<V>: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.ok == other.ok && self.err == other.err
+    }
+}
+
+impl<T, E, V> Eq for ResultMapStorage<T, E, V>
+where
+    T: Key,
+    E: Key,
+    T::MapStorage<V>: Eq,
+    E::MapStorage<V>: Eq,
+{
+}
+
+pub enum Vacant<'a, T: 'a, E: 'a, V: 'a>
+where
+    T: Key,
+    E: Key,
+{
+    Ok(<T::MapStorage<V> as MapStorage<T, V>>::Vacant<'a>),
+    Err(<E::MapStorage<V> as MapStorage<E, V>>::Vacant<'a>),
+}
+
+pub enum Occupied<'a, T: 'a, E: 'a, V: 'a>
+where
+    T: Key,
+    E: Key,
+{
+    Ok(<T::MapStorage<V> as MapStorage<T, V>>::Occupied<'a>),
+    Err(<E::MapStorage<V> as MapStorage<E, V>>::Occupied<'a>),
+}
+
+impl<'a, T, E, V> VacantEntry<'a, Result<T, E>, V> for Vacant<'a, T, E, V>
+where
+    T: Key,
+    E: Key,
+    V: 'a,
+{
+    #[inline]
+    fn key(&self) -> Result<T, E> {
+        match self {
+            Vacant::Ok(entry) => Ok(entry.key()),
+            Vacant::Err(entry) => Err(entry.key()),
+        }
+    }
+
+    #[inline]
+    fn into_key(self) -> Result<T, E> {
+        match self {
+            Vacant::Ok(entry) => Ok(entry.into_key()),
+            Vacant::Err(entry) => Err(entry.into_key()),
+        }
+    }
+
+    #[inline]
+    fn insert(self, value: V) -> &'a mut V {
+        match self {
+            Vacant::Ok(entry) => entry.insert(value),
+            Vacant::Err(entry) => entry.insert(value),
+        }
+    }
+}
+
+impl<'a, T, E, V> OccupiedEntry<'a, Result<T, E>, V> for Occupied<'a, T, E, V>
+where
+    T: Key,
+    E: Key,
+    V: 'a,
+{
+    #[inline]
+    fn key(&self) -> Result<T, E> {
+        match self {
+            Occupied::Ok(entry) => Ok(entry.key()),
+            Occupied::Err(entry) => Err(entry.key()),
+        }
+    }
+
+    #[inline]
+    fn get(&self) -> &V {
+        match self {
+            Occupied::Ok(entry) => entry.get(),
+            Occupied::Err(entry) => entry.get(),
+        }
+    }
+
+    #[inline]
+    fn get_mut(&mut self) -> &mut V {
+        match self {
+            Occupied::Ok(entry) => entry.get_mut(),
+            Occupied::Err(entry) => entry.get_mut(),
+        }
+    }
+
+    #[inline]
+    fn into_mut(self) -> &'a mut V {
+        match self {
+            Occupied::Ok(entry) => entry.into_mut(),
+            Occupied::Err(entry) => entry.into_mut(),
+        }
+    }
+
+    #[inline]
+    fn insert(&mut self, value: V) -> V {
+        match self {
+            Occupied::Ok(entry) => entry.insert(value),
+            Occupied::Err(entry) => entry.insert(value),
+        }
+    }
+
+    #[inline]
+    fn remove(self) -> V {
+        match self {
+            Occupied::Ok(entry) => entry.remove(),
+            Occupied::Err(entry) => entry.remove(),
+        }
+    }
+
+    #[inline]
+    fn replace_key(self, key: Result<T, E>) -> Result<T, E> {
+        match (self, key) {
+            (Occupied::Ok(entry), Ok(key)) => Ok(entry.replace_key(key)),
+            (Occupied::Err(entry), Err(key)) => Err(entry.replace_key(key)),
+            (_, key) => key,
+        }
+    }
+}
+
+impl<T, E, V> ConstMapStorage<Result<T, E>, V> for ResultMapStorage<T, E, V>
+where
+    T: Key,
+    E: Key,
+    T::MapStorage<V>: ConstMapStorage<T, V>,
+    E::MapStorage<V>: ConstMapStorage<E, V>,
+{
+    const EMPTY: Self = Self {
+        ok: <T::MapStorage<V> as ConstMapStorage<T, V>>::EMPTY,
+        err: <E::MapStorage<V> as ConstMapStorage<E, V>>::EMPTY,
+    };
+}
+
+impl<T, E, V> MapStorage<Result<T, E>, V> for ResultMapStorage<T, E, V>
+where
+    T: Key,
+    E: Key,
+{
+    type Iter<'this>
+        = Iter<'this, T, E, V>
+    where
+        T: 'this,
+        E: 'this,
+        V: 'this;
+    type Keys<'this>
+        = Keys<'this, T, E, V>
+    where
+        T: 'this,
+        E: 'this,
+        V: 'this;
+    type Values<'this>
+        = Values<'this, T, E, V>
+    where
+        T: 'this,
+        E: 'this,
+        V: 'this;
+    type IterMut<'this>
+        = IterMut<'this, T, E, V>
+    where
+        T: 'this,
+        E: 'this,
+        V: 'this;
+    type ValuesMut<'this>
+        = ValuesMut<'this, T, E, V>
+    where
+        T: 'this,
+        E: 'this,
+        V: 'this;
+    type IntoIter = IntoIter<T, E, V>;
+    type Occupied<'this>
+        = Occupied<'this, T, E, V>
+    where
+        T: 'this,
+        E: 'this,
+        V: 'this;
+    type Vacant<'this>
+        = Vacant<'this, T, E, V>
+    where
+        T: 'this,
+        E: 'this,
+        V: 'this;
+
+    #[inline]
+    fn empty() -> Self {
+        Self {
+            ok: T::MapStorage::empty(),
+            err: E::MapStorage::empty(),
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.ok.len() + self.err.len()
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.ok.is_empty() && self.err.is_empty()
+    }
+
+    #[inline]
+    fn insert(&mut self, key: Result<T, E>, value: V) -> Option<V> {
+        match key {
+            Ok(key) => self.ok.insert(key, value),
+            Err(key) => self.err.insert(key, value),
+        }
+    }
+
+    #[inline]
+    fn contains_key(&self, key: Result<T, E>) -> bool {
+        match key {
+            Ok(key) => self.ok.contains_key(key),
+            Err(key) => self.err.contains_key(key),
+        }
+    }
+
+    #[inline]
+    fn get(&self, key: Result<T, E>) -> Option<&V> {
+        match key {
+            Ok(key) => self.ok.get(key),
+            Err(key) => self.err.get(key),
+        }
+    }
+
+    #[inline]
+    fn get_mut(&mut self, key: Result<T, E>) -> Option<&mut V> {
+        match key {
+            Ok(key) => self.ok.get_mut(key),
+            Err(key) => self.err.get_mut(key),
+        }
+    }
+
+    #[inline]
+    fn remove(&mut self, key: Result<T, E>) -> Option<V> {
+        match key {
+            Ok(key) => self.ok.remove(key),
+            Err(key) => self.err.remove(key),
+        }
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, mut func: F)
+    where
+        F: FnMut(Result<T, E>, &mut V) -> bool,
+    {
+        self.ok.retain(|k, v| func(Ok(k), v));
+        self.err.retain(|k, v| func(Err(k), v));
+    }
+
+    #[inline]
+    fn retain_with_removed<F, R>(&mut self, mut keep: F, mut on_remove: R)
+    where
+        F: FnMut(Result<T, E>, &mut V) -> bool,
+        R: FnMut(Result<T, E>, V),
+    {
+        self.ok
+            .retain_with_removed(|k, v| keep(Ok(k), v), |k, v| on_remove(Ok(k), v));
+        self.err
+            .retain_with_removed(|k, v| keep(Err(k), v), |k, v| on_remove(Err(k), v));
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.ok.clear();
+        self.err.clear();
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        Halves::new(self.ok.iter(), self.err.iter())
+    }
+
+    #[inline]
+    fn keys(&self) -> Self::Keys<'_> {
+        Halves::new(self.ok.keys(), self.err.keys())
+    }
+
+    #[inline]
+    fn values(&self) -> Self::Values<'_> {
+        Halves::new(self.ok.values(), self.err.values())
+    }
+
+    #[inline]
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        Halves::new(self.ok.iter_mut(), self.err.iter_mut())
+    }
+
+    #[inline]
+    fn values_mut(&mut self) -> Self::ValuesMut<'_> {
+        Halves::new(self.ok.values_mut(), self.err.values_mut())
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        Halves::new(self.ok.into_iter(), self.err.into_iter())
+    }
+
+    #[inline]
+    fn entry(&mut self, key: Result<T, E>) -> Entry<'_, Self, Result<T, E>, V> {
+        match key {
+            Ok(key) => match self.ok.entry(key) {
+                Entry::Occupied(entry) => Entry::Occupied(Occupied::Ok(entry)),
+                Entry::Vacant(entry) => Entry::Vacant(Vacant::Ok(entry)),
+            },
+            Err(key) => match self.err.entry(key) {
+                Entry::Occupied(entry) => Entry::Occupied(Occupied::Err(entry)),
+                Entry::Vacant(entry) => Entry::Vacant(Vacant::Err(entry)),
+            },
+        }
+    }
+
+    #[inline]
+    fn reserve(&mut self, additional: usize) {
+        self.ok.reserve(additional);
+        self.err.reserve(additional);
+    }
+
+    #[inline]
+    fn shrink_to_fit(&mut self) {
+        self.ok.shrink_to_fit();
+        self.err.shrink_to_fit();
+    }
+
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.ok.heap_size() + self.err.heap_size()
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.ok.capacity() + self.err.capacity()
+    }
+}
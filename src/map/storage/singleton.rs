@@ -1,15 +1,30 @@
-use core::mem;
-
-use crate::map::{Entry, MapStorage};
+use crate::map::{ConstMapStorage, Entry, MapStorage};
 use crate::option_bucket::{NoneBucket, OptionBucket, SomeBucket};
 
 /// [`MapStorage`] type that can only inhabit a single value (like `()`).
 #[repr(transparent)]
-#[derive(Clone, Copy)]
+#[derive(Copy)]
 pub struct SingletonMapStorage<V> {
     inner: Option<V>,
 }
 
+impl<V> Clone for SingletonMapStorage<V>
+where
+    V: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+
+    #[inline]
+    fn clone_from(&mut self, source: &Self) {
+        self.inner.clone_from(&source.inner);
+    }
+}
+
 impl<V> PartialEq for SingletonMapStorage<V>
 where
     V: PartialEq,
@@ -22,18 +37,46 @@ where
 
 impl<V> Eq for SingletonMapStorage<V> where V: Eq {}
 
+impl<K, V> ConstMapStorage<K, V> for SingletonMapStorage<V>
+where
+    K: Default,
+{
+    const EMPTY: Self = Self { inner: None };
+}
+
 impl<K, V> MapStorage<K, V> for SingletonMapStorage<V>
 where
     K: Default,
 {
-    type Iter<'this> = ::core::option::IntoIter<(K, &'this V)> where V: 'this;
-    type Keys<'this> = ::core::option::IntoIter<K> where V: 'this;
-    type Values<'this> = ::core::option::Iter<'this, V> where V: 'this;
-    type IterMut<'this> = ::core::option::IntoIter<(K, &'this mut V)> where V: 'this;
-    type ValuesMut<'this> = ::core::option::IterMut<'this, V> where V: 'this;
+    type Iter<'this>
+        = ::core::option::IntoIter<(K, &'this V)>
+    where
+        V: 'this;
+    type Keys<'this>
+        = ::core::option::IntoIter<K>
+    where
+        V: 'this;
+    type Values<'this>
+        = ::core::option::Iter<'this, V>
+    where
+        V: 'this;
+    type IterMut<'this>
+        = ::core::option::IntoIter<(K, &'this mut V)>
+    where
+        V: 'this;
+    type ValuesMut<'this>
+        = ::core::option::IterMut<'this, V>
+    where
+        V: 'this;
     type IntoIter = ::core::option::IntoIter<(K, V)>;
-    type Occupied<'this> = SomeBucket<'this, V> where V: 'this;
-    type Vacant<'this> = NoneBucket<'this, V> where V: 'this;
+    type Occupied<'this>
+        = SomeBucket<'this, V>
+    where
+        V: 'this;
+    type Vacant<'this>
+        = NoneBucket<'this, V>
+    where
+        V: 'this;
 
     #[inline]
     fn empty() -> Self {
@@ -54,7 +97,7 @@ where
 
     #[inline]
     fn insert(&mut self, _: K, value: V) -> Option<V> {
-        mem::replace(&mut self.inner, Some(value))
+        self.inner.replace(value)
     }
 
     #[inline]
@@ -74,7 +117,7 @@ where
 
     #[inline]
     fn remove(&mut self, _: K) -> Option<V> {
-        mem::replace(&mut self.inner, None)
+        self.inner.take()
     }
 
     #[inline]
@@ -89,6 +132,19 @@ where
         }
     }
 
+    #[inline]
+    fn retain_with_removed<F, R>(&mut self, mut keep: F, mut on_remove: R)
+    where
+        F: FnMut(K, &mut V) -> bool,
+        R: FnMut(K, V),
+    {
+        if let OptionBucket::Some(mut bucket) = OptionBucket::new(&mut self.inner) {
+            if !keep(K::default(), bucket.as_mut()) {
+                on_remove(K::default(), bucket.take());
+            }
+        }
+    }
+
     #[inline]
     fn clear(&mut self) {
         self.inner = None;
@@ -101,7 +157,7 @@ where
 
     #[inline]
     fn keys(&self) -> Self::Keys<'_> {
-        Some(K::default()).into_iter()
+        self.inner.as_ref().map(|_| K::default()).into_iter()
     }
 
     #[inline]
@@ -0,0 +1,579 @@
+use core::marker::PhantomData;
+
+use crate::iter::{Nested, SplitNested};
+use crate::key::Key;
+use crate::map::{ConstMapStorage, Entry, MapStorage, OccupiedEntry, VacantEntry};
+
+/// Storage for the second element of a tuple key.
+type Inner<B, V> = <B as Key>::MapStorage<V>;
+/// Storage for the first element of a tuple key, holding the storage for the
+/// second element.
+type Outer<A, B, V> = <A as Key>::MapStorage<Inner<B, V>>;
+
+type Iter<'a, A, B, V> =
+    Nested<<Outer<A, B, V> as MapStorage<A, Inner<B, V>>>::Iter<'a>, SplitIter<B, V>>;
+type Keys<'a, A, B, V> =
+    Nested<<Outer<A, B, V> as MapStorage<A, Inner<B, V>>>::Iter<'a>, SplitKeys<B, V>>;
+type Values<'a, A, B, V> =
+    Nested<<Outer<A, B, V> as MapStorage<A, Inner<B, V>>>::Values<'a>, SplitValues<B, V>>;
+type IterMut<'a, A, B, V> =
+    Nested<<Outer<A, B, V> as MapStorage<A, Inner<B, V>>>::IterMut<'a>, SplitIterMut<B, V>>;
+type ValuesMut<'a, A, B, V> =
+    Nested<<Outer<A, B, V> as MapStorage<A, Inner<B, V>>>::ValuesMut<'a>, SplitValuesMut<B, V>>;
+type IntoIter<A, B, V> =
+    Nested<<Outer<A, B, V> as MapStorage<A, Inner<B, V>>>::IntoIter, SplitIntoIter<B, V>>;
+
+/// Splits entries of the outer storage for [`TupleMapStorage::iter`].
+pub struct SplitIter<B, V>(PhantomData<fn() -> (B, V)>);
+
+impl<'a, A, B, V> SplitNested<(A, &'a Inner<B, V>)> for SplitIter<B, V>
+where
+    A: Clone,
+    B: Key,
+    V: 'a,
+{
+    type Key = A;
+    type Iter = <Inner<B, V> as MapStorage<B, V>>::Iter<'a>;
+    type Item = ((A, B), &'a V);
+
+    #[inline]
+    fn split((a, inner): (A, &'a Inner<B, V>)) -> (A, Self::Iter) {
+        (a, inner.iter())
+    }
+
+    #[inline]
+    fn join(a: A, (b, value): (B, &'a V)) -> Self::Item {
+        ((a, b), value)
+    }
+}
+
+/// Splits entries of the outer storage for [`TupleMapStorage::keys`].
+pub struct SplitKeys<B, V>(PhantomData<fn() -> (B, V)>);
+
+impl<'a, A, B, V> SplitNested<(A, &'a Inner<B, V>)> for SplitKeys<B, V>
+where
+    A: Clone,
+    B: Key,
+    V: 'a,
+{
+    type Key = A;
+    type Iter = <Inner<B, V> as MapStorage<B, V>>::Keys<'a>;
+    type Item = (A, B);
+
+    #[inline]
+    fn split((a, inner): (A, &'a Inner<B, V>)) -> (A, Self::Iter) {
+        (a, inner.keys())
+    }
+
+    #[inline]
+    fn join(a: A, b: B) -> Self::Item {
+        (a, b)
+    }
+}
+
+/// Splits values of the outer storage for [`TupleMapStorage::values`].
+pub struct SplitValues<B, V>(PhantomData<fn() -> (B, V)>);
+
+impl<'a, B, V> SplitNested<&'a Inner<B, V>> for SplitValues<B, V>
+where
+    B: Key,
+    V: 'a,
+{
+    type Key = ();
+    type Iter = <Inner<B, V> as MapStorage<B, V>>::Values<'a>;
+    type Item = &'a V;
+
+    #[inline]
+    fn split(inner: &'a Inner<B, V>) -> ((), Self::Iter) {
+        ((), inner.values())
+    }
+
+    #[inline]
+    fn join((): (), value: &'a V) -> Self::Item {
+        value
+    }
+}
+
+/// Splits entries of the outer storage for [`TupleMapStorage::iter_mut`].
+pub struct SplitIterMut<B, V>(PhantomData<fn() -> (B, V)>);
+
+impl<'a, A, B, V> SplitNested<(A, &'a mut Inner<B, V>)> for SplitIterMut<B, V>
+where
+    A: Clone,
+    B: Key,
+    V: 'a,
+{
+    type Key = A;
+    type Iter = <Inner<B, V> as MapStorage<B, V>>::IterMut<'a>;
+    type Item = ((A, B), &'a mut V);
+
+    #[inline]
+    fn split((a, inner): (A, &'a mut Inner<B, V>)) -> (A, Self::Iter) {
+        (a, inner.iter_mut())
+    }
+
+    #[inline]
+    fn join(a: A, (b, value): (B, &'a mut V)) -> Self::Item {
+        ((a, b), value)
+    }
+}
+
+/// Splits values of the outer storage for [`TupleMapStorage::values_mut`].
+pub struct SplitValuesMut<B, V>(PhantomData<fn() -> (B, V)>);
+
+impl<'a, B, V> SplitNested<&'a mut Inner<B, V>> for SplitValuesMut<B, V>
+where
+    B: Key,
+    V: 'a,
+{
+    type Key = ();
+    type Iter = <Inner<B, V> as MapStorage<B, V>>::ValuesMut<'a>;
+    type Item = &'a mut V;
+
+    #[inline]
+    fn split(inner: &'a mut Inner<B, V>) -> ((), Self::Iter) {
+        ((), inner.values_mut())
+    }
+
+    #[inline]
+    fn join((): (), value: &'a mut V) -> Self::Item {
+        value
+    }
+}
+
+/// Splits entries of the outer storage for [`TupleMapStorage::into_iter`].
+pub struct SplitIntoIter<B, V>(PhantomData<fn() -> (B, V)>);
+
+impl<A, B, V> SplitNested<(A, Inner<B, V>)> for SplitIntoIter<B, V>
+where
+    A: Clone,
+    B: Key,
+{
+    type Key = A;
+    type Iter = <Inner<B, V> as MapStorage<B, V>>::IntoIter;
+    type Item = ((A, B), V);
+
+    #[inline]
+    fn split((a, inner): (A, Inner<B, V>)) -> (A, Self::Iter) {
+        (a, inner.into_iter())
+    }
+
+    #[inline]
+    fn join(a: A, (b, value): (B, V)) -> Self::Item {
+        ((a, b), value)
+    }
+}
+
+/// [`MapStorage`] for tuples of two keys.
+///
+/// The storage for the second element of the key is nested inside of the
+/// storage for the first element, so entries are iterated over in
+/// lexicographic order: first by the first element, then by the second.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Map};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum State {
+///     Idle,
+///     Running,
+/// }
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Event {
+///     Start,
+///     Stop,
+/// }
+///
+/// let mut transitions = Map::new();
+/// transitions.insert((State::Running, Event::Stop), State::Idle);
+/// transitions.insert((State::Idle, Event::Start), State::Running);
+///
+/// assert_eq!(transitions.get((State::Idle, Event::Start)), Some(&State::Running));
+/// assert_eq!(transitions.get((State::Idle, Event::Stop)), None);
+///
+/// assert!(transitions.keys().eq([(State::Idle, Event::Start), (State::Running, Event::Stop)]));
+/// ```
+pub struct TupleMapStorage<A, B, V>
+where
+    A: Key,
+    B: Key,
+{
+    data: Outer<A, B, V>,
+}
+
+impl<A, B, V> Clone for TupleMapStorage<A, B, V>
+where
+    A: Key,
+    B: Key,
+    Outer<A, B, V>: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+        }
+    }
+
+    #[inline]
+    fn clone_from(&mut self, source: &Self) {
+        self.data.clone_from(&source.data);
+    }
+}
+
+impl<A, B, V> Copy for TupleMapStorage<A, B, V>
+where
+    A: Key,
+    B: Key,
+    Outer<A, B, V>: Copy,
+{
+}
+
+/// Storage is compared by its entries, since the storage for the first
+/// element of the key may hold empty storage for the second element.
+impl<A, B, V> PartialEq for TupleMapStorage<A, B, V>
+where
+    A: Key,
+    B: Key,
+    V: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .all(|(key, value)| other.get(key) == Some(value))
+    }
+}
+
+impl<A, B, V> Eq for TupleMapStorage<A, B, V>
+where
+    A: Key,
+    B: Key,
+    V: Eq,
+{
+}
+
+pub enum Vacant<'a, A: 'a, B: 'a, V: 'a>
+where
+    A: Key,
+    B: Key,
+{
+    /// There is no storage for the first element of the key yet.
+    Outer(
+        <Outer<A, B, V> as MapStorage<A, Inner<B, V>>>::Vacant<'a>,
+        B,
+    ),
+    /// The storage for the first element of the key exists, but doesn't hold
+    /// the second element.
+    Inner(A, <Inner<B, V> as MapStorage<B, V>>::Vacant<'a>),
+}
+
+pub struct Occupied<'a, A: 'a, B: 'a, V: 'a>
+where
+    A: Key,
+    B: Key,
+{
+    key: A,
+    inner: <Inner<B, V> as MapStorage<B, V>>::Occupied<'a>,
+}
+
+impl<'a, A, B, V> VacantEntry<'a, (A, B), V> for Vacant<'a, A, B, V>
+where
+    A: Key,
+    B: Key,
+    V: 'a,
+{
+    #[inline]
+    fn key(&self) -> (A, B) {
+        match self {
+            Vacant::Outer(entry, b) => (entry.key(), b.clone()),
+            Vacant::Inner(a, entry) => (a.clone(), entry.key()),
+        }
+    }
+
+    #[inline]
+    fn into_key(self) -> (A, B) {
+        match self {
+            Vacant::Outer(entry, b) => (entry.into_key(), b),
+            Vacant::Inner(a, entry) => (a, entry.into_key()),
+        }
+    }
+
+    #[inline]
+    fn insert(self, value: V) -> &'a mut V {
+        match self {
+            Vacant::Outer(entry, b) => entry
+                .insert(Inner::<B, V>::empty())
+                .get_or_insert_with_key(b, |_| value),
+            Vacant::Inner(_, entry) => entry.insert(value),
+        }
+    }
+}
+
+impl<'a, A, B, V> OccupiedEntry<'a, (A, B), V> for Occupied<'a, A, B, V>
+where
+    A: Key,
+    B: Key,
+    V: 'a,
+{
+    #[inline]
+    fn key(&self) -> (A, B) {
+        (self.key.clone(), self.inner.key())
+    }
+
+    #[inline]
+    fn get(&self) -> &V {
+        self.inner.get()
+    }
+
+    #[inline]
+    fn get_mut(&mut self) -> &mut V {
+        self.inner.get_mut()
+    }
+
+    #[inline]
+    fn into_mut(self) -> &'a mut V {
+        self.inner.into_mut()
+    }
+
+    #[inline]
+    fn insert(&mut self, value: V) -> V {
+        self.inner.insert(value)
+    }
+
+    #[inline]
+    fn remove(self) -> V {
+        self.inner.remove()
+    }
+
+    #[inline]
+    fn replace_key(self, (a, b): (A, B)) -> (A, B) {
+        (a, self.inner.replace_key(b))
+    }
+}
+
+impl<A, B, V> ConstMapStorage<(A, B), V> for TupleMapStorage<A, B, V>
+where
+    A: Key,
+    B: Key,
+    Outer<A, B, V>: ConstMapStorage<A, Inner<B, V>>,
+{
+    const EMPTY: Self = Self {
+        data: <Outer<A, B, V> as ConstMapStorage<A, Inner<B, V>>>::EMPTY,
+    };
+}
+
+impl<A, B, V> MapStorage<(A, B), V> for TupleMapStorage<A, B, V>
+where
+    A: Key,
+    B: Key,
+{
+    type Iter<'this>
+        = Iter<'this, A, B, V>
+    where
+        A: 'this,
+        B: 'this,
+        V: 'this;
+    type Keys<'this>
+        = Keys<'this, A, B, V>
+    where
+        A: 'this,
+        B: 'this,
+        V: 'this;
+    type Values<'this>
+        = Values<'this, A, B, V>
+    where
+        A: 'this,
+        B: 'this,
+        V: 'this;
+    type IterMut<'this>
+        = IterMut<'this, A, B, V>
+    where
+        A: 'this,
+        B: 'this,
+        V: 'this;
+    type ValuesMut<'this>
+        = ValuesMut<'this, A, B, V>
+    where
+        A: 'this,
+        B: 'this,
+        V: 'this;
+    type IntoIter = IntoIter<A, B, V>;
+    type Occupied<'this>
+        = Occupied<'this, A, B, V>
+    where
+        A: 'this,
+        B: 'this,
+        V: 'this;
+    type Vacant<'this>
+        = Vacant<'this, A, B, V>
+    where
+        A: 'this,
+        B: 'this,
+        V: 'this;
+
+    #[inline]
+    fn empty() -> Self {
+        Self {
+            data: Outer::<A, B, V>::empty(),
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.data.values().map(MapStorage::len).sum()
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.data.values().all(MapStorage::is_empty)
+    }
+
+    #[inline]
+    fn insert(&mut self, (a, b): (A, B), value: V) -> Option<V> {
+        self.data
+            .get_or_insert_with_key(a, |_| Inner::<B, V>::empty())
+            .insert(b, value)
+    }
+
+    #[inline]
+    fn contains_key(&self, (a, b): (A, B)) -> bool {
+        self.data
+            .get(a)
+            .map_or(false, |inner| inner.contains_key(b))
+    }
+
+    #[inline]
+    fn get(&self, (a, b): (A, B)) -> Option<&V> {
+        self.data.get(a)?.get(b)
+    }
+
+    #[inline]
+    fn get_mut(&mut self, (a, b): (A, B)) -> Option<&mut V> {
+        self.data.get_mut(a)?.get_mut(b)
+    }
+
+    #[inline]
+    fn remove(&mut self, (a, b): (A, B)) -> Option<V> {
+        let inner = self.data.get_mut(a.clone())?;
+        let value = inner.remove(b)?;
+
+        if inner.is_empty() {
+            self.data.remove(a);
+        }
+
+        Some(value)
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, mut func: F)
+    where
+        F: FnMut((A, B), &mut V) -> bool,
+    {
+        self.data.retain(|a, inner| {
+            inner.retain(|b, value| func((a.clone(), b), value));
+            !inner.is_empty()
+        });
+    }
+
+    #[inline]
+    fn retain_with_removed<F, R>(&mut self, mut keep: F, mut on_remove: R)
+    where
+        F: FnMut((A, B), &mut V) -> bool,
+        R: FnMut((A, B), V),
+    {
+        self.data.retain(|a, inner| {
+            inner.retain_with_removed(
+                |b, value| keep((a.clone(), b), value),
+                |b, value| on_remove((a.clone(), b), value),
+            );
+            !inner.is_empty()
+        });
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        Nested::new(self.data.iter(), self.len())
+    }
+
+    #[inline]
+    fn keys(&self) -> Self::Keys<'_> {
+        Nested::new(self.data.iter(), self.len())
+    }
+
+    #[inline]
+    fn values(&self) -> Self::Values<'_> {
+        Nested::new(self.data.values(), self.len())
+    }
+
+    #[inline]
+    fn iter_mut(&mut self) -> Self::IterMut<'_> {
+        let len = self.len();
+
+        Nested::new(self.data.iter_mut(), len)
+    }
+
+    #[inline]
+    fn values_mut(&mut self) -> Self::ValuesMut<'_> {
+        let len = self.len();
+
+        Nested::new(self.data.values_mut(), len)
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        let len = self.len();
+
+        Nested::new(self.data.into_iter(), len)
+    }
+
+    #[inline]
+    fn entry(&mut self, (a, b): (A, B)) -> Entry<'_, Self, (A, B), V> {
+        match self.data.entry(a) {
+            Entry::Occupied(entry) => {
+                let a = entry.key();
+
+                match entry.into_mut().entry(b) {
+                    Entry::Occupied(inner) => Entry::Occupied(Occupied { key: a, inner }),
+                    Entry::Vacant(inner) => Entry::Vacant(Vacant::Inner(a, inner)),
+                }
+            }
+            Entry::Vacant(entry) => Entry::Vacant(Vacant::Outer(entry, b)),
+        }
+    }
+
+    #[inline]
+    fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+    }
+
+    #[inline]
+    fn shrink_to_fit(&mut self) {
+        // Entries might have been removed through an occupied entry, which
+        // leaves empty storage behind for the first element of the key.
+        self.data.retain(|_, inner| {
+            inner.shrink_to_fit();
+            !inner.is_empty()
+        });
+
+        self.data.shrink_to_fit();
+    }
+
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.data.heap_size() + self.data.values().map(MapStorage::heap_size).sum::<usize>()
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.data.values().map(MapStorage::capacity).sum()
+    }
+}
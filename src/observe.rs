@@ -0,0 +1,324 @@
+//! A [`Map`] wrapper which reports every change made to it.
+//!
+//! See [`ObservableMap`].
+
+use core::fmt;
+use core::ops::Deref;
+
+use crate::key::Key;
+use crate::map::{Change, Entry, Map, OccupiedEntry, VacantEntry};
+
+/// A wrapper around a [`Map`] which calls an observer for every modification
+/// made through it.
+///
+/// All read methods of the underlying map are available through [`Deref`],
+/// while mutation goes through the methods on the wrapper so that each change
+/// can be reported as a [`Change`]:
+///
+/// * [`Change::Added`] when a new key is inserted.
+/// * [`Change::Changed`] when the value of an existing key is replaced or
+///   modified, with the old value followed by the new one.
+/// * [`Change::Removed`] when a key is removed.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::Key;
+/// use fixed_map::map::Change;
+/// use fixed_map::observe::ObservableMap;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Setting {
+///     Volume,
+///     Theme,
+/// }
+///
+/// let mut log = Vec::new();
+///
+/// let mut settings = ObservableMap::new(|change: Change<'_, Setting, u32>| {
+///     log.push(format!("{change:?}"));
+/// });
+///
+/// settings.insert(Setting::Volume, 10);
+/// settings.insert(Setting::Volume, 20);
+/// settings.remove(Setting::Volume);
+///
+/// assert!(settings.is_empty());
+/// drop(settings);
+///
+/// assert_eq!(log, [
+///     "Added(Volume, 10)",
+///     "Changed(Volume, 10, 20)",
+///     "Removed(Volume, 20)",
+/// ]);
+/// ```
+pub struct ObservableMap<K, V, F>
+where
+    K: Key,
+{
+    map: Map<K, V>,
+    observer: F,
+}
+
+impl<K, V, F> ObservableMap<K, V, F>
+where
+    K: Key,
+    F: FnMut(Change<'_, K, V>),
+{
+    /// Construct an empty map which reports changes to `observer`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::map::Change;
+    /// use fixed_map::observe::ObservableMap;
+    ///
+    /// let map = ObservableMap::new(|_: Change<'_, bool, u32>| {});
+    /// assert!(map.is_empty());
+    /// ```
+    #[inline]
+    pub fn new(observer: F) -> Self {
+        Self::from_map(Map::new(), observer)
+    }
+
+    /// Wrap an existing map, reporting any changes made to it from now on to
+    /// `observer`. The entries already in `map` are not reported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::Map;
+    /// use fixed_map::map::Change;
+    /// use fixed_map::observe::ObservableMap;
+    ///
+    /// let mut removed = 0;
+    ///
+    /// let mut map = ObservableMap::from_map(Map::from_array([(true, 1)]), |change| {
+    ///     if let Change::Removed(..) = change {
+    ///         removed += 1;
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(map.get(true), Some(&1));
+    /// map.remove(true);
+    /// drop(map);
+    ///
+    /// assert_eq!(removed, 1);
+    /// ```
+    #[inline]
+    pub fn from_map(map: Map<K, V>, observer: F) -> Self {
+        Self { map, observer }
+    }
+
+    /// Inserts a key-value pair into the map, reporting either
+    /// [`Change::Added`] or [`Change::Changed`].
+    ///
+    /// Returns the previous value associated with the key, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::map::Change;
+    /// use fixed_map::observe::ObservableMap;
+    ///
+    /// let mut changes = 0;
+    /// let mut map = ObservableMap::new(|_: Change<'_, bool, u32>| changes += 1);
+    ///
+    /// assert_eq!(map.insert(true, 1), None);
+    /// assert_eq!(map.insert(true, 2), Some(1));
+    /// drop(map);
+    ///
+    /// assert_eq!(changes, 2);
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.map.entry(key.clone()) {
+            Entry::Occupied(mut entry) => {
+                let old = entry.insert(value);
+                (self.observer)(Change::Changed(key, &old, entry.get()));
+                Some(old)
+            }
+            Entry::Vacant(entry) => {
+                let new = entry.insert(value);
+                (self.observer)(Change::Added(key, new));
+                None
+            }
+        }
+    }
+
+    /// Removes a key from the map, reporting [`Change::Removed`] if it was
+    /// present.
+    ///
+    /// Returns the value associated with the key, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::map::Change;
+    /// use fixed_map::observe::ObservableMap;
+    ///
+    /// let mut changes = 0;
+    /// let mut map = ObservableMap::new(|_: Change<'_, bool, u32>| changes += 1);
+    ///
+    /// map.insert(true, 1);
+    /// assert_eq!(map.remove(true), Some(1));
+    /// assert_eq!(map.remove(true), None);
+    /// drop(map);
+    ///
+    /// assert_eq!(changes, 2);
+    /// ```
+    #[inline]
+    pub fn remove(&mut self, key: K) -> Option<V> {
+        let value = self.map.remove(key.clone())?;
+        (self.observer)(Change::Removed(key, &value));
+        Some(value)
+    }
+
+    /// Modifies the value associated with `key` in place using `f`, reporting
+    /// [`Change::Changed`] with the value from before and after the call.
+    ///
+    /// Returns `false` and doesn't call `f` if the key isn't present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::map::Change;
+    /// use fixed_map::observe::ObservableMap;
+    ///
+    /// let mut seen = Vec::new();
+    /// let mut map = ObservableMap::new(|change: Change<'_, bool, u32>| {
+    ///     if let Change::Changed(_, old, new) = change {
+    ///         seen.push((*old, *new));
+    ///     }
+    /// });
+    ///
+    /// map.insert(true, 1);
+    /// assert!(map.modify(true, |value| *value += 10));
+    /// assert!(!map.modify(false, |value| *value += 10));
+    /// drop(map);
+    ///
+    /// assert_eq!(seen, [(1, 11)]);
+    /// ```
+    #[inline]
+    pub fn modify<M>(&mut self, key: K, f: M) -> bool
+    where
+        V: Clone,
+        M: FnOnce(&mut V),
+    {
+        let Some(value) = self.map.get_mut(key.clone()) else {
+            return false;
+        };
+
+        let old = value.clone();
+        f(value);
+        (self.observer)(Change::Changed(key, &old, value));
+        true
+    }
+
+    /// Retains only the entries for which `f` returns `true`, reporting
+    /// [`Change::Removed`] for every entry which is removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::map::Change;
+    /// use fixed_map::observe::ObservableMap;
+    ///
+    /// let mut removed = Vec::new();
+    /// let mut map = ObservableMap::new(|change: Change<'_, bool, u32>| {
+    ///     if let Change::Removed(key, _) = change {
+    ///         removed.push(key);
+    ///     }
+    /// });
+    ///
+    /// map.insert(false, 1);
+    /// map.insert(true, 2);
+    /// map.retain(|_, value| *value > 1);
+    /// drop(map);
+    ///
+    /// assert_eq!(removed, [false]);
+    /// ```
+    #[inline]
+    pub fn retain<R>(&mut self, f: R)
+    where
+        R: FnMut(K, &mut V) -> bool,
+    {
+        let observer = &mut self.observer;
+
+        self.map
+            .retain_with_removed(f, |key, value| observer(Change::Removed(key, &value)));
+    }
+
+    /// Clears the map, reporting [`Change::Removed`] for every entry in it.
+    /// Keeps the allocated memory for reuse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::Map;
+    /// use fixed_map::observe::ObservableMap;
+    ///
+    /// let mut removed = 0;
+    /// let mut map = ObservableMap::from_map(Map::from_array([(false, 1), (true, 2)]), |_| {
+    ///     removed += 1;
+    /// });
+    ///
+    /// map.clear();
+    /// assert!(map.is_empty());
+    /// drop(map);
+    ///
+    /// assert_eq!(removed, 2);
+    /// ```
+    #[inline]
+    pub fn clear(&mut self) {
+        for (key, value) in &self.map {
+            (self.observer)(Change::Removed(key, value));
+        }
+
+        self.map.clear();
+    }
+
+    /// Unwrap the underlying map, dropping the observer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::map::Change;
+    /// use fixed_map::observe::ObservableMap;
+    ///
+    /// let mut map = ObservableMap::new(|_: Change<'_, bool, u32>| {});
+    /// map.insert(true, 1);
+    ///
+    /// let map = map.into_inner();
+    /// assert_eq!(map.get(true), Some(&1));
+    /// ```
+    #[inline]
+    pub fn into_inner(self) -> Map<K, V> {
+        self.map
+    }
+}
+
+impl<K, V, F> Deref for ObservableMap<K, V, F>
+where
+    K: Key,
+{
+    type Target = Map<K, V>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.map
+    }
+}
+
+impl<K, V, F> fmt::Debug for ObservableMap<K, V, F>
+where
+    K: Key + fmt::Debug,
+    V: fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObservableMap")
+            .field("map", &self.map)
+            .finish_non_exhaustive()
+    }
+}
@@ -294,6 +294,11 @@ where
     fn remove(self) -> V {
         SomeBucket::take(self)
     }
+
+    #[inline]
+    fn replace_key(self, key: K) -> K {
+        key
+    }
 }
 
 /// Abstraction for an [`&mut Option`][Option] that's known to be `None`.
@@ -376,6 +381,11 @@ where
         K::default()
     }
 
+    #[inline]
+    fn into_key(self) -> K {
+        K::default()
+    }
+
     #[inline]
     fn insert(self, value: V) -> &'a mut V {
         NoneBucket::insert(self, value)
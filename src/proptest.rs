@@ -0,0 +1,89 @@
+//! [`proptest`] strategies for [`Map`] and [`Set`].
+//!
+//! This requires the `proptest` feature.
+//!
+//! [`proptest`]: https://docs.rs/proptest
+
+use core::fmt;
+
+use ::proptest::collection::{vec, SizeRange};
+use ::proptest::strategy::Strategy;
+
+use crate::key::Key;
+use crate::map::Map;
+use crate::set::Set;
+
+/// Construct a strategy which generates a [`Map`] from the given key and value
+/// strategies.
+///
+/// Entries are drawn from a vector of key-value pairs using the default size
+/// range of proptest's collection strategies, so keys which are generated
+/// more than once keep the last value.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::Key;
+/// use fixed_map::proptest::map_strategy;
+/// use proptest::prelude::*;
+/// use proptest::test_runner::TestRunner;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Key {
+///     First,
+///     Second,
+/// }
+///
+/// let key = prop_oneof![Just(Key::First), Just(Key::Second)];
+///
+/// let mut runner = TestRunner::default();
+///
+/// runner
+///     .run(&map_strategy(key, 0..10u32), |map| {
+///         prop_assert!(map.len() <= 2);
+///         prop_assert!(map.values().all(|v| *v < 10));
+///         Ok(())
+///     })
+///     .unwrap();
+/// ```
+#[inline]
+pub fn map_strategy<K, V>(key: K, value: V) -> impl Strategy<Value = Map<K::Value, V::Value>>
+where
+    K: Strategy,
+    V: Strategy,
+    K::Value: Key + fmt::Debug,
+    V::Value: fmt::Debug,
+{
+    vec((key, value), SizeRange::default()).prop_map(|entries| entries.into_iter().collect())
+}
+
+/// Construct a strategy which generates a [`Set`] from the given value
+/// strategy.
+///
+/// Values are drawn from a vector using the default size range of proptest's
+/// collection strategies.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::proptest::set_strategy;
+/// use proptest::prelude::*;
+/// use proptest::test_runner::TestRunner;
+///
+/// let mut runner = TestRunner::default();
+///
+/// runner
+///     .run(&set_strategy(any::<bool>()), |set| {
+///         prop_assert!(set.len() <= 2);
+///         Ok(())
+///     })
+///     .unwrap();
+/// ```
+#[inline]
+pub fn set_strategy<T>(value: T) -> impl Strategy<Value = Set<T::Value>>
+where
+    T: Strategy,
+    T::Value: Key + fmt::Debug,
+{
+    vec(value, SizeRange::default()).prop_map(|values| values.into_iter().collect())
+}
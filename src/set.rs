@@ -1,13 +1,15 @@
 //! Contains the fixed [`Set`] implementation.
 
 pub mod storage;
+#[cfg(feature = "rayon")]
+pub use crate::set::storage::ParSetStorage;
 pub use crate::set::storage::SetStorage;
 
 use core::cmp::Ordering;
 use core::fmt;
 use core::hash::{Hash, Hasher};
 
-use crate::key::Key;
+use crate::key::{FixedKey, Key};
 
 /// The iterator produced by [`Set::iter`].
 pub type Iter<'a, T> = <<T as Key>::SetStorage as SetStorage<T>>::Iter<'a>;
@@ -15,6 +17,10 @@ pub type Iter<'a, T> = <<T as Key>::SetStorage as SetStorage<T>>::Iter<'a>;
 /// The iterator produced by [`Set::into_iter`].
 pub type IntoIter<T> = <<T as Key>::SetStorage as SetStorage<T>>::IntoIter;
 
+/// The parallel iterator produced by [`Set::par_iter`].
+#[cfg(feature = "rayon")]
+pub type ParIter<'a, T> = <<T as Key>::SetStorage as ParSetStorage<T>>::ParIter<'a>;
+
 /// A fixed set with storage specialized through the [`Key`] trait.
 ///
 /// # Examples
@@ -149,7 +155,39 @@ where
         }
     }
 
-    /// An iterator visiting all values in arbitrary order.
+    /// Creates a [`Set`] from an array of values.
+    ///
+    /// Values which occur more than once in the array are only stored once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second,
+    ///     Third,
+    /// }
+    ///
+    /// let set = Set::from_array([Key::Third, Key::First, Key::Third]);
+    ///
+    /// assert_eq!(set.len(), 2);
+    /// assert!(set.iter().eq([Key::First, Key::Third]));
+    /// ```
+    #[inline]
+    pub fn from_array<const N: usize>(values: [T; N]) -> Set<T> {
+        let mut set = Set::new();
+
+        for value in values {
+            set.insert(value);
+        }
+
+        set
+    }
+
+    /// An iterator visiting all values in [key order](crate::Key#ordering).
     /// The iterator element type is `T`.
     ///
     /// # Examples
@@ -325,6 +363,82 @@ where
         self.storage.retain(f);
     }
 
+    /// Removes every value in `other` from the set, in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second(bool),
+    ///     Third,
+    /// }
+    ///
+    /// let mut active = Set::from_array([Key::First, Key::Second(true), Key::Third]);
+    /// active.subtract(&Set::from_array([Key::Second(true), Key::Second(false)]));
+    ///
+    /// assert!(active.iter().eq([Key::First, Key::Third]));
+    /// ```
+    #[inline]
+    pub fn subtract(&mut self, other: &Set<T>) {
+        for value in other.storage.iter() {
+            self.storage.remove(value);
+        }
+    }
+
+    /// Retains only the values which are also in `other`, in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second(bool),
+    ///     Third,
+    /// }
+    ///
+    /// let mut active = Set::from_array([Key::First, Key::Second(true), Key::Third]);
+    /// active.intersect_with(&Set::from_array([Key::Second(true), Key::Third]));
+    ///
+    /// assert!(active.iter().eq([Key::Second(true), Key::Third]));
+    /// ```
+    #[inline]
+    pub fn intersect_with(&mut self, other: &Set<T>) {
+        self.storage.retain(|value| other.storage.contains(value));
+    }
+
+    /// Inserts every value in `other` into the set, in place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Key {
+    ///     First,
+    ///     Second(bool),
+    ///     Third,
+    /// }
+    ///
+    /// let mut active = Set::from_array([Key::First]);
+    /// active.union_with(&Set::from_array([Key::Second(false), Key::First]));
+    ///
+    /// assert!(active.iter().eq([Key::First, Key::Second(false)]));
+    /// ```
+    #[inline]
+    pub fn union_with(&mut self, other: &Set<T>) {
+        for value in other.storage.iter() {
+            self.storage.insert(value);
+        }
+    }
+
     /// Clears the set, removing all values.
     ///
     /// # Examples
@@ -393,6 +507,142 @@ where
     pub fn len(&self) -> usize {
         self.storage.len()
     }
+
+    /// Returns the number of values for which `f` returns `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Clone, Copy, Key)]
+    /// enum State {
+    ///     Idle,
+    ///     Failed(bool),
+    /// }
+    ///
+    /// let set = Set::from_array([State::Idle, State::Failed(true), State::Failed(false)]);
+    ///
+    /// assert_eq!(set.count(|state| matches!(state, State::Failed(_))), 2);
+    /// ```
+    #[inline]
+    pub fn count<F>(&self, mut f: F) -> usize
+    where
+        F: FnMut(T) -> bool,
+    {
+        self.storage.iter().filter(|value| f(value.clone())).count()
+    }
+}
+
+impl<T> Set<T>
+where
+    T: FixedKey + 'static,
+{
+    /// Returns a set containing every possible value of the key.
+    ///
+    /// This is only available for keys which implement [`FixedKey`], such as
+    /// enums with unit variants.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Permission {
+    ///     Read,
+    ///     Write,
+    ///     Delete,
+    /// }
+    ///
+    /// let mut granted = Set::full();
+    /// granted.remove(Permission::Delete);
+    ///
+    /// assert!(granted.iter().eq([Permission::Read, Permission::Write]));
+    /// assert!(Set::<Permission>::full().complement().is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn full() -> Set<T> {
+        let mut set = Set::<T>::new();
+
+        for value in T::keys() {
+            set.storage.insert(value);
+        }
+
+        set
+    }
+
+    /// Returns a set containing every possible value which is not present in
+    /// this set.
+    ///
+    /// This is only available for keys which implement [`FixedKey`], such as
+    /// enums with unit variants.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use fixed_map::{Key, Set};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    /// enum Feature {
+    ///     Audio,
+    ///     Video,
+    ///     Chat,
+    /// }
+    ///
+    /// let enabled = Set::from_array([Feature::Video]);
+    /// let disabled = enabled.complement();
+    ///
+    /// assert!(disabled.iter().eq([Feature::Audio, Feature::Chat]));
+    /// assert_eq!(disabled.complement(), enabled);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn complement(&self) -> Set<T> {
+        let mut set = Set::<T>::new();
+
+        for value in T::keys() {
+            if !self.storage.contains(value) {
+                set.storage.insert(value);
+            }
+        }
+
+        set
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T> Set<T>
+where
+    T: Key,
+    T::SetStorage: ParSetStorage<T>,
+{
+    /// A parallel iterator visiting all values in the set.
+    ///
+    /// This requires the `rayon` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "hashbrown")] {
+    /// use fixed_map::Set;
+    /// use rayon::prelude::*;
+    ///
+    /// let mut set = Set::new();
+    /// set.insert(Some(1u32));
+    /// set.insert(Some(2u32));
+    /// set.insert(None);
+    ///
+    /// let sum: u32 = set.par_iter().flatten().sum();
+    /// assert_eq!(sum, 3);
+    /// assert_eq!(set.par_iter().filter(Option::is_none).count(), 1);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn par_iter(&self) -> ParIter<'_, T> {
+        self.storage.par_iter()
+    }
 }
 
 /// [`Clone`] implementation for a [`Set`].
@@ -432,6 +682,11 @@ where
             storage: self.storage.clone(),
         }
     }
+
+    #[inline]
+    fn clone_from(&mut self, source: &Set<T>) {
+        self.storage.clone_from(&source.storage);
+    }
 }
 
 /// The [`Copy`] implementation for a [`Set`] depends on its [`Key`]. If the
@@ -844,7 +1099,7 @@ where
     type Item = T;
     type IntoIter = IntoIter<T>;
 
-    /// An iterator visiting all values in arbitrary order.
+    /// An iterator visiting all values in [key order](crate::Key#ordering).
     /// The iterator element type is `T`.
     ///
     /// # Examples
@@ -904,7 +1159,7 @@ where
 
         let mut seq = serializer.serialize_seq(Some(self.len()))?;
 
-        for v in self.iter() {
+        for v in self {
             seq.serialize_element(&v)?;
         }
 
@@ -952,3 +1207,31 @@ where
         deserializer.deserialize_seq(SeqVisitor(core::marker::PhantomData))
     }
 }
+
+#[cfg(feature = "arbitrary")]
+impl<'a, T> arbitrary::Arbitrary<'a> for Set<T>
+where
+    T: Key + arbitrary::Arbitrary<'a>,
+{
+    #[inline]
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut set = Set::new();
+
+        for value in u.arbitrary_iter()? {
+            set.insert(value?);
+        }
+
+        Ok(set)
+    }
+
+    #[inline]
+    fn arbitrary_take_rest(u: arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut set = Set::new();
+
+        for value in u.arbitrary_take_rest_iter()? {
+            set.insert(value?);
+        }
+
+        Ok(set)
+    }
+}
@@ -3,9 +3,20 @@
 mod singleton;
 pub use self::singleton::SingletonSetStorage;
 
+mod bitset;
+pub use self::bitset::BitsetSetStorage;
+
 mod boolean;
 pub use self::boolean::BooleanSetStorage;
 
+mod byte;
+pub use self::byte::ByteSetStorage;
+
+#[cfg(feature = "alloc")]
+mod btree;
+#[cfg(feature = "alloc")]
+pub use self::btree::BTreeSetStorage;
+
 #[cfg(feature = "hashbrown")]
 mod hashbrown;
 #[cfg(feature = "hashbrown")]
@@ -14,6 +25,17 @@ pub use self::hashbrown::HashbrownSetStorage;
 mod option;
 pub use self::option::OptionSetStorage;
 
+mod result;
+pub use self::result::ResultSetStorage;
+
+mod tuple;
+pub use self::tuple::TupleSetStorage;
+
+use core::iter::FusedIterator;
+
+#[cfg(feature = "rayon")]
+use rayon::iter::ParallelIterator;
+
 /// The trait defining how storage works for [`Set`][crate::Set].
 ///
 /// # Type Arguments
@@ -21,12 +43,12 @@ pub use self::option::OptionSetStorage;
 /// - `T` is the key being stored.
 pub trait SetStorage<T>: Sized {
     /// Immutable iterator over storage.
-    type Iter<'this>: Iterator<Item = T>
+    type Iter<'this>: ExactSizeIterator<Item = T> + FusedIterator
     where
         Self: 'this;
 
     /// Owning iterator over the storage.
-    type IntoIter: Iterator<Item = T>;
+    type IntoIter: ExactSizeIterator<Item = T> + FusedIterator;
 
     /// Construct empty storage.
     fn empty() -> Self;
@@ -60,3 +82,21 @@ pub trait SetStorage<T>: Sized {
     /// This is the storage abstraction for [`Set::into_iter`][crate::Set::into_iter].
     fn into_iter(self) -> Self::IntoIter;
 }
+
+/// Storage which supports parallel iteration with [`rayon`].
+///
+/// This is implemented for the array storage of unit enum keys, for dynamic
+/// keys backed by `hashbrown`, and for [`Option<T>`] when the storage of `T`
+/// supports it.
+///
+/// [`rayon`]: https://docs.rs/rayon
+#[cfg(feature = "rayon")]
+pub trait ParSetStorage<T>: SetStorage<T> {
+    /// Parallel iterator over storage.
+    type ParIter<'this>: ParallelIterator<Item = T>
+    where
+        Self: 'this;
+
+    /// This is the storage abstraction for [`Set::par_iter`][crate::Set::par_iter].
+    fn par_iter(&self) -> Self::ParIter<'_>;
+}
@@ -0,0 +1,285 @@
+// Iterators are confusing if they impl `Copy`.
+#![allow(missing_copy_implementations)]
+
+use core::cmp::Ordering;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+
+#[cfg(feature = "rayon")]
+use rayon::iter::ParallelBridge;
+
+use crate::key::FixedKey;
+#[cfg(feature = "rayon")]
+use crate::set::ParSetStorage;
+use crate::set::SetStorage;
+
+/// [`SetStorage`] for keys with a fixed number of values, such as enums where
+/// every variant is a unit variant.
+///
+/// Membership is packed into `N` 64-bit words, one bit for each value in the
+/// order given by [`FixedKey::index`]. `#[derive(Key)]` selects this storage
+/// for unit-only enums with `N` set to the number of words needed to fit every
+/// index. Iteration happens in the order of [`FixedKey::index`], which is
+/// declaration order unless the key is indexed by discriminant.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Set};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Key {
+///     First,
+///     Second,
+///     Third,
+/// }
+///
+/// let mut set = Set::new();
+/// set.insert(Key::Third);
+/// set.insert(Key::First);
+///
+/// assert!(set.contains(Key::First));
+/// assert!(!set.contains(Key::Second));
+/// assert_eq!(set.len(), 2);
+///
+/// assert!(set.iter().eq([Key::First, Key::Third]));
+/// assert!(set.iter().rev().eq([Key::Third, Key::First]));
+/// assert_eq!(core::mem::size_of_val(&set), 8);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BitsetSetStorage<const N: usize> {
+    bits: [u64; N],
+}
+
+/// Sets are ordered by comparing their members in index order, which is
+/// equivalent to comparing the indexes of their set bits lexicographically.
+impl<const N: usize> PartialOrd for BitsetSetStorage<N> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for BitsetSetStorage<N> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        Indexes { bits: self.bits }.cmp(Indexes { bits: other.bits })
+    }
+}
+
+/// See [`BitsetSetStorage::iter`].
+pub struct Iter<T, const N: usize> {
+    indexes: Indexes<N>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T, const N: usize> Clone for Iter<T, N> {
+    #[inline]
+    fn clone(&self) -> Iter<T, N> {
+        Iter {
+            indexes: Indexes {
+                bits: self.indexes.bits,
+            },
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T, const N: usize> Iterator for Iter<T, N>
+where
+    T: FixedKey,
+{
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        T::from_index(self.indexes.next()?)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indexes.size_hint()
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for Iter<T, N>
+where
+    T: FixedKey,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        T::from_index(self.indexes.next_back()?)
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for Iter<T, N>
+where
+    T: FixedKey,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        self.indexes.len()
+    }
+}
+
+impl<T, const N: usize> FusedIterator for Iter<T, N> where T: FixedKey {}
+
+impl<T, const N: usize> SetStorage<T> for BitsetSetStorage<N>
+where
+    T: FixedKey,
+{
+    type Iter<'this> = Iter<T, N>;
+    type IntoIter = Iter<T, N>;
+
+    #[inline]
+    fn empty() -> Self {
+        Self { bits: [0; N] }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        count(&self.bits)
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.bits.iter().all(|word| *word == 0)
+    }
+
+    #[inline]
+    fn insert(&mut self, value: T) -> bool {
+        let (word, mask) = to_bits(value.index());
+        let word = &mut self.bits[word];
+        let inserted = *word & mask == 0;
+        *word |= mask;
+        inserted
+    }
+
+    #[inline]
+    fn contains(&self, value: T) -> bool {
+        let (word, mask) = to_bits(value.index());
+        self.bits[word] & mask != 0
+    }
+
+    #[inline]
+    fn remove(&mut self, value: T) -> bool {
+        let (word, mask) = to_bits(value.index());
+        let word = &mut self.bits[word];
+        let removed = *word & mask != 0;
+        *word &= !mask;
+        removed
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(T) -> bool,
+    {
+        for value in SetStorage::<T>::iter(self) {
+            if !f(value) {
+                SetStorage::<T>::remove(self, value);
+            }
+        }
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.bits = [0; N];
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter {
+            indexes: Indexes { bits: self.bits },
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        Iter {
+            indexes: Indexes { bits: self.bits },
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T, const N: usize> ParSetStorage<T> for BitsetSetStorage<N>
+where
+    T: FixedKey + Send,
+{
+    type ParIter<'this> = rayon::iter::IterBridge<Iter<T, N>>;
+
+    #[inline]
+    fn par_iter(&self) -> Self::ParIter<'_> {
+        SetStorage::<T>::iter(self).par_bridge()
+    }
+}
+
+/// Iterator over the indexes of the set bits, in ascending order.
+struct Indexes<const N: usize> {
+    bits: [u64; N],
+}
+
+impl<const N: usize> Iterator for Indexes<N> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        for (index, word) in self.bits.iter_mut().enumerate() {
+            if *word != 0 {
+                let bit = word.trailing_zeros();
+                *word &= !(1 << bit);
+                return Some(to_index(index, bit));
+            }
+        }
+
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = count(&self.bits);
+        (len, Some(len))
+    }
+}
+
+impl<const N: usize> DoubleEndedIterator for Indexes<N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        for (index, word) in self.bits.iter_mut().enumerate().rev() {
+            if *word != 0 {
+                let bit = u64::BITS - 1 - word.leading_zeros();
+                *word &= !(1 << bit);
+                return Some(to_index(index, bit));
+            }
+        }
+
+        None
+    }
+}
+
+impl<const N: usize> ExactSizeIterator for Indexes<N> {
+    #[inline]
+    fn len(&self) -> usize {
+        count(&self.bits)
+    }
+}
+
+#[inline]
+fn count<const N: usize>(bits: &[u64; N]) -> usize {
+    bits.iter().map(|word| word.count_ones() as usize).sum()
+}
+
+/// Get the word index and the mask of the bit for the given key index.
+#[inline]
+const fn to_bits(index: usize) -> (usize, u64) {
+    (index / 64, 1 << (index % 64))
+}
+
+/// Get the key index corresponding to a bit in a word.
+#[inline]
+const fn to_index(index: usize, bit: u32) -> usize {
+    index * 64 + bit as usize
+}
@@ -1,6 +1,7 @@
 // Iterators are confusing if they impl `Copy`.
 #![allow(missing_copy_implementations)]
 
+use core::iter::FusedIterator;
 use core::mem;
 
 use crate::set::SetStorage;
@@ -10,6 +11,9 @@ const FALSE_BIT: u8 = 0b01;
 
 /// [`SetStorage`] for [`bool`] types.
 ///
+/// Values are iterated over with `false` before `true`, matching the ordering
+/// of [`bool`].
+///
 /// # Examples
 ///
 /// ```
@@ -46,8 +50,8 @@ const FALSE_BIT: u8 = 0b01;
 /// a.insert(Key::Bool(true));
 /// a.insert(Key::Bool(false));
 ///
-/// assert!(a.iter().eq([Key::Bool(true), Key::Bool(false)]));
-/// assert_eq!(a.iter().rev().collect::<Vec<_>>(), vec![Key::Bool(false), Key::Bool(true)]);
+/// assert!(a.iter().eq([Key::Bool(false), Key::Bool(true)]));
+/// assert_eq!(a.iter().rev().collect::<Vec<_>>(), vec![Key::Bool(true), Key::Bool(false)]);
 /// ```
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub struct BooleanSetStorage {
@@ -71,16 +75,16 @@ impl Iterator for Iter {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        if self.bits & TRUE_BIT != 0 {
-            self.bits &= !TRUE_BIT;
-            return Some(true);
-        }
-
         if self.bits & FALSE_BIT != 0 {
             self.bits &= !FALSE_BIT;
             return Some(false);
         }
 
+        if self.bits & TRUE_BIT != 0 {
+            self.bits &= !TRUE_BIT;
+            return Some(true);
+        }
+
         None
     }
 
@@ -94,16 +98,16 @@ impl Iterator for Iter {
 impl DoubleEndedIterator for Iter {
     #[inline]
     fn next_back(&mut self) -> Option<Self::Item> {
-        if self.bits & FALSE_BIT != 0 {
-            self.bits &= !FALSE_BIT;
-            return Some(false);
-        }
-
         if self.bits & TRUE_BIT != 0 {
             self.bits &= !TRUE_BIT;
             return Some(true);
         }
 
+        if self.bits & FALSE_BIT != 0 {
+            self.bits &= !FALSE_BIT;
+            return Some(false);
+        }
+
         None
     }
 }
@@ -115,6 +119,8 @@ impl ExactSizeIterator for Iter {
     }
 }
 
+impl FusedIterator for Iter {}
+
 impl SetStorage<bool> for BooleanSetStorage {
     type Iter<'this> = Iter;
     type IntoIter = Iter;
@@ -158,13 +164,13 @@ impl SetStorage<bool> for BooleanSetStorage {
     where
         F: FnMut(bool) -> bool,
     {
-        if test(self.bits, true) && !f(true) {
-            self.bits &= !TRUE_BIT;
-        }
-
         if test(self.bits, false) && !f(false) {
             self.bits &= !FALSE_BIT;
         }
+
+        if test(self.bits, true) && !f(true) {
+            self.bits &= !TRUE_BIT;
+        }
     }
 
     #[inline]
@@ -0,0 +1,131 @@
+use core::iter;
+
+use alloc::collections::btree_set;
+use alloc::collections::BTreeSet;
+
+use crate::set::SetStorage;
+
+/// [`SetStorage`] for dynamically stored types, using [`BTreeSet`].
+///
+/// Unlike [`HashbrownSetStorage`][crate::set::storage::HashbrownSetStorage],
+/// this iterates over values in ascending order. It is used for variants which
+/// are marked with `#[key(ordered)]`.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Set};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Key {
+///     #[key(ordered)]
+///     Score(u32),
+///     Unranked,
+/// }
+///
+/// let mut set = Set::new();
+/// set.insert(Key::Score(30));
+/// set.insert(Key::Unranked);
+/// set.insert(Key::Score(10));
+///
+/// assert!(set.iter().eq([Key::Score(10), Key::Score(30), Key::Unranked]));
+/// ```
+#[repr(transparent)]
+pub struct BTreeSetStorage<T> {
+    inner: BTreeSet<T>,
+}
+
+impl<T> Clone for BTreeSetStorage<T>
+where
+    T: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        BTreeSetStorage {
+            inner: self.inner.clone(),
+        }
+    }
+
+    #[inline]
+    fn clone_from(&mut self, source: &Self) {
+        self.inner.clone_from(&source.inner);
+    }
+}
+
+impl<T> PartialEq for BTreeSetStorage<T>
+where
+    T: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.inner.eq(&other.inner)
+    }
+}
+
+impl<T> Eq for BTreeSetStorage<T> where T: Eq {}
+
+impl<T> SetStorage<T> for BTreeSetStorage<T>
+where
+    T: Clone + Ord,
+{
+    type Iter<'this>
+        = iter::Cloned<btree_set::Iter<'this, T>>
+    where
+        T: 'this;
+    type IntoIter = btree_set::IntoIter<T>;
+
+    #[inline]
+    fn empty() -> Self {
+        Self {
+            inner: BTreeSet::new(),
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    #[inline]
+    fn insert(&mut self, value: T) -> bool {
+        self.inner.insert(value)
+    }
+
+    #[inline]
+    fn contains(&self, value: T) -> bool {
+        self.inner.contains(&value)
+    }
+
+    #[inline]
+    fn remove(&mut self, value: T) -> bool {
+        self.inner.remove(&value)
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, mut func: F)
+    where
+        F: FnMut(T) -> bool,
+    {
+        self.inner.retain(|value| func(value.clone()));
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.inner.clear();
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        self.inner.iter().cloned()
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.inner.into_iter()
+    }
+}
@@ -0,0 +1,185 @@
+// Iterators are confusing if they impl `Copy`.
+#![allow(missing_copy_implementations)]
+
+use core::iter::FusedIterator;
+
+use crate::set::SetStorage;
+
+const WORDS: usize = 4;
+
+/// [`SetStorage`] for [`u8`] types.
+///
+/// This is stored as a 256-bit bitmask, with one bit for each possible byte.
+/// Iteration happens in ascending byte order.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::Set;
+///
+/// let mut set = Set::new();
+/// set.insert(b'z');
+/// set.insert(b'0');
+/// set.insert(b' ');
+///
+/// assert!(set.contains(b'0'));
+/// assert!(!set.contains(b'1'));
+/// assert_eq!(set.len(), 3);
+///
+/// assert!(set.iter().eq([b' ', b'0', b'z']));
+/// assert!(set.iter().rev().eq([b'z', b'0', b' ']));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct ByteSetStorage {
+    bits: [u64; WORDS],
+}
+
+/// See [`ByteSetStorage::iter`].
+pub struct Iter {
+    bits: [u64; WORDS],
+}
+
+impl Clone for Iter {
+    #[inline]
+    fn clone(&self) -> Iter {
+        Iter { bits: self.bits }
+    }
+}
+
+impl Iterator for Iter {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        for (index, word) in self.bits.iter_mut().enumerate() {
+            if *word != 0 {
+                let bit = word.trailing_zeros();
+                *word &= !(1 << bit);
+                return Some(to_value(index, bit));
+            }
+        }
+
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = count(&self.bits);
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Iter {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        for (index, word) in self.bits.iter_mut().enumerate().rev() {
+            if *word != 0 {
+                let bit = u64::BITS - 1 - word.leading_zeros();
+                *word &= !(1 << bit);
+                return Some(to_value(index, bit));
+            }
+        }
+
+        None
+    }
+}
+
+impl ExactSizeIterator for Iter {
+    #[inline]
+    fn len(&self) -> usize {
+        count(&self.bits)
+    }
+}
+
+impl FusedIterator for Iter {}
+
+impl SetStorage<u8> for ByteSetStorage {
+    type Iter<'this> = Iter;
+    type IntoIter = Iter;
+
+    #[inline]
+    fn empty() -> Self {
+        Self { bits: [0; WORDS] }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        count(&self.bits)
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.bits == [0; WORDS]
+    }
+
+    #[inline]
+    fn insert(&mut self, value: u8) -> bool {
+        let (word, mask) = to_bits(value);
+        let word = &mut self.bits[word];
+        let inserted = *word & mask == 0;
+        *word |= mask;
+        inserted
+    }
+
+    #[inline]
+    fn contains(&self, value: u8) -> bool {
+        let (word, mask) = to_bits(value);
+        self.bits[word] & mask != 0
+    }
+
+    #[inline]
+    fn remove(&mut self, value: u8) -> bool {
+        let (word, mask) = to_bits(value);
+        let word = &mut self.bits[word];
+        let removed = *word & mask != 0;
+        *word &= !mask;
+        removed
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(u8) -> bool,
+    {
+        for value in self.iter() {
+            if !f(value) {
+                self.remove(value);
+            }
+        }
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.bits = [0; WORDS];
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        Iter { bits: self.bits }
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        Iter { bits: self.bits }
+    }
+}
+
+#[inline]
+fn count(bits: &[u64; WORDS]) -> usize {
+    bits.iter().map(|word| word.count_ones() as usize).sum()
+}
+
+/// Get the word index and the mask of the bit for the given value.
+#[inline]
+const fn to_bits(value: u8) -> (usize, u64) {
+    ((value >> 6) as usize, 1 << (value & 0b11_1111))
+}
+
+/// Get the value corresponding to a bit in a word.
+#[inline]
+#[allow(clippy::cast_possible_truncation)]
+const fn to_value(index: usize, bit: u32) -> u8 {
+    // `index` is less than `WORDS` and `bit` is less than 64, so this fits in
+    // a byte.
+    ((index << 6) as u8) | (bit as u8)
+}
@@ -1,6 +1,11 @@
 use core::hash::Hash;
 use core::iter;
 
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+#[cfg(feature = "rayon")]
+use crate::set::ParSetStorage;
 use crate::set::SetStorage;
 
 /// [`SetStorage`] for dynamically stored types, using [`hashbrown::HashSet`].
@@ -40,6 +45,11 @@ where
             inner: self.inner.clone(),
         }
     }
+
+    #[inline]
+    fn clone_from(&mut self, source: &Self) {
+        self.inner.clone_from(&source.inner);
+    }
 }
 
 impl<T> PartialEq for HashbrownSetStorage<T>
@@ -56,9 +66,12 @@ impl<T> Eq for HashbrownSetStorage<T> where T: Eq + Hash {}
 
 impl<T> SetStorage<T> for HashbrownSetStorage<T>
 where
-    T: Copy + Eq + Hash,
+    T: Clone + Eq + Hash,
 {
-    type Iter<'this> = iter::Copied<::hashbrown::hash_set::Iter<'this, T>> where T: 'this;
+    type Iter<'this>
+        = iter::Cloned<::hashbrown::hash_set::Iter<'this, T>>
+    where
+        T: 'this;
     type IntoIter = ::hashbrown::hash_set::IntoIter<T>;
 
     #[inline]
@@ -98,7 +111,7 @@ where
     where
         F: FnMut(T) -> bool,
     {
-        self.inner.retain(|&value| func(value));
+        self.inner.retain(|value| func(value.clone()));
     }
 
     #[inline]
@@ -108,7 +121,7 @@ where
 
     #[inline]
     fn iter(&self) -> Self::Iter<'_> {
-        self.inner.iter().copied()
+        self.inner.iter().cloned()
     }
 
     #[inline]
@@ -116,3 +129,19 @@ where
         self.inner.into_iter()
     }
 }
+
+#[cfg(feature = "rayon")]
+impl<T> ParSetStorage<T> for HashbrownSetStorage<T>
+where
+    T: Clone + Eq + Hash + Send + Sync,
+{
+    type ParIter<'this>
+        = rayon::iter::Cloned<::hashbrown::hash_set::rayon::ParIter<'this, T>>
+    where
+        Self: 'this;
+
+    #[inline]
+    fn par_iter(&self) -> Self::ParIter<'_> {
+        self.inner.par_iter().cloned()
+    }
+}
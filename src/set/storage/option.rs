@@ -1,19 +1,44 @@
-use core::iter;
 use core::mem;
 use core::option;
 
+#[cfg(feature = "rayon")]
+use rayon::iter::ParallelIterator;
+
+use crate::iter::{Halves, MapHalves};
 use crate::key::Key;
+#[cfg(feature = "rayon")]
+use crate::set::ParSetStorage;
 use crate::set::SetStorage;
 
-type Iter<'a, T> = iter::Chain<
-    iter::Map<<<T as Key>::SetStorage as SetStorage<T>>::Iter<'a>, fn(T) -> Option<T>>,
-    option::IntoIter<Option<T>>,
->;
-type IntoIter<T> = iter::Chain<
-    iter::Map<<<T as Key>::SetStorage as SetStorage<T>>::IntoIter, fn(T) -> Option<T>>,
-    option::IntoIter<Option<T>>,
+type Iter<'a, T> =
+    Halves<<<T as Key>::SetStorage as SetStorage<T>>::Iter<'a>, option::IntoIter<()>, ValueHalves>;
+type IntoIter<T> =
+    Halves<<<T as Key>::SetStorage as SetStorage<T>>::IntoIter, option::IntoIter<()>, ValueHalves>;
+#[cfg(feature = "rayon")]
+type ParIter<'a, T> = rayon::iter::Chain<
+    rayon::iter::Map<<<T as Key>::SetStorage as ParSetStorage<T>>::ParIter<'a>, fn(T) -> Option<T>>,
+    rayon::option::IntoIter<Option<T>>,
 >;
 
+/// Maps values of the `Some` storage and the presence of `None` into values
+/// of the option storage.
+#[derive(Clone, Copy)]
+pub enum ValueHalves {}
+
+impl<T> MapHalves<T, ()> for ValueHalves {
+    type Item = Option<T>;
+
+    #[inline]
+    fn first(value: T) -> Self::Item {
+        Some(value)
+    }
+
+    #[inline]
+    fn second((): ()) -> Self::Item {
+        None
+    }
+}
+
 /// [`SetStorage`] for [`Option`] types.
 ///
 /// # Examples
@@ -66,6 +91,12 @@ where
             none: self.none,
         }
     }
+
+    #[inline]
+    fn clone_from(&mut self, source: &Self) {
+        self.some.clone_from(&source.some);
+        self.none = source.none;
+    }
 }
 
 impl<T> Copy for OptionSetStorage<T>
@@ -93,11 +124,32 @@ where
 {
 }
 
+impl<T> OptionSetStorage<T>
+where
+    T: Key,
+{
+    /// Check in debug builds that the reported length of the storage agrees
+    /// with the number of values it actually holds.
+    ///
+    /// This is called after every modification of the storage.
+    #[inline]
+    fn debug_assert_len(&self) {
+        debug_assert_eq!(
+            self.len(),
+            self.iter().count(),
+            "length of option storage disagrees with its values"
+        );
+    }
+}
+
 impl<T> SetStorage<Option<T>> for OptionSetStorage<T>
 where
     T: Key,
 {
-    type Iter<'this> = Iter<'this, T> where T: 'this;
+    type Iter<'this>
+        = Iter<'this, T>
+    where
+        T: 'this;
     type IntoIter = IntoIter<T>;
 
     #[inline]
@@ -115,15 +167,18 @@ where
 
     #[inline]
     fn is_empty(&self) -> bool {
-        self.some.is_empty() && self.none
+        self.some.is_empty() && !self.none
     }
 
     #[inline]
     fn insert(&mut self, value: Option<T>) -> bool {
-        match value {
+        let inserted = match value {
             Some(value) => self.some.insert(value),
             None => mem::replace(&mut self.none, true),
-        }
+        };
+
+        self.debug_assert_len();
+        inserted
     }
 
     #[inline]
@@ -136,10 +191,13 @@ where
 
     #[inline]
     fn remove(&mut self, key: Option<T>) -> bool {
-        match key {
+        let removed = match key {
             Some(key) => self.some.remove(key),
             None => mem::replace(&mut self.none, false),
-        }
+        };
+
+        self.debug_assert_len();
+        removed
     }
 
     #[inline]
@@ -152,28 +210,44 @@ where
         if self.none {
             self.none = func(None);
         }
+
+        self.debug_assert_len();
     }
 
     #[inline]
     fn clear(&mut self) {
         self.some.clear();
         self.none = false;
+        self.debug_assert_len();
     }
 
     #[inline]
     fn iter(&self) -> Self::Iter<'_> {
-        let map: fn(_) -> _ = Some;
-        self.some
-            .iter()
-            .map(map)
-            .chain(self.none.then_some(None::<T>))
+        Halves::new(self.some.iter(), self.none.then_some(()).into_iter())
     }
 
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
+        Halves::new(self.some.into_iter(), self.none.then_some(()).into_iter())
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T> ParSetStorage<Option<T>> for OptionSetStorage<T>
+where
+    T: Key + Send,
+    T::SetStorage: ParSetStorage<T>,
+{
+    type ParIter<'this>
+        = ParIter<'this, T>
+    where
+        T: 'this;
+
+    #[inline]
+    fn par_iter(&self) -> Self::ParIter<'_> {
         let map: fn(_) -> _ = Some;
         self.some
-            .into_iter()
+            .par_iter()
             .map(map)
             .chain(self.none.then_some(None::<T>))
     }
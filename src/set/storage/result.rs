@@ -0,0 +1,246 @@
+#[cfg(feature = "rayon")]
+use rayon::iter::ParallelIterator;
+
+use crate::iter::{Halves, MapHalves};
+use crate::key::Key;
+#[cfg(feature = "rayon")]
+use crate::set::ParSetStorage;
+use crate::set::SetStorage;
+
+type Iter<'a, T, E> = Halves<
+    <<T as Key>::SetStorage as SetStorage<T>>::Iter<'a>,
+    <<E as Key>::SetStorage as SetStorage<E>>::Iter<'a>,
+    ValueHalves,
+>;
+type IntoIter<T, E> = Halves<
+    <<T as Key>::SetStorage as SetStorage<T>>::IntoIter,
+    <<E as Key>::SetStorage as SetStorage<E>>::IntoIter,
+    ValueHalves,
+>;
+#[cfg(feature = "rayon")]
+type ParIter<'a, T, E> = rayon::iter::Chain<
+    rayon::iter::Map<
+        <<T as Key>::SetStorage as ParSetStorage<T>>::ParIter<'a>,
+        fn(T) -> Result<T, E>,
+    >,
+    rayon::iter::Map<
+        <<E as Key>::SetStorage as ParSetStorage<E>>::ParIter<'a>,
+        fn(E) -> Result<T, E>,
+    >,
+>;
+
+/// Maps values of the `Ok` and `Err` storage into values of the result
+/// storage.
+#[derive(Clone, Copy)]
+pub enum ValueHalves {}
+
+impl<T, E> MapHalves<T, E> for ValueHalves {
+    type Item = Result<T, E>;
+
+    #[inline]
+    fn first(value: T) -> Self::Item {
+        Ok(value)
+    }
+
+    #[inline]
+    fn second(value: E) -> Self::Item {
+        Err(value)
+    }
+}
+
+/// [`SetStorage`] for [`Result`] types.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Set};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Success {
+///     Created,
+///     Updated,
+/// }
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum Failure {
+///     NotFound,
+///     Conflict,
+/// }
+///
+/// let mut a = Set::new();
+/// a.insert(Err(Failure::Conflict));
+/// a.insert(Ok(Success::Updated));
+///
+/// assert!(a.contains(Ok(Success::Updated)));
+/// assert!(!a.contains(Ok(Success::Created)));
+/// assert!(a.contains(Err(Failure::Conflict)));
+/// assert!(!a.contains(Err(Failure::NotFound)));
+///
+/// assert!(a.iter().eq([Ok(Success::Updated), Err(Failure::Conflict)]));
+/// ```
+pub struct ResultSetStorage<T, E>
+where
+    T: Key,
+    E: Key,
+{
+    ok: T::SetStorage,
+    err: E::SetStorage,
+}
+
+impl<T, E> Clone for ResultSetStorage<T, E>
+where
+    T: Key,
+    E: Key,
+    T::SetStorage: Clone,
+    E::SetStorage: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            ok: self.ok.clone(),
+            err: self.err.clone(),
+        }
+    }
+
+    #[inline]
+    fn clone_from(&mut self, source: &Self) {
+        self.ok.clone_from(&source.ok);
+        self.err.clone_from(&source.err);
+    }
+}
+
+impl<T, E> Copy for ResultSetStorage<T, E>
+where
+    T: Key,
+    E: Key,
+    T::SetStorage: Copy,
+    E::SetStorage: Copy,
+{
+}
+
+impl<T, E> PartialEq for ResultSetStorage<T, E>
+where
+    T: Key,
+    E: Key,
+    T::SetStorage: PartialEq,
+    E::SetStorage: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.ok == other.ok && self.err == other.err
+    }
+}
+
+impl<T, E> Eq for ResultSetStorage<T, E>
+where
+    T: Key,
+    E: Key,
+    T::SetStorage: Eq,
+    E::SetStorage: Eq,
+{
+}
+
+impl<T, E> SetStorage<Result<T, E>> for ResultSetStorage<T, E>
+where
+    T: Key,
+    E: Key,
+{
+    type Iter<'this>
+        = Iter<'this, T, E>
+    where
+        T: 'this,
+        E: 'this;
+    type IntoIter = IntoIter<T, E>;
+
+    #[inline]
+    fn empty() -> Self {
+        Self {
+            ok: T::SetStorage::empty(),
+            err: E::SetStorage::empty(),
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.ok.len() + self.err.len()
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.ok.is_empty() && self.err.is_empty()
+    }
+
+    #[inline]
+    fn insert(&mut self, value: Result<T, E>) -> bool {
+        match value {
+            Ok(value) => self.ok.insert(value),
+            Err(value) => self.err.insert(value),
+        }
+    }
+
+    #[inline]
+    fn contains(&self, value: Result<T, E>) -> bool {
+        match value {
+            Ok(value) => self.ok.contains(value),
+            Err(value) => self.err.contains(value),
+        }
+    }
+
+    #[inline]
+    fn remove(&mut self, value: Result<T, E>) -> bool {
+        match value {
+            Ok(value) => self.ok.remove(value),
+            Err(value) => self.err.remove(value),
+        }
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, mut func: F)
+    where
+        F: FnMut(Result<T, E>) -> bool,
+    {
+        self.ok.retain(|value| func(Ok(value)));
+        self.err.retain(|value| func(Err(value)));
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.ok.clear();
+        self.err.clear();
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        Halves::new(self.ok.iter(), self.err.iter())
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        Halves::new(self.ok.into_iter(), self.err.into_iter())
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T, E> ParSetStorage<Result<T, E>> for ResultSetStorage<T, E>
+where
+    T: Key + Send,
+    E: Key + Send,
+    T::SetStorage: ParSetStorage<T>,
+    E::SetStorage: ParSetStorage<E>,
+{
+    type ParIter<'this>
+        = ParIter<'this, T, E>
+    where
+        T: 'this,
+        E: 'this;
+
+    #[inline]
+    fn par_iter(&self) -> Self::ParIter<'_> {
+        let ok: fn(_) -> _ = Ok;
+        let err: fn(_) -> _ = Err;
+        self.ok
+            .par_iter()
+            .map(ok)
+            .chain(self.err.par_iter().map(err))
+    }
+}
@@ -0,0 +1,228 @@
+use core::marker::PhantomData;
+
+use crate::iter::{Nested, SplitNested};
+use crate::key::Key;
+use crate::map::MapStorage;
+use crate::set::SetStorage;
+
+/// Storage for the second element of a tuple key.
+type Inner<B> = <B as Key>::SetStorage;
+/// Storage for the first element of a tuple key, holding the storage for the
+/// second element.
+type Outer<A, B> = <A as Key>::MapStorage<Inner<B>>;
+
+type Iter<'a, A, B> = Nested<<Outer<A, B> as MapStorage<A, Inner<B>>>::Iter<'a>, SplitIter<B>>;
+type IntoIter<A, B> = Nested<<Outer<A, B> as MapStorage<A, Inner<B>>>::IntoIter, SplitIntoIter<B>>;
+
+/// Splits entries of the outer storage for [`TupleSetStorage::iter`].
+pub struct SplitIter<B>(PhantomData<fn() -> B>);
+
+impl<'a, A, B> SplitNested<(A, &'a Inner<B>)> for SplitIter<B>
+where
+    A: Clone,
+    B: Key,
+{
+    type Key = A;
+    type Iter = <Inner<B> as SetStorage<B>>::Iter<'a>;
+    type Item = (A, B);
+
+    #[inline]
+    fn split((a, inner): (A, &'a Inner<B>)) -> (A, Self::Iter) {
+        (a, inner.iter())
+    }
+
+    #[inline]
+    fn join(a: A, b: B) -> Self::Item {
+        (a, b)
+    }
+}
+
+/// Splits entries of the outer storage for [`TupleSetStorage::into_iter`].
+pub struct SplitIntoIter<B>(PhantomData<fn() -> B>);
+
+impl<A, B> SplitNested<(A, Inner<B>)> for SplitIntoIter<B>
+where
+    A: Clone,
+    B: Key,
+{
+    type Key = A;
+    type Iter = <Inner<B> as SetStorage<B>>::IntoIter;
+    type Item = (A, B);
+
+    #[inline]
+    fn split((a, inner): (A, Inner<B>)) -> (A, Self::Iter) {
+        (a, inner.into_iter())
+    }
+
+    #[inline]
+    fn join(a: A, b: B) -> Self::Item {
+        (a, b)
+    }
+}
+
+/// [`SetStorage`] for tuples of two keys.
+///
+/// The storage for the second element of the key is nested inside of the
+/// storage for the first element, so values are iterated over in
+/// lexicographic order: first by the first element, then by the second.
+///
+/// # Examples
+///
+/// ```
+/// use fixed_map::{Key, Set};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Key)]
+/// enum State {
+///     Idle,
+///     Running,
+/// }
+///
+/// let mut seen = Set::new();
+/// seen.insert((State::Running, true));
+/// seen.insert((State::Idle, false));
+/// seen.insert((State::Running, false));
+///
+/// assert!(seen.contains((State::Idle, false)));
+/// assert!(!seen.contains((State::Idle, true)));
+///
+/// assert!(seen.iter().eq([(State::Idle, false), (State::Running, false), (State::Running, true)]));
+/// ```
+pub struct TupleSetStorage<A, B>
+where
+    A: Key,
+    B: Key,
+{
+    data: Outer<A, B>,
+}
+
+impl<A, B> Clone for TupleSetStorage<A, B>
+where
+    A: Key,
+    B: Key,
+    Outer<A, B>: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+        }
+    }
+
+    #[inline]
+    fn clone_from(&mut self, source: &Self) {
+        self.data.clone_from(&source.data);
+    }
+}
+
+impl<A, B> Copy for TupleSetStorage<A, B>
+where
+    A: Key,
+    B: Key,
+    Outer<A, B>: Copy,
+{
+}
+
+/// Storage is compared by its values, since the storage for the first element
+/// of the key may hold empty storage for the second element.
+impl<A, B> PartialEq for TupleSetStorage<A, B>
+where
+    A: Key,
+    B: Key,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|value| other.contains(value))
+    }
+}
+
+impl<A, B> Eq for TupleSetStorage<A, B>
+where
+    A: Key,
+    B: Key,
+{
+}
+
+impl<A, B> SetStorage<(A, B)> for TupleSetStorage<A, B>
+where
+    A: Key,
+    B: Key,
+{
+    type Iter<'this>
+        = Iter<'this, A, B>
+    where
+        A: 'this,
+        B: 'this;
+    type IntoIter = IntoIter<A, B>;
+
+    #[inline]
+    fn empty() -> Self {
+        Self {
+            data: Outer::<A, B>::empty(),
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.data.values().map(SetStorage::len).sum()
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.data.values().all(SetStorage::is_empty)
+    }
+
+    #[inline]
+    fn insert(&mut self, (a, b): (A, B)) -> bool {
+        self.data
+            .get_or_insert_with_key(a, |_| Inner::<B>::empty())
+            .insert(b)
+    }
+
+    #[inline]
+    fn contains(&self, (a, b): (A, B)) -> bool {
+        self.data.get(a).map_or(false, |inner| inner.contains(b))
+    }
+
+    #[inline]
+    fn remove(&mut self, (a, b): (A, B)) -> bool {
+        let Some(inner) = self.data.get_mut(a.clone()) else {
+            return false;
+        };
+
+        let removed = inner.remove(b);
+
+        if inner.is_empty() {
+            self.data.remove(a);
+        }
+
+        removed
+    }
+
+    #[inline]
+    fn retain<F>(&mut self, mut func: F)
+    where
+        F: FnMut((A, B)) -> bool,
+    {
+        self.data.retain(|a, inner| {
+            inner.retain(|b| func((a.clone(), b)));
+            !inner.is_empty()
+        });
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    #[inline]
+    fn iter(&self) -> Self::Iter<'_> {
+        Nested::new(self.data.iter(), self.len())
+    }
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        let len = self.len();
+
+        Nested::new(self.data.into_iter(), len)
+    }
+}
@@ -0,0 +1,57 @@
+use fixed_map::{Key, Map, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Key {
+    Simple,
+    Flag(bool),
+    Option(Option<bool>),
+}
+
+#[test]
+fn composite_aggregates() {
+    let mut map = Map::new();
+    map.insert(Key::Flag(true), 7u32);
+    map.insert(Key::Option(None), 2);
+    map.insert(Key::Simple, 5);
+    map.insert(Key::Option(Some(false)), 9);
+
+    assert_eq!(map.sum_values::<u32>(), 23);
+    assert_eq!(map.max_value(), Some((Key::Option(Some(false)), &9)));
+    assert_eq!(map.min_value(), Some((Key::Option(None), &2)));
+}
+
+#[test]
+fn increment_composite() {
+    let mut map = Map::new();
+
+    for key in [
+        Key::Flag(true),
+        Key::Option(None),
+        Key::Flag(true),
+        Key::Simple,
+        Key::Flag(true),
+    ] {
+        map.increment(key, 1u8);
+    }
+
+    assert_eq!(map.get(Key::Flag(true)), Some(&3));
+    assert_eq!(map.get(Key::Flag(false)), None);
+    assert_eq!(map.sum_values::<u8>(), 5);
+}
+
+#[test]
+fn count_matching() {
+    let mut map = Map::new();
+    map.insert(Key::Flag(true), 7u32);
+    map.insert(Key::Option(None), 2);
+    map.insert(Key::Option(Some(true)), 4);
+
+    assert_eq!(map.count(|_, &value| value % 2 == 0), 2);
+    assert_eq!(map.count(|key, _| matches!(key, Key::Option(_))), 2);
+    assert_eq!(map.count(|_, _| false), 0);
+    assert_eq!(map.count(|_, _| true), map.len());
+
+    let set = map.keys().collect::<Set<_>>();
+    assert_eq!(set.count(|key| key != Key::Option(None)), 2);
+    assert_eq!(Set::<Key>::new().count(|_| true), 0);
+}
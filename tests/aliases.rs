@@ -0,0 +1,42 @@
+use fixed_map::Key;
+
+mod perms {
+    use fixed_map::Key;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    #[key(aliases)]
+    pub enum Perm {
+        Read,
+        Write,
+        Execute,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+#[key(aliases)]
+enum Route {
+    Flag(bool),
+    Fallback,
+}
+
+#[test]
+fn aliases() {
+    use self::perms::{Perm, PermMap, PermSet};
+
+    let mut set = PermSet::new();
+    set.insert(Perm::Read);
+    set.insert(Perm::Execute);
+    assert!(set.iter().eq([Perm::Read, Perm::Execute]));
+
+    let mut map: PermMap<u32> = PermMap::new();
+    map.insert(Perm::Write, 2);
+    assert_eq!(map.get(Perm::Write), Some(&2));
+
+    let mut routes: RouteMap<&str> = RouteMap::new();
+    routes.insert(Route::Flag(true), "on");
+    routes.insert(Route::Fallback, "fallback");
+    assert_eq!(routes.len(), 2);
+
+    let routes: RouteSet = routes.keys().collect();
+    assert!(routes.contains(Route::Flag(true)));
+}
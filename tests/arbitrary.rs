@@ -0,0 +1,42 @@
+#![cfg(feature = "arbitrary")]
+
+use arbitrary::{Arbitrary, Unstructured};
+use fixed_map::{Key, Map, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+enum Part {
+    One,
+    Two,
+    Three,
+}
+
+impl<'a> Arbitrary<'a> for Part {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(*u.choose(&[Part::One, Part::Two, Part::Three])?)
+    }
+}
+
+const DATA: &[u8] = &[
+    1, 0, 7, 1, 2, 9, 1, 1, 3, 1, 2, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+];
+
+#[test]
+fn map() {
+    let mut u = Unstructured::new(DATA);
+    let map = Map::<Part, u8>::arbitrary(&mut u).unwrap();
+    assert!(map.len() <= 3);
+
+    let map = Map::<Part, u8>::arbitrary_take_rest(Unstructured::new(DATA)).unwrap();
+    assert!(map.len() <= 3);
+    assert_eq!(map.len(), map.iter().count());
+}
+
+#[test]
+fn set() {
+    let mut u = Unstructured::new(DATA);
+    let set = Set::<Option<u8>>::arbitrary(&mut u).unwrap();
+    assert_eq!(set.len(), set.iter().count());
+
+    let set = Set::<bool>::arbitrary_take_rest(Unstructured::new(DATA)).unwrap();
+    assert!(set.len() <= 2);
+}
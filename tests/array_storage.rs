@@ -0,0 +1,102 @@
+use core::iter::Copied;
+use core::slice;
+
+use fixed_map::key::{FixedKey, Key};
+use fixed_map::map::storage::ArrayMapStorage;
+use fixed_map::map::Entry;
+use fixed_map::set::storage::BitsetSetStorage;
+use fixed_map::{Map, Set};
+
+/// A hand-written key for the even numbers `0, 2, .., 14`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Even(u8);
+
+static EVENS: [Even; 8] = [
+    Even(0),
+    Even(2),
+    Even(4),
+    Even(6),
+    Even(8),
+    Even(10),
+    Even(12),
+    Even(14),
+];
+
+impl Key for Even {
+    type MapStorage<V> = ArrayMapStorage<V, 8>;
+    type SetStorage = BitsetSetStorage<1>;
+}
+
+impl FixedKey for Even {
+    const COUNT: usize = 8;
+
+    fn keys() -> Copied<slice::Iter<'static, Self>> {
+        EVENS.iter().copied()
+    }
+
+    fn index(self) -> usize {
+        usize::from(self.0 / 2)
+    }
+
+    fn from_index(index: usize) -> Option<Self> {
+        EVENS.get(index).copied()
+    }
+}
+
+#[test]
+fn basic_operations() {
+    let mut map = Map::new();
+    assert_eq!(map.insert(Even(4), "four"), None);
+    assert_eq!(map.insert(Even(14), "fourteen"), None);
+    assert_eq!(map.insert(Even(0), "zero"), None);
+    assert_eq!(map.insert(Even(4), "FOUR"), Some("four"));
+
+    assert_eq!(map.len(), 3);
+    assert_eq!(map.get(Even(4)), Some(&"FOUR"));
+    assert!(!map.contains_key(Even(2)));
+
+    assert!(map.keys().eq([Even(0), Even(4), Even(14)]));
+    assert!(map.values().rev().eq([&"fourteen", &"FOUR", &"zero"]));
+    assert_eq!(map.iter().len(), 3);
+
+    assert_eq!(map.remove(Even(0)), Some("zero"));
+    map.retain(|key, _| key != Even(14));
+
+    assert!(map.into_iter().eq([(Even(4), "FOUR")]));
+}
+
+#[test]
+fn entry_and_const() {
+    const EMPTY: Map<Even, u32> = Map::EMPTY;
+
+    let mut map = EMPTY;
+    *map.entry(Even(6)).or_default() += 2;
+    *map.entry(Even(6)).or_default() += 3;
+
+    match map.entry(Even(8)) {
+        Entry::Vacant(..) => {}
+        Entry::Occupied(..) => panic!("expected vacant entry"),
+    }
+
+    assert_eq!(map.get(Even(6)), Some(&5));
+    assert_eq!(Map::<Even, u32>::default_filled().len(), Even::COUNT);
+}
+
+#[test]
+fn ordering_follows_index() {
+    let a = Map::from_array([(Even(2), 1)]);
+    let b = Map::from_array([(Even(10), 1)]);
+    let c = Map::from_array([(Even(2), 1), (Even(10), 1)]);
+
+    assert!(a < b);
+    assert!(c < b);
+    assert!(a < c);
+}
+
+#[test]
+fn set_storage() {
+    let set = Set::from_array([Even(12), Even(2)]);
+
+    assert!(set.iter().eq([Even(2), Even(12)]));
+    assert_eq!(set.complement().len(), 6);
+}
@@ -0,0 +1,44 @@
+use fixed_map::{Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum State {
+    Idle,
+    Busy(bool),
+    Done(Option<bool>),
+}
+
+fn total_len<K>(map: &Map<K, &String>) -> usize
+where
+    K: fixed_map::key::Key,
+{
+    map.values().map(|value| value.len()).sum()
+}
+
+#[test]
+fn as_ref_map() {
+    let mut map = Map::new();
+    map.insert(State::Done(None), String::from("done"));
+    map.insert(State::Busy(true), String::from("busy"));
+    map.insert(State::Idle, String::from("idle!"));
+
+    let view = map.as_ref_map();
+    assert_eq!(view.len(), 3);
+    assert!(view.keys().eq(map.keys()));
+    assert!(std::ptr::eq(
+        *view.get(State::Busy(true)).unwrap(),
+        map.get(State::Busy(true)).unwrap()
+    ));
+    assert_eq!(total_len(&view), 13);
+}
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn as_ref_map_hashbrown() {
+    let mut map = Map::new();
+    map.insert(7u32, String::from("seven"));
+    map.insert(42u32, String::from("forty-two"));
+
+    let view = map.as_ref_map();
+    assert_eq!(view.get(42).map(|value| value.as_str()), Some("forty-two"));
+    assert_eq!(total_len(&view), 14);
+}
@@ -0,0 +1,152 @@
+use fixed_map::key::FixedKey;
+use fixed_map::{Key, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Wide {
+    V0,
+    V1,
+    V2,
+    V3,
+    V4,
+    V5,
+    V6,
+    V7,
+    V8,
+    V9,
+    V10,
+    V11,
+    V12,
+    V13,
+    V14,
+    V15,
+    V16,
+    V17,
+    V18,
+    V19,
+    V20,
+    V21,
+    V22,
+    V23,
+    V24,
+    V25,
+    V26,
+    V27,
+    V28,
+    V29,
+    V30,
+    V31,
+    V32,
+    V33,
+    V34,
+    V35,
+    V36,
+    V37,
+    V38,
+    V39,
+    V40,
+    V41,
+    V42,
+    V43,
+    V44,
+    V45,
+    V46,
+    V47,
+    V48,
+    V49,
+    V50,
+    V51,
+    V52,
+    V53,
+    V54,
+    V55,
+    V56,
+    V57,
+    V58,
+    V59,
+    V60,
+    V61,
+    V62,
+    V63,
+    V64,
+    V65,
+    V66,
+    V67,
+    V68,
+    V69,
+}
+
+#[test]
+fn size_spans_words() {
+    assert_eq!(
+        core::mem::size_of::<Set<Wide>>(),
+        2 * core::mem::size_of::<u64>()
+    );
+}
+
+#[test]
+fn across_word_boundary() {
+    let mut set = Set::new();
+    assert!(set.insert(Wide::V69));
+    assert!(set.insert(Wide::V63));
+    assert!(set.insert(Wide::V64));
+    assert!(set.insert(Wide::V0));
+    assert!(!set.insert(Wide::V64));
+
+    assert_eq!(set.len(), 4);
+    assert!(set.contains(Wide::V63));
+    assert!(!set.contains(Wide::V65));
+
+    assert!(set.iter().eq([Wide::V0, Wide::V63, Wide::V64, Wide::V69]));
+    assert!(set
+        .iter()
+        .rev()
+        .eq([Wide::V69, Wide::V64, Wide::V63, Wide::V0]));
+
+    assert!(set.remove(Wide::V63));
+    assert!(!set.remove(Wide::V63));
+    assert!(set.into_iter().eq([Wide::V0, Wide::V64, Wide::V69]));
+}
+
+#[test]
+fn full_and_retain() {
+    let mut set = Wide::keys().collect::<Set<_>>();
+    assert_eq!(set.len(), Wide::COUNT);
+
+    set.retain(|key| key.index() % 2 == 0);
+    assert_eq!(set.len(), 35);
+    assert!(set.iter().map(Wide::index).eq((0..70).step_by(2)));
+
+    set.clear();
+    assert!(set.is_empty());
+}
+
+#[test]
+fn ordering_follows_declaration_order() {
+    let a = Set::from_array([Wide::V1]);
+    let b = Set::from_array([Wide::V65]);
+    let c = Set::from_array([Wide::V1, Wide::V65]);
+
+    assert!(a < b);
+    assert!(c < b);
+    assert!(a < c);
+}
+
+#[test]
+fn complement_spans_words() {
+    let set = Set::from_array([Wide::V0, Wide::V64, Wide::V69]);
+    let complement = set.complement();
+
+    assert_eq!(complement.len(), Wide::COUNT - 3);
+    assert!(!complement.contains(Wide::V64));
+    assert!(complement.contains(Wide::V65));
+    assert_eq!(complement.complement(), set);
+    assert_eq!(Set::<Wide>::new().complement().len(), Wide::COUNT);
+}
+
+#[test]
+fn full_spans_words() {
+    let set = Set::<Wide>::full();
+    assert_eq!(set.len(), Wide::COUNT);
+    assert!(set.iter().eq(Wide::keys()));
+    assert!(set.complement().is_empty());
+}
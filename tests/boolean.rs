@@ -0,0 +1,31 @@
+use fixed_map::{Map, Set};
+
+#[test]
+fn map_iterates_false_first() {
+    let mut map = Map::new();
+    map.insert(true, 'a');
+    map.insert(false, 'b');
+
+    assert!(map.iter().eq([(false, &'b'), (true, &'a')]));
+    assert!(map.keys().eq([false, true]));
+    assert!(map.values().eq(&['b', 'a']));
+    assert!(map.into_iter().rev().eq([(true, 'a'), (false, 'b')]));
+}
+
+#[test]
+fn set_iterates_false_first() {
+    let mut set = Set::new();
+    set.insert(true);
+    set.insert(false);
+
+    let mut seen = Vec::new();
+
+    set.retain(|value| {
+        seen.push(value);
+        true
+    });
+
+    assert_eq!(seen, [false, true]);
+    assert!(set.iter().eq([false, true]));
+    assert!(set.iter().rev().eq([true, false]));
+}
@@ -0,0 +1,37 @@
+use fixed_map::{Map, Set};
+
+#[test]
+fn map_bounds() {
+    let mut map = Map::new();
+    map.insert(u8::MAX, 2);
+    map.insert(0, 1);
+
+    assert!(map.iter().eq([(0, &1), (u8::MAX, &2)]));
+    assert!(map.iter().rev().eq([(u8::MAX, &2), (0, &1)]));
+
+    *map.entry(128).or_insert(0) += 3;
+    map.retain(|key, _| key != 0);
+
+    assert_eq!(map.len(), 2);
+    assert!(map.into_iter().eq([(128, 3), (u8::MAX, 2)]));
+}
+
+#[test]
+fn set_bounds() {
+    let mut set = Set::new();
+
+    for value in [u8::MAX, 0, 63, 64, 127, 128] {
+        assert!(set.insert(value));
+    }
+
+    assert!(!set.insert(64));
+    assert_eq!(set.len(), 6);
+    assert!(set.iter().eq([0, 63, 64, 127, 128, u8::MAX]));
+    assert!(set.iter().rev().eq([u8::MAX, 128, 127, 64, 63, 0]));
+
+    assert!(set.remove(63));
+    assert!(!set.remove(63));
+    set.retain(|value| value % 2 == 0);
+
+    assert!(set.into_iter().eq([0, 64, 128]));
+}
@@ -0,0 +1,58 @@
+#![cfg(feature = "hashbrown")]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use fixed_map::{Key, Map};
+
+/// Counts allocations made by the current process.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+#[derive(Clone, Copy, Key)]
+enum Key {
+    Frame(u32),
+    Idle,
+}
+
+fn fill(map: &mut Map<Key, u32>) -> usize {
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+
+    for frame in 0..256 {
+        map.insert(Key::Frame(frame), frame);
+    }
+
+    map.insert(Key::Idle, 0);
+    ALLOCATIONS.load(Ordering::SeqCst) - before
+}
+
+// Both behaviors are checked in a single test, since tests running in parallel
+// would share the allocation counter.
+#[test]
+fn clear_keeps_and_clear_and_shrink_releases_capacity() {
+    let mut map = Map::new();
+    assert!(fill(&mut map) > 0);
+
+    map.clear();
+    assert!(map.is_empty());
+    assert_eq!(fill(&mut map), 0);
+
+    map.clear_and_shrink();
+    assert!(map.is_empty());
+    assert!(fill(&mut map) > 0);
+}
@@ -0,0 +1,71 @@
+#[cfg(feature = "hashbrown")]
+use fixed_map::Set;
+use fixed_map::{Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Key)]
+enum Unit {
+    First,
+    Second,
+}
+
+#[cfg(feature = "hashbrown")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Key)]
+enum Composite {
+    Unit(Unit),
+    Bool(bool),
+    Byte(u8),
+    Option(Option<Unit>),
+    Dynamic(u32),
+    Plain,
+}
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn map_clone_from() {
+    let mut source = Map::new();
+    source.insert(Composite::Unit(Unit::Second), vec![1]);
+    source.insert(Composite::Bool(true), vec![2]);
+    source.insert(Composite::Byte(7), vec![3]);
+    source.insert(Composite::Option(None), vec![4]);
+    source.insert(Composite::Dynamic(42), vec![5]);
+    source.insert(Composite::Plain, vec![6]);
+
+    let mut target = Map::new();
+    target.insert(Composite::Unit(Unit::First), vec![10, 20]);
+    target.insert(Composite::Bool(true), vec![30; 16]);
+    target.insert(Composite::Dynamic(1), vec![40]);
+
+    target.clone_from(&source);
+    assert_eq!(target, source);
+
+    target.clone_from(&Map::new());
+    assert!(target.is_empty());
+}
+
+#[test]
+fn unit_map_clone_from() {
+    let mut source = Map::new();
+    source.insert(Unit::Second, String::from("b"));
+
+    let mut target = Map::new();
+    target.insert(Unit::First, String::from("a"));
+    target.insert(Unit::Second, String::from("something long"));
+
+    target.clone_from(&source);
+    assert!(target.iter().eq([(Unit::Second, &String::from("b"))]));
+}
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn set_clone_from() {
+    let mut source = Set::new();
+    source.insert(Composite::Option(Some(Unit::First)));
+    source.insert(Composite::Dynamic(3));
+
+    let mut target = Set::new();
+    target.insert(Composite::Dynamic(4));
+    target.insert(Composite::Plain);
+
+    target.clone_from(&source);
+    assert_eq!(target, source);
+}
@@ -0,0 +1,34 @@
+use fixed_map::{Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Key {
+    Simple,
+    Bool(bool),
+    Byte(u8),
+    Option(Option<Part>),
+    Singleton(()),
+}
+
+static SIMPLE: Map<Part, u32> = Map::EMPTY;
+static COMPOSITE: Map<Key, u32> = Map::EMPTY;
+static BOOL: Map<bool, u32> = Map::EMPTY;
+
+#[test]
+fn const_empty() {
+    assert!(SIMPLE.is_empty());
+    assert!(COMPOSITE.is_empty());
+    assert!(BOOL.is_empty());
+
+    let mut map = COMPOSITE;
+    map.insert(Key::Byte(42), 1);
+    map.insert(Key::Option(Some(Part::Two)), 2);
+    assert!(map
+        .iter()
+        .eq([(Key::Byte(42), &1), (Key::Option(Some(Part::Two)), &2)]));
+}
@@ -0,0 +1,86 @@
+use fixed_map::map::Change;
+use fixed_map::{Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Key {
+    Simple,
+    Option(Option<Part>),
+    Flag(bool),
+}
+
+fn maps() -> (Map<Key, u32>, Map<Key, u32>) {
+    let mut a = Map::new();
+    a.insert(Key::Simple, 1);
+    a.insert(Key::Option(None), 2);
+    a.insert(Key::Flag(true), 3);
+
+    let mut b = a;
+    b.insert(Key::Option(None), 20);
+    b.insert(Key::Option(Some(Part::Two)), 4);
+    b.remove(Key::Flag(true));
+    (a, b)
+}
+
+#[test]
+fn composite_keys() {
+    let (a, b) = maps();
+
+    let diff = a.diff(&b);
+    let changes = diff.iter().collect::<Vec<_>>();
+
+    assert_eq!(diff.len(), 3);
+    assert_eq!(changes.len(), 3);
+    assert!(changes.contains(&Change::Changed(Key::Option(None), &2, &20)));
+    assert!(changes.contains(&Change::Added(Key::Option(Some(Part::Two)), &4)));
+    assert!(changes.contains(&Change::Removed(Key::Flag(true), &3)));
+}
+
+#[test]
+fn equal_and_empty() {
+    let empty = Map::<Key, u32>::new();
+    let mut map = Map::new();
+    map.insert(Key::Simple, 1);
+
+    assert!(empty.diff(&empty).is_empty());
+    assert!(map.diff(&map).is_empty());
+    assert!(empty.diff(&map).iter().eq([Change::Added(Key::Simple, &1)]));
+    assert!(map
+        .diff(&empty)
+        .iter()
+        .map(|change| change.key())
+        .eq([Key::Simple]));
+}
+
+#[test]
+fn apply_round_trip() {
+    let (mut a, b) = maps();
+    let original = a;
+
+    a.apply_diff(&a.diff(&b));
+    assert_eq!(a, b);
+
+    let mut c = b;
+    c.apply_diff(&b.diff(&original));
+    assert_eq!(c, original);
+}
+
+#[test]
+fn apply_is_idempotent() {
+    let (a, b) = maps();
+    let diff = a.diff(&b);
+
+    let mut target = b;
+    target.apply_diff(&diff);
+    assert_eq!(target, b);
+
+    let mut twice = a;
+    twice.apply_diff(&diff);
+    twice.apply_diff(&diff);
+    assert_eq!(twice, b);
+}
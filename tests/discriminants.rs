@@ -0,0 +1,28 @@
+use fixed_map::{Key, Map, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Status {
+    NotFound = 404,
+    Ok = 200,
+    Teapot = 418,
+    Continue = 100,
+}
+
+#[test]
+fn explicit_discriminants() {
+    let mut map = Map::new();
+    map.insert(Status::Continue, 1);
+    map.insert(Status::Ok, 2);
+    map.insert(Status::NotFound, 3);
+
+    assert_eq!(map.get(Status::Continue), Some(&1));
+    assert_eq!(map.get(Status::Ok), Some(&2));
+    assert_eq!(map.get(Status::NotFound), Some(&3));
+    assert_eq!(map.get(Status::Teapot), None);
+    assert!(map
+        .keys()
+        .eq([Status::NotFound, Status::Ok, Status::Continue]));
+
+    let set: Set<_> = [Status::Teapot, Status::Continue].into_iter().collect();
+    assert!(set.iter().eq([Status::Teapot, Status::Continue]));
+}
@@ -0,0 +1,14 @@
+use fixed_map::Map;
+
+#[test]
+fn empty_and_single() {
+    let mut map = Map::<bool, u32>::new();
+    assert_eq!(map.display().to_string(), "");
+
+    map.insert(true, 1);
+    assert_eq!(map.display().separator("; ").to_string(), "true=1");
+
+    map.insert(false, 0);
+    assert_eq!(map.display().to_string(), "false=0, true=1");
+    assert_eq!(map.display().separator("").to_string(), "false=0true=1");
+}
@@ -0,0 +1,96 @@
+use fixed_map::{Key, Map, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Priority {
+    High,
+    Medium,
+    Low,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Composite {
+    Priority(Option<Priority>),
+    Flag(bool),
+    Byte(u8),
+    Unit(()),
+}
+
+#[test]
+fn unit_storage() {
+    let mut map = Map::new();
+    map.insert(Priority::High, 1);
+    map.insert(Priority::Low, 3);
+
+    assert!(map.keys().rev().eq([Priority::Low, Priority::High]));
+    assert!(map.values().rev().eq([&3, &1]));
+    assert!(map
+        .iter()
+        .rev()
+        .eq([(Priority::Low, &3), (Priority::High, &1)]));
+    assert!(map
+        .iter_mut()
+        .rev()
+        .map(|(k, _)| k)
+        .eq([Priority::Low, Priority::High]));
+    assert!(map.values_mut().rev().map(|v| *v).eq([3, 1]));
+    assert!(map
+        .into_iter()
+        .rev()
+        .eq([(Priority::Low, 3), (Priority::High, 1)]));
+
+    let set = [Priority::Medium, Priority::High]
+        .into_iter()
+        .collect::<Set<_>>();
+    assert!(set.iter().rev().eq([Priority::Medium, Priority::High]));
+    assert!(set.into_iter().rev().eq([Priority::Medium, Priority::High]));
+}
+
+#[test]
+fn option_storage() {
+    let mut map = Map::new();
+    map.insert(None, 0);
+    map.insert(Some(Priority::High), 1);
+    map.insert(Some(Priority::Low), 3);
+
+    assert!(map
+        .keys()
+        .rev()
+        .eq([None, Some(Priority::Low), Some(Priority::High)]));
+
+    let mut iter = map.iter();
+    assert_eq!(iter.next_back(), Some((None, &0)));
+    assert_eq!(iter.next(), Some((Some(Priority::High), &1)));
+    assert_eq!(iter.next_back(), Some((Some(Priority::Low), &3)));
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn singleton_storage() {
+    let mut map = Map::new();
+    map.insert((), 1);
+
+    assert!(map.keys().rev().eq([()]));
+    assert!(map.iter_mut().rev().map(|(_, v)| *v).eq([1]));
+}
+
+#[test]
+fn composite_storage() {
+    let mut map = Map::new();
+    map.insert(Composite::Priority(Some(Priority::Medium)), 1);
+    map.insert(Composite::Flag(true), 2);
+    map.insert(Composite::Flag(false), 3);
+    map.insert(Composite::Byte(7), 4);
+    map.insert(Composite::Unit(()), 5);
+
+    assert!(map.values().rev().eq([&5, &4, &2, &3, &1]));
+    assert!(map.keys().rev().eq([
+        Composite::Unit(()),
+        Composite::Byte(7),
+        Composite::Flag(true),
+        Composite::Flag(false),
+        Composite::Priority(Some(Priority::Medium)),
+    ]));
+
+    let set = map.keys().collect::<Set<_>>();
+    assert!(set.iter().rev().eq(map.keys().rev()));
+}
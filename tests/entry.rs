@@ -1,11 +1,42 @@
 use fixed_map::{Key, Map};
 
-#[derive(Clone, Copy, Key)]
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
 enum Part {
     One,
     Two,
 }
 
+/// A key which is only compared and hashed by its `id`.
+#[cfg(feature = "hashbrown")]
+#[derive(Debug, Clone, Copy)]
+struct Tagged {
+    id: u32,
+    tag: u32,
+}
+
+#[cfg(feature = "hashbrown")]
+impl PartialEq for Tagged {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+#[cfg(feature = "hashbrown")]
+impl Eq for Tagged {}
+
+#[cfg(feature = "hashbrown")]
+impl core::hash::Hash for Tagged {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+#[cfg(feature = "hashbrown")]
+impl fixed_map::key::Key for Tagged {
+    type MapStorage<V> = fixed_map::map::storage::HashbrownMapStorage<Tagged, V>;
+    type SetStorage = fixed_map::set::storage::HashbrownSetStorage<Tagged>;
+}
+
 #[test]
 fn simple() {
     let mut map: Map<Part, i32> = Map::new();
@@ -21,6 +52,28 @@ fn simple() {
     );
 }
 
+#[test]
+fn key_before_deciding() {
+    #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    enum Key {
+        Plain,
+        Option(Option<bool>),
+    }
+
+    let mut map: Map<Key, i32> = Map::new();
+    map.insert(Key::Option(None), 1);
+
+    for key in [Key::Plain, Key::Option(None), Key::Option(Some(true))] {
+        let entry = map.entry(key);
+        assert_eq!(entry.key(), key);
+        entry.or_insert(2);
+    }
+
+    assert_eq!(map.get(Key::Plain), Some(&2));
+    assert_eq!(map.get(Key::Option(None)), Some(&1));
+    assert_eq!(map.get(Key::Option(Some(true))), Some(&2));
+}
+
 #[test]
 fn other() {
     use fixed_map::{Key, Map};
@@ -87,3 +140,284 @@ fn compound() {
         &2
     );
 }
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn vacant_into_key() {
+    use fixed_map::map::{Entry, VacantEntry};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    enum Key {
+        Simple,
+        Number(u32),
+        Option(Option<Part>),
+        Flag(bool),
+    }
+
+    let mut map: Map<Key, i32> = Map::new();
+
+    for key in [
+        Key::Simple,
+        Key::Number(7),
+        Key::Option(None),
+        Key::Option(Some(Part::Two)),
+        Key::Flag(true),
+    ] {
+        match map.entry(key) {
+            Entry::Vacant(entry) => assert_eq!(entry.into_key(), key),
+            Entry::Occupied(..) => panic!("expected vacant entry"),
+        }
+    }
+
+    assert!(map.is_empty());
+}
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn replace_key() {
+    use fixed_map::map::{Entry, OccupiedEntry};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    enum Key {
+        Simple,
+        Number(u32),
+        Option(Option<Part>),
+        Flag(bool),
+    }
+
+    let keys = [
+        Key::Simple,
+        Key::Number(7),
+        Key::Option(None),
+        Key::Option(Some(Part::Two)),
+        Key::Flag(true),
+    ];
+
+    let mut map: Map<Key, usize> = keys.iter().copied().zip(0..).collect();
+
+    for key in keys {
+        match map.entry(key) {
+            Entry::Occupied(entry) => assert_eq!(entry.replace_key(key), key),
+            Entry::Vacant(..) => panic!("expected occupied entry"),
+        }
+    }
+
+    for (index, key) in keys.into_iter().enumerate() {
+        assert_eq!(map.get(key), Some(&index));
+    }
+}
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn replace_key_with_uncompared_data() {
+    use fixed_map::map::{Entry, OccupiedEntry};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    enum Outer {
+        Tagged(Tagged),
+        Maybe(Option<Tagged>),
+        Pair((bool, Tagged)),
+    }
+
+    fn tag(key: Outer) -> u32 {
+        match key {
+            Outer::Tagged(tagged) | Outer::Maybe(Some(tagged)) | Outer::Pair((_, tagged)) => {
+                tagged.tag
+            }
+            Outer::Maybe(None) => panic!("expected a tagged key"),
+        }
+    }
+
+    let old = Tagged { id: 1, tag: 10 };
+    let new = Tagged { id: 1, tag: 20 };
+
+    let keys: [fn(Tagged) -> Outer; 3] = [
+        Outer::Tagged,
+        |tagged| Outer::Maybe(Some(tagged)),
+        |tagged| Outer::Pair((true, tagged)),
+    ];
+
+    for key in keys {
+        let mut map = Map::new();
+        map.insert(key(old), "value");
+
+        match map.entry(key(Tagged { id: 1, tag: 0 })) {
+            Entry::Occupied(entry) => assert_eq!(tag(entry.replace_key(key(new))), 10),
+            Entry::Vacant(..) => panic!("expected occupied entry"),
+        }
+
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.get(key(old)), Some(&"value"));
+        assert!(map.keys().map(tag).eq([20]));
+    }
+}
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn get_key_value() {
+    use fixed_map::map::{Entry, OccupiedEntry};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    enum Key {
+        Simple,
+        Number(u32),
+        Option(Option<Part>),
+        Unit(()),
+    }
+
+    let keys = [
+        Key::Simple,
+        Key::Number(7),
+        Key::Option(None),
+        Key::Option(Some(Part::One)),
+        Key::Unit(()),
+    ];
+
+    let mut map: Map<Key, usize> = keys.iter().copied().zip(0..).collect();
+
+    for (index, key) in keys.into_iter().enumerate() {
+        match map.entry(key) {
+            Entry::Occupied(entry) => assert_eq!(entry.get_key_value(), (key, &index)),
+            Entry::Vacant(..) => panic!("expected occupied entry"),
+        }
+    }
+}
+
+#[test]
+fn update_transitions() {
+    #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    enum Key {
+        Plain,
+        Option(Option<Part>),
+    }
+
+    let mut map: Map<Key, u32> = Map::new();
+
+    for key in [Key::Plain, Key::Option(None), Key::Option(Some(Part::Two))] {
+        map.update(key, |value| Some(value.map_or(1, |v| v + 1)));
+        map.update(key, |value| Some(value.map_or(1, |v| v + 1)));
+        assert_eq!(map.get(key), Some(&2));
+
+        map.update(key, |value| value.filter(|&v| v > 2));
+        assert_eq!(map.get(key), None);
+
+        map.update(key, |value| value);
+        assert_eq!(map.get(key), None);
+    }
+
+    assert!(map.is_empty());
+}
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn update_keeps_stored_key() {
+    let mut map = Map::new();
+    map.insert(Tagged { id: 1, tag: 10 }, 1);
+
+    map.update(Tagged { id: 1, tag: 20 }, |value| value.map(|v| v + 1));
+
+    assert_eq!(map.get(Tagged { id: 1, tag: 0 }), Some(&2));
+    assert!(map.keys().map(|key| key.tag).eq([10]));
+}
+
+#[test]
+fn or_default_nested_collections() {
+    #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    enum Outer {
+        Plain,
+        Option(Option<Part>),
+        Flag(bool),
+    }
+
+    let mut map: Map<Outer, Map<Option<Part>, Vec<u32>>> = Map::new();
+
+    for (n, outer) in [Outer::Plain, Outer::Option(None), Outer::Flag(true)]
+        .into_iter()
+        .enumerate()
+    {
+        for inner in [None, Some(Part::One), None] {
+            map.entry(outer)
+                .or_default()
+                .entry(inner)
+                .or_default()
+                .push(n as u32);
+        }
+    }
+
+    assert_eq!(map.len(), 3);
+
+    for (n, inner) in map.values().enumerate() {
+        let n = n as u32;
+        assert_eq!(inner.get(None), Some(&vec![n, n]));
+        assert_eq!(inner.get(Some(Part::One)), Some(&vec![n]));
+        assert_eq!(inner.get(Some(Part::Two)), None);
+    }
+}
+
+#[test]
+fn get_or_insert_variants() {
+    let mut map: Map<Option<Part>, u32> = Map::new();
+
+    assert_eq!(*map.get_or_insert(None, 1), 1);
+    assert_eq!(*map.get_or_insert(None, 2), 1);
+    assert_eq!(*map.get_or_insert_with(Some(Part::Two), || 3), 3);
+    assert_eq!(
+        *map.get_or_insert_with(Some(Part::Two), || unreachable!()),
+        3
+    );
+
+    assert_eq!(map.len(), 2);
+}
+
+#[test]
+fn get_or_insert_with_key() {
+    let mut map: Map<Option<Part>, Option<Part>> = Map::new();
+
+    assert_eq!(
+        *map.get_or_insert_with_key(Some(Part::One), |k| k),
+        Some(Part::One)
+    );
+    assert_eq!(*map.get_or_insert_with_key(None, |k| k), None);
+    assert_eq!(
+        *map.get_or_insert_with_key(Some(Part::One), |_| unreachable!()),
+        Some(Part::One)
+    );
+
+    let mut counts: Map<u8, u32> = Map::new();
+    *counts.get_or_insert_with_key(3, u32::from) += 1;
+    *counts.get_or_insert_with_key(3, u32::from) += 1;
+    assert_eq!(counts.get(3), Some(&5));
+}
+
+#[test]
+fn occupied_vacant_predicates() {
+    let mut map: Map<Option<Part>, u32> = Map::new();
+    map.insert(None, 1);
+    map.insert(Some(Part::Two), 2);
+
+    for (key, occupied) in [
+        (None, true),
+        (Some(Part::One), false),
+        (Some(Part::Two), true),
+    ] {
+        let entry = map.entry(key);
+        assert_eq!(entry.is_occupied(), occupied);
+        assert_eq!(entry.is_vacant(), !occupied);
+    }
+
+    let mut hits = 0;
+
+    for key in [Some(Part::One), Some(Part::One), None] {
+        let entry = map.entry(key);
+
+        if entry.is_occupied() {
+            hits += 1;
+        }
+
+        *entry.or_default() += 1;
+    }
+
+    assert_eq!(hits, 2);
+    assert_eq!(map.get(Some(Part::One)), Some(&2));
+    assert_eq!(map.get(None), Some(&2));
+}
@@ -0,0 +1,42 @@
+use fixed_map::{Key, Map, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Key {
+    Simple,
+    Option(Option<Part>),
+    Flag(bool),
+}
+
+#[test]
+fn composite_keys() {
+    let mut a = Map::new();
+    a.insert(Key::Simple, 1);
+    a.insert(Key::Option(None), 2);
+    a.insert(Key::Flag(true), 3);
+
+    let mut b = a;
+    b.insert(Key::Option(None), 20);
+    b.insert(Key::Option(Some(Part::Two)), 4);
+
+    assert!(!a.eq_ignoring(&b, &Set::from_array([Key::Option(None)])));
+    assert!(a.eq_ignoring(
+        &b,
+        &Set::from_array([Key::Option(None), Key::Option(Some(Part::Two))])
+    ));
+    assert!(b.eq_ignoring(
+        &a,
+        &Set::from_array([Key::Option(None), Key::Option(Some(Part::Two))])
+    ));
+
+    b.insert(Key::Flag(true), 30);
+    assert!(!b.eq_ignoring(
+        &a,
+        &Set::from_array([Key::Option(None), Key::Option(Some(Part::Two))])
+    ));
+}
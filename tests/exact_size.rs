@@ -0,0 +1,173 @@
+use fixed_map::map::{Entry, OccupiedEntry};
+use fixed_map::{Key, Map, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Composite {
+    Part(Option<Part>),
+    Flag(bool),
+    Byte(u8),
+    Unit(()),
+    Simple,
+}
+
+fn assert_exact<I>(iter: I)
+where
+    I: ExactSizeIterator,
+{
+    let len = iter.len();
+    assert_eq!(iter.size_hint(), (len, Some(len)));
+    assert_eq!(iter.count(), len);
+}
+
+#[test]
+fn option_storage() {
+    let mut map = Map::new();
+    assert_exact(map.iter());
+
+    map.insert(Some(Part::Two), 1);
+    map.insert(None, 2);
+
+    assert_eq!(map.iter().len(), 2);
+    assert_exact(map.iter());
+    assert_exact(map.keys());
+    assert_exact(map.values());
+    assert_exact(map.iter_mut());
+    assert_exact(map.values_mut());
+    assert_exact(map.into_iter());
+
+    let mut set = Set::new();
+    set.insert(None);
+    set.insert(Some(Part::One));
+    assert_eq!(set.iter().len(), 2);
+    assert_exact(set.iter());
+    assert_exact(set.into_iter());
+}
+
+#[test]
+fn composite_storage() {
+    let mut map = Map::new();
+    map.insert(Composite::Part(None), 1);
+    map.insert(Composite::Flag(true), 2);
+    map.insert(Composite::Byte(42), 3);
+    map.insert(Composite::Unit(()), 4);
+    map.insert(Composite::Simple, 5);
+
+    {
+        let mut iter = map.iter();
+        assert_eq!(iter.len(), 5);
+        iter.next();
+        assert_eq!(iter.len(), 4);
+    }
+
+    assert_exact(map.keys());
+    assert_exact(map.values());
+    assert_exact(map.iter_mut());
+    assert_exact(map.values_mut());
+    assert_exact(map.into_iter());
+
+    let mut set = Set::new();
+    set.insert(Composite::Byte(1));
+    set.insert(Composite::Byte(2));
+    set.insert(Composite::Simple);
+    assert_eq!(set.iter().len(), 3);
+    assert_exact(set.iter());
+    assert_exact(set.into_iter());
+}
+
+#[test]
+fn unit_storage() {
+    let mut map = Map::new();
+    map.insert(Part::Two, 1);
+
+    assert_eq!(map.values().len(), 1);
+    assert_exact(map.keys());
+    assert_exact(map.into_iter());
+
+    let empty = Map::<(), u32>::new();
+    assert_eq!(empty.keys().len(), 0);
+    assert_exact(empty.keys());
+}
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn hashbrown_storage() {
+    let mut map = Map::new();
+    map.insert(1u32, 'a');
+    map.insert(2u32, 'b');
+
+    assert_eq!(map.iter().len(), 2);
+    assert_exact(map.iter());
+    assert_exact(map.keys());
+    assert_exact(map.values());
+    assert_exact(map.iter_mut());
+    assert_exact(map.values_mut());
+    assert_exact(map.into_iter());
+
+    let set = [1u32, 2, 3].into_iter().collect::<Set<_>>();
+    assert_eq!(set.iter().len(), 3);
+    assert_exact(set.into_iter());
+}
+
+#[test]
+fn byte_storage() {
+    let mut map = Map::new();
+    map.insert(b'a', 1);
+    map.insert(b'a', 2);
+    map.insert(b'z', 3);
+    *map.entry(b'm').or_insert(0) += 4;
+    assert_eq!(map.len(), 3);
+    assert_exact(map.iter());
+    assert_exact(map.keys());
+    assert_exact(map.values());
+    assert_exact(map.iter_mut());
+    assert_exact(map.values_mut());
+
+    if let Entry::Occupied(entry) = map.entry(b'z') {
+        assert_eq!(entry.remove(), 3);
+    }
+
+    assert_eq!(map.remove(b'z'), None);
+    assert_eq!(map.iter().len(), 2);
+
+    map.retain(|key, _| key != b'a');
+    assert_eq!(map.keys().len(), 1);
+    assert_exact(map.into_iter());
+
+    map.clear();
+    assert!(map.is_empty());
+    assert_eq!(map.iter().len(), 0);
+}
+
+#[test]
+fn wrapped_exact_length() {
+    use fixed_map::iter::ExactSize;
+
+    let mut iter = ExactSize::new([1, 2, 3].into_iter(), 3);
+    assert_eq!(iter.next_back(), Some(3));
+    assert_eq!(iter.len(), 2);
+    assert!(iter.eq([1, 2]));
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "iterator produced fewer items than its length")]
+fn wrapped_fewer_items() {
+    use fixed_map::iter::ExactSize;
+
+    ExactSize::new([1, 2].into_iter(), 3).for_each(drop);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "iterator produced more items than its length")]
+fn wrapped_more_items() {
+    use fixed_map::iter::ExactSize;
+
+    ExactSize::new([1, 2, 3].into_iter(), 2).for_each(drop);
+}
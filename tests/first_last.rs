@@ -0,0 +1,57 @@
+use fixed_map::{Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Priority {
+    High,
+    Medium,
+    Low,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Composite {
+    Priority(Option<Priority>),
+    Flag(bool),
+    Unit,
+}
+
+#[test]
+fn first_last() {
+    let mut map = Map::new();
+    assert_eq!(map.first(), None);
+    assert_eq!(map.last(), None);
+
+    map.insert(Composite::Unit, 1);
+    assert_eq!(map.first(), Some((Composite::Unit, &1)));
+    assert_eq!(map.last(), Some((Composite::Unit, &1)));
+
+    map.insert(Composite::Flag(false), 2);
+    map.insert(Composite::Priority(Some(Priority::Low)), 3);
+    map.insert(Composite::Priority(None), 4);
+
+    assert_eq!(
+        map.first(),
+        Some((Composite::Priority(Some(Priority::Low)), &3))
+    );
+    assert_eq!(map.last(), Some((Composite::Unit, &1)));
+}
+
+#[test]
+fn pop_first_last() {
+    let mut map = Map::new();
+    assert_eq!(map.pop_first(), None);
+    assert_eq!(map.pop_last(), None);
+
+    map.insert(Composite::Unit, 1);
+    map.insert(Composite::Flag(true), 2);
+    map.insert(Composite::Flag(false), 3);
+    map.insert(Composite::Priority(Some(Priority::Medium)), 4);
+
+    assert_eq!(
+        map.pop_first(),
+        Some((Composite::Priority(Some(Priority::Medium)), 4))
+    );
+    assert_eq!(map.pop_last(), Some((Composite::Unit, 1)));
+    assert_eq!(map.pop_last(), Some((Composite::Flag(true), 2)));
+    assert_eq!(map.pop_first(), Some((Composite::Flag(false), 3)));
+    assert!(map.is_empty());
+}
@@ -0,0 +1,74 @@
+use fixed_map::key::FixedKey;
+use fixed_map::{Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Key {
+    A,
+    B,
+    C,
+    D,
+    E,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Empty {}
+
+#[test]
+fn index_matches_declaration_order() {
+    assert!(Key::keys().map(Key::index).eq(0..Key::COUNT));
+
+    for key in Key::keys() {
+        assert_eq!(Key::from_index(key.index()), Some(key));
+    }
+
+    assert_eq!(Key::from_index(Key::COUNT), None);
+    assert_eq!(Empty::from_index(0), None);
+}
+
+#[test]
+fn index_round_trip_pairs() {
+    let mut map = Map::new();
+    map.insert(Key::D, "d");
+    map.insert(Key::B, "b");
+
+    let wire = map
+        .iter()
+        .map(|(key, value)| (key.index(), *value))
+        .collect::<Vec<_>>();
+
+    assert_eq!(wire, [(1, "b"), (3, "d")]);
+
+    let decoded = wire
+        .into_iter()
+        .map(|(index, value)| (Key::from_index(index).unwrap(), value))
+        .collect::<Map<_, _>>();
+
+    assert_eq!(decoded, map);
+}
+
+#[test]
+fn from_index_parallel_array() {
+    let weights = [0.5f32, 1.0, 1.5, 2.0, 2.5];
+    let mut map = Map::new();
+
+    for (index, weight) in weights.iter().enumerate() {
+        if let Some(key) = Key::from_index(index) {
+            map.insert(key, *weight);
+        }
+    }
+
+    assert_eq!(map.len(), Key::COUNT);
+    assert_eq!(map.get(Key::C), Some(&1.5));
+    assert!(Key::from_index(weights.len()).is_none());
+}
+
+#[test]
+fn default_filled() {
+    let map: Map<Key, Option<u8>> = Map::default_filled();
+    assert_eq!(map.len(), Key::COUNT);
+    assert!(map.iter().map(|(key, _)| key).eq(Key::keys()));
+    assert!(map.values().all(Option::is_none));
+
+    let empty: Map<Empty, u32> = Map::default_filled();
+    assert!(empty.is_empty());
+}
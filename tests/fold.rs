@@ -0,0 +1,44 @@
+use fixed_map::{Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Grade {
+    Exam,
+    Homework(Option<bool>),
+    Bonus,
+}
+
+#[test]
+fn fold() {
+    let mut scores = Map::new();
+    assert_eq!(scores.fold(7, |total, _, score| total + score), 7);
+
+    scores.insert(Grade::Bonus, 5);
+    scores.insert(Grade::Homework(None), 40);
+    scores.insert(Grade::Homework(Some(true)), 100);
+    scores.insert(Grade::Exam, 80);
+
+    let visited = scores.fold(Vec::new(), |mut keys, grade, _| {
+        keys.push(grade);
+        keys
+    });
+    assert!(visited.iter().copied().eq(scores.keys()));
+
+    let weighted = scores.fold(0, |total, grade, score| {
+        let weight = match grade {
+            Grade::Exam => 3,
+            Grade::Homework(_) => 1,
+            Grade::Bonus => 0,
+        };
+
+        total + weight * score
+    });
+    assert_eq!(weighted, 380);
+}
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn fold_hashbrown() {
+    let map: Map<u32, u32> = (1..=4).map(|n| (n, n * 10)).collect();
+
+    assert_eq!(map.fold(0, |total, key, value| total + key * value), 300);
+}
@@ -0,0 +1,58 @@
+use core::iter::FusedIterator;
+
+use fixed_map::{Key, Map, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Composite {
+    Part(Option<Part>),
+    Flag(bool),
+    Simple,
+}
+
+fn assert_fused<I>(mut iter: I)
+where
+    I: FusedIterator,
+{
+    for _ in iter.by_ref() {}
+    assert!(iter.next().is_none());
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn map_iterators() {
+    let mut map = Map::new();
+    map.insert(Composite::Part(None), 1);
+    map.insert(Composite::Flag(false), 2);
+    map.insert(Composite::Simple, 3);
+
+    assert_fused(map.iter());
+    assert_fused(map.keys());
+    assert_fused(map.values());
+    assert_fused(map.iter_mut());
+    assert_fused(map.values_mut());
+    assert_fused(map.into_iter());
+
+    let mut map = Map::new();
+    map.insert(true, Part::One);
+    assert_fused(map.keys());
+    assert_fused(map.iter_mut());
+}
+
+#[test]
+fn set_iterators() {
+    let mut set = Set::new();
+    set.insert(Composite::Part(Some(Part::Two)));
+    set.insert(Composite::Flag(true));
+
+    assert_fused(set.iter());
+    assert_fused(set.into_iter());
+
+    let set = [1u8, 200].into_iter().collect::<Set<_>>();
+    assert_fused(set.iter());
+}
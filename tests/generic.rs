@@ -0,0 +1,78 @@
+use fixed_map::{Key, Map, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Wrapper<T>
+where
+    T: fixed_map::key::Key,
+{
+    Inner(T),
+    Empty,
+}
+
+#[test]
+fn generic_map() {
+    let mut map = Map::new();
+    map.insert(Wrapper::Inner(true), 1);
+    map.insert(Wrapper::Empty, 2);
+    map.insert(Wrapper::Inner(false), 3);
+
+    assert_eq!(map.get(Wrapper::Inner(true)), Some(&1));
+    assert_eq!(map.get(Wrapper::Empty), Some(&2));
+    assert_eq!(map.len(), 3);
+
+    assert!(map
+        .keys()
+        .eq([Wrapper::Inner(false), Wrapper::Inner(true), Wrapper::Empty]));
+
+    *map.entry(Wrapper::Empty).or_insert(0) += 10;
+    assert_eq!(map.get(Wrapper::Empty), Some(&12));
+}
+
+#[test]
+fn generic_set() {
+    let mut set = Set::new();
+    set.insert(Wrapper::Inner(3u8));
+    set.insert(Wrapper::Inner(1));
+
+    assert!(set.contains(Wrapper::Inner(3)));
+    assert!(!set.contains(Wrapper::Empty));
+    assert!(set.iter().eq([Wrapper::Inner(1), Wrapper::Inner(3)]));
+
+    let copy = set;
+    assert_eq!(copy, set);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Mixed<T>
+where
+    T: fixed_map::key::Key,
+{
+    Generic(T),
+    Concrete(bool),
+}
+
+#[test]
+fn mixed_generic_and_concrete() {
+    let mut map = Map::new();
+    map.insert(Mixed::Concrete(true), 'a');
+    map.insert(Mixed::Generic(7u8), 'b');
+
+    assert!(map
+        .iter()
+        .rev()
+        .eq([(Mixed::Concrete(true), &'a'), (Mixed::Generic(7), &'b')]));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Tagged<const N: usize> {
+    Flag(bool),
+    Other,
+}
+
+#[test]
+fn const_generic() {
+    let mut map = Map::<Tagged<4>, u32>::new();
+    map.insert(Tagged::Flag(false), 1);
+    map.insert(Tagged::Other, 2);
+    assert!(map.values().eq([&1, &2]));
+}
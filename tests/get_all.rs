@@ -0,0 +1,34 @@
+use fixed_map::{Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Key {
+    Simple,
+    Option(Option<Part>),
+    Flag(bool),
+}
+
+#[test]
+fn composite_keys() {
+    let mut map = Map::new();
+    map.insert(Key::Option(None), 1);
+    map.insert(Key::Flag(false), 2);
+
+    assert_eq!(
+        map.get_all([
+            Key::Flag(false),
+            Key::Simple,
+            Key::Option(None),
+            Key::Option(Some(Part::One)),
+            Key::Flag(false),
+        ]),
+        [Some(&2), None, Some(&1), None, Some(&2)]
+    );
+
+    assert_eq!(map.get_all([]), [None::<&u32>; 0]);
+}
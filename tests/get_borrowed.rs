@@ -0,0 +1,30 @@
+#![cfg(feature = "hashbrown")]
+
+use fixed_map::Map;
+
+#[test]
+fn borrowed_str() {
+    let mut map = Map::new();
+    map.insert("alpha", 1);
+    map.insert("beta", 2);
+
+    let input = String::from("alpha beta gamma");
+
+    let found = input
+        .split(' ')
+        .map(|word| map.get_borrowed(word).copied())
+        .collect::<Vec<_>>();
+
+    assert_eq!(found, [Some(1), Some(2), None]);
+    assert_eq!(map.get_borrowed(&"beta"), Some(&2));
+}
+
+#[test]
+fn borrowed_bytes() {
+    let mut map = Map::new();
+    map.insert(b"GET".as_slice(), "get");
+
+    let request = b"GET /".to_vec();
+    assert_eq!(map.get_borrowed(&request[..3]), Some(&"get"));
+    assert_eq!(map.get_borrowed(&request[..]), None);
+}
@@ -0,0 +1,33 @@
+#![cfg(all(feature = "std", feature = "hashbrown"))]
+
+use std::collections::HashMap;
+
+use fixed_map::Map;
+
+#[test]
+fn option_keys() {
+    let mut map = Map::new();
+    map.insert(None, "none");
+    map.insert(Some(7u32), "seven");
+
+    let mut hash_map = HashMap::new();
+    hash_map.insert(Some(7u32), "seven");
+    assert_ne!(map, hash_map);
+
+    hash_map.insert(None, "none");
+    assert_eq!(map, hash_map);
+    assert_eq!(hash_map, map);
+
+    hash_map.insert(None, "nothing");
+    assert_ne!(map, hash_map);
+}
+
+#[test]
+fn round_trip() {
+    let hash_map = (0..100u32)
+        .map(|n| (Some(n), n * 2))
+        .collect::<HashMap<_, _>>();
+    let map = Map::from(hash_map.clone());
+    assert_eq!(map, hash_map);
+    assert_eq!(HashMap::<_, _>::from(map), hash_map);
+}
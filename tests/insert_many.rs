@@ -0,0 +1,32 @@
+use fixed_map::{Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Key {
+    Simple,
+    Flag(bool),
+    Option(Option<bool>),
+}
+
+#[test]
+fn insert_many_reports_new_keys() {
+    let mut map = Map::new();
+    map.insert(Key::Flag(true), 1);
+
+    let added = map.insert_many([
+        (Key::Flag(true), 2),
+        (Key::Option(None), 3),
+        (Key::Simple, 4),
+        (Key::Option(None), 5),
+    ]);
+
+    assert!(added.iter().eq([Key::Simple, Key::Option(None)]));
+    assert_eq!(map.get(Key::Flag(true)), Some(&2));
+    assert_eq!(map.get(Key::Option(None)), Some(&5));
+
+    let added = map.insert_many([(Key::Simple, 6)]);
+    assert!(added.is_empty());
+
+    let added = map.insert_many([]);
+    assert!(added.is_empty());
+    assert_eq!(map.len(), 3);
+}
@@ -0,0 +1,50 @@
+use fixed_map::{Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Key {
+    First,
+    Second(bool),
+    Third,
+}
+
+fn map() -> Map<Key, String> {
+    let mut map = Map::new();
+    map.insert(Key::Third, String::from("c"));
+    map.insert(Key::Second(true), String::from("b"));
+    map.insert(Key::First, String::from("a"));
+    map
+}
+
+#[test]
+fn by_value() {
+    let mut keys = Vec::new();
+    let mut values = Vec::new();
+
+    for (key, value) in map() {
+        keys.push(key);
+        values.push(value);
+    }
+
+    assert_eq!(keys, [Key::First, Key::Second(true), Key::Third]);
+    assert_eq!(values, ["a", "b", "c"]);
+
+    let collected = map().into_iter().collect::<Vec<_>>();
+    assert_eq!(collected.len(), 3);
+    assert_eq!(collected[1], (Key::Second(true), String::from("b")));
+}
+
+#[test]
+fn by_reference() {
+    let mut map = map();
+
+    for (_, value) in &mut map {
+        value.push('!');
+    }
+
+    let joined = (&map)
+        .into_iter()
+        .map(|(_, value)| value.as_str())
+        .collect::<Vec<_>>();
+
+    assert_eq!(joined, ["a!", "b!", "c!"]);
+}
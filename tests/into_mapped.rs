@@ -0,0 +1,32 @@
+use fixed_map::{Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum State {
+    Idle,
+    Busy(bool),
+    Done(Option<bool>),
+}
+
+#[test]
+fn into_mapped() {
+    let mut map = Map::new();
+    map.insert(State::Done(None), 1u32);
+    map.insert(State::Busy(true), 2u32);
+    map.insert(State::Idle, 3u32);
+
+    let widened: Map<State, u64> = map.into_mapped();
+    assert!(widened.iter().eq([
+        (State::Idle, &3u64),
+        (State::Busy(true), &2u64),
+        (State::Done(None), &1u64),
+    ]));
+
+    let mut names = Map::new();
+    names.insert(State::Busy(false), "busy");
+
+    let names: Map<State, String> = names.into_mapped();
+    assert_eq!(
+        names.get(State::Busy(false)).map(String::as_str),
+        Some("busy")
+    );
+}
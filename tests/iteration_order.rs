@@ -0,0 +1,80 @@
+use fixed_map::{Key, Map, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Key {
+    A,
+    B,
+    C,
+    D,
+    E,
+}
+
+const ALL: [Key; 5] = [Key::A, Key::B, Key::C, Key::D, Key::E];
+
+fn scrambled() -> Map<Key, u32> {
+    let mut map = Map::new();
+    map.insert(Key::D, 4);
+    map.insert(Key::A, 1);
+    map.insert(Key::E, 5);
+    map.insert(Key::C, 3);
+    map.insert(Key::B, 2);
+    map
+}
+
+#[test]
+fn map_iter() {
+    let map = scrambled();
+    assert!(map.iter().map(|(k, _)| k).eq(ALL));
+    assert!(map.iter().map(|(_, v)| *v).eq([1, 2, 3, 4, 5]));
+}
+
+#[test]
+fn map_keys() {
+    let map = scrambled();
+    assert!(map.keys().eq(ALL));
+}
+
+#[test]
+fn map_values() {
+    let map = scrambled();
+    assert!(map.values().copied().eq([1, 2, 3, 4, 5]));
+}
+
+#[test]
+fn map_iter_mut() {
+    let mut map = scrambled();
+    assert!(map.iter_mut().map(|(k, _)| k).eq(ALL));
+}
+
+#[test]
+fn map_values_mut() {
+    let mut map = scrambled();
+    assert!(map.values_mut().map(|v| *v).eq([1, 2, 3, 4, 5]));
+}
+
+#[test]
+fn map_into_iter() {
+    let map = scrambled();
+    assert!(map.into_iter().eq(ALL.into_iter().zip(1..=5)));
+}
+
+#[test]
+fn map_sparse() {
+    let mut map = Map::new();
+    map.insert(Key::E, 5);
+    map.insert(Key::B, 2);
+    map.insert(Key::D, 4);
+    assert!(map.keys().eq([Key::B, Key::D, Key::E]));
+}
+
+#[test]
+fn set_iter() {
+    let mut set = Set::new();
+
+    for key in [Key::C, Key::E, Key::A, Key::D, Key::B] {
+        set.insert(key);
+    }
+
+    assert!(set.iter().eq(ALL));
+    assert!(set.into_iter().eq(ALL));
+}
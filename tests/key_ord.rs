@@ -0,0 +1,82 @@
+use std::cmp::Ordering;
+
+use fixed_map::{Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+#[key(ord)]
+enum Priority {
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+#[key(ord, storage = "dense")]
+#[repr(u8)]
+enum Register {
+    Data = 7,
+    Status = 0,
+    Control = 2,
+}
+
+#[test]
+fn declaration_order() {
+    assert!(Priority::Low < Priority::Medium);
+    assert!(Priority::Medium < Priority::High);
+    assert_eq!(Priority::High.cmp(&Priority::High), Ordering::Equal);
+    assert_eq!(
+        Priority::High.partial_cmp(&Priority::Low),
+        Some(Ordering::Greater)
+    );
+    assert_eq!(Priority::Low.max(Priority::High), Priority::High);
+
+    let mut values = vec![Priority::High, Priority::Low, Priority::Medium];
+    values.sort();
+    assert_eq!(values, [Priority::Low, Priority::Medium, Priority::High]);
+}
+
+#[test]
+fn matches_iteration_order() {
+    let mut map = Map::new();
+    map.insert(Priority::High, 3);
+    map.insert(Priority::Low, 1);
+    map.insert(Priority::Medium, 2);
+
+    let keys = map.keys().collect::<Vec<_>>();
+    let mut sorted = keys.clone();
+    sorted.sort();
+    assert_eq!(keys, sorted);
+
+    #[cfg(feature = "alloc")]
+    assert!(map.sorted_iter().eq(map.iter()));
+}
+
+#[test]
+fn dense_discriminant_order() {
+    assert!(Register::Status < Register::Control);
+    assert!(Register::Control < Register::Data);
+
+    let map = Map::from_fn(|key: Register| key as u8);
+    let mut sorted = map.keys().collect::<Vec<_>>();
+    sorted.sort();
+    assert!(map.keys().eq(sorted));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn btree_storage() {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    #[key(ord, storage = "btree")]
+    enum Stage {
+        Build,
+        Test,
+        Deploy,
+    }
+
+    let mut map = Map::new();
+    map.insert(Stage::Deploy, "deploy");
+    map.insert(Stage::Build, "build");
+    map.insert(Stage::Test, "test");
+
+    assert!(map.values().eq([&"build", &"test", &"deploy"]));
+}
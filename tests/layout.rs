@@ -0,0 +1,65 @@
+use core::mem::size_of;
+
+use fixed_map::{Key, Map, Set};
+
+#[derive(Clone, Copy, Key)]
+enum State {
+    S0,
+    S1,
+    S2,
+    S3,
+    S4,
+    S5,
+    S6,
+    S7,
+    S8,
+    S9,
+    S10,
+    S11,
+    S12,
+    S13,
+    S14,
+    S15,
+}
+
+#[test]
+fn unit_storage_is_an_array() {
+    assert_eq!(size_of::<Map<State, u32>>(), size_of::<[Option<u32>; 16]>());
+    assert_eq!(size_of::<Map<State, u8>>(), size_of::<[Option<u8>; 16]>());
+}
+
+#[test]
+fn unit_set_storage_is_a_bitset() {
+    assert_eq!(size_of::<Set<State>>(), size_of::<u64>());
+}
+
+#[derive(Clone, Copy, Key)]
+enum Composite {
+    Simple,
+    Flag(bool),
+    Singleton(()),
+    Option(Option<State>),
+}
+
+#[test]
+fn value_niches_are_preserved() {
+    use core::num::NonZeroU32;
+
+    assert_eq!(size_of::<Map<(), NonZeroU32>>(), size_of::<NonZeroU32>());
+    assert_eq!(
+        size_of::<Map<bool, NonZeroU32>>(),
+        2 * size_of::<NonZeroU32>()
+    );
+    assert_eq!(
+        size_of::<Map<State, NonZeroU32>>(),
+        16 * size_of::<NonZeroU32>()
+    );
+    assert_eq!(
+        size_of::<Map<Option<State>, NonZeroU32>>(),
+        17 * size_of::<NonZeroU32>()
+    );
+    assert_eq!(
+        size_of::<Map<Composite, NonZeroU32>>(),
+        21 * size_of::<NonZeroU32>()
+    );
+}
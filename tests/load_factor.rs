@@ -0,0 +1,80 @@
+use fixed_map::{Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Fixed {
+    First,
+    Second(Option<bool>),
+}
+
+#[test]
+fn fixed_storage() {
+    let mut map = Map::new();
+    assert_eq!(map.load_factor(), 1.0);
+
+    map.insert(Fixed::First, 1);
+    map.insert(Fixed::Second(None), 2);
+    assert_eq!(map.load_factor(), 1.0);
+
+    map.clear();
+    assert_eq!(map.load_factor(), 1.0);
+}
+
+#[cfg(feature = "hashbrown")]
+mod dynamic {
+    use fixed_map::{Key, Map};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    enum Dynamic {
+        Frame(u32),
+        Idle,
+    }
+
+    #[test]
+    fn hashbrown() {
+        let mut map: Map<u32, u32> = Map::new();
+        assert_eq!(map.load_factor(), 1.0);
+
+        map.insert_many((0..100).map(|n| (n, n)));
+        let full = map.load_factor();
+        assert!(full > 0.0 && full <= 1.0);
+
+        map.retain(|key, _| key < 5);
+        assert!(map.load_factor() < full);
+
+        map.shrink_to_fit();
+        assert!(map.load_factor() > 0.5);
+
+        map.clear_and_shrink();
+        assert_eq!(map.load_factor(), 1.0);
+    }
+
+    #[test]
+    fn composite() {
+        let mut map = Map::new();
+        map.insert(Dynamic::Idle, 0);
+        assert_eq!(map.load_factor(), 1.0);
+
+        for n in 0..100 {
+            map.insert(Dynamic::Frame(n), n);
+        }
+
+        map.retain(|key, _| key == Dynamic::Idle);
+        assert!(map.load_factor() < 0.5);
+
+        map.shrink_to_fit();
+        assert_eq!(map.load_factor(), 1.0);
+    }
+
+    #[test]
+    fn option() {
+        let mut map: Map<Option<u32>, u32> = Map::with_capacity(64);
+        map.insert(None, 0);
+        map.insert(Some(1), 1);
+
+        let sparse = map.load_factor();
+        assert!(sparse < 0.5);
+
+        map.shrink_to_fit();
+        assert!(map.load_factor() > sparse);
+    }
+}
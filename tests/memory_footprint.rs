@@ -0,0 +1,59 @@
+#![cfg(feature = "hashbrown")]
+
+use core::mem::size_of;
+
+use fixed_map::{Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Key {
+    Simple,
+    Option(Option<Part>),
+    Dynamic(u32),
+    MaybeDynamic(Option<u64>),
+    #[key(ordered)]
+    Ordered(u32),
+}
+
+#[test]
+fn inline_storage_is_zero() {
+    let mut map = Map::new();
+    map.insert(Part::One, [0u8; 64]);
+    assert_eq!(map.memory_footprint(), 0);
+
+    let mut map = Map::new();
+    map.insert(Some(Part::Two), 1);
+    map.insert(None, 2);
+    assert_eq!(map.memory_footprint(), 0);
+}
+
+#[test]
+fn composite_sums_dynamic_fields() {
+    let mut map: Map<Key, u16> = Map::new();
+    map.insert(Key::Simple, 1);
+    map.insert(Key::Option(None), 2);
+    assert_eq!(map.memory_footprint(), 0);
+
+    map.insert(Key::Dynamic(1), 3);
+    let dynamic = map.memory_footprint();
+    assert!(dynamic >= size_of::<(u32, u16)>());
+
+    map.insert(Key::MaybeDynamic(Some(1)), 4);
+    let maybe = map.memory_footprint() - dynamic;
+    assert!(maybe >= size_of::<(u64, u16)>());
+
+    map.insert(Key::Ordered(1), 5);
+    map.insert(Key::Ordered(2), 6);
+    assert_eq!(
+        map.memory_footprint() - dynamic - maybe,
+        2 * size_of::<(u32, u16)>()
+    );
+
+    map.clear_and_shrink();
+    assert_eq!(map.memory_footprint(), 0);
+}
@@ -0,0 +1,35 @@
+use fixed_map::{Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Key {
+    Simple,
+    Option(Option<Part>),
+    Flag(bool),
+}
+
+#[test]
+fn reduce_shards() {
+    let shards = [
+        Map::from_array([(Key::Simple, 1), (Key::Option(None), 2)]),
+        Map::from_array([(Key::Option(None), 3), (Key::Flag(false), 4)]),
+        Map::from_array([(Key::Simple, 5), (Key::Option(Some(Part::Two)), 6)]),
+    ];
+
+    let mut total = Map::new();
+
+    for shard in shards {
+        total.merge_with(shard, |a, b| *a += b);
+    }
+
+    assert_eq!(total.len(), 4);
+    assert_eq!(total.get(Key::Simple), Some(&6));
+    assert_eq!(total.get(Key::Option(None)), Some(&5));
+    assert_eq!(total.get(Key::Option(Some(Part::Two))), Some(&6));
+    assert_eq!(total.get(Key::Flag(false)), Some(&4));
+}
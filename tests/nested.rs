@@ -0,0 +1,99 @@
+use fixed_map::{Key, Map, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    A,
+    B,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Inner {
+    Part(Part),
+    Flag(bool),
+    Plain,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Key {
+    First(Part),
+    Nested(Inner),
+    Maybe(Option<Part>),
+    Second,
+}
+
+#[test]
+fn unit_payload() {
+    let mut a = Map::new();
+    a.insert(Key::First(Part::A), 1);
+    a.insert(Key::Second, 2);
+
+    assert_eq!(a.get(Key::First(Part::A)), Some(&1));
+    assert_eq!(a.get(Key::First(Part::B)), None);
+    assert_eq!(a.get(Key::Second), Some(&2));
+
+    assert_eq!(a.insert(Key::First(Part::A), 3), Some(1));
+    assert_eq!(a.remove(Key::First(Part::A)), Some(3));
+    assert!(a.iter().eq([(Key::Second, &2)]));
+}
+
+#[test]
+fn nested_payload() {
+    let mut a = Map::new();
+    a.insert(Key::Nested(Inner::Plain), 1);
+    a.insert(Key::Nested(Inner::Flag(true)), 2);
+    a.insert(Key::Nested(Inner::Part(Part::B)), 3);
+    a.insert(Key::Maybe(None), 4);
+    a.insert(Key::Maybe(Some(Part::A)), 5);
+    a.insert(Key::First(Part::B), 6);
+
+    assert_eq!(a.len(), 6);
+    assert_eq!(a.get(Key::Nested(Inner::Flag(false))), None);
+    assert_eq!(a.get(Key::Nested(Inner::Flag(true))), Some(&2));
+
+    assert!(a.keys().eq([
+        Key::First(Part::B),
+        Key::Nested(Inner::Part(Part::B)),
+        Key::Nested(Inner::Flag(true)),
+        Key::Nested(Inner::Plain),
+        Key::Maybe(Some(Part::A)),
+        Key::Maybe(None),
+    ]));
+
+    a.retain(|key, _| !matches!(key, Key::Nested(_)));
+    assert!(a.values().copied().eq([6, 5, 4]));
+}
+
+#[test]
+fn nested_set() {
+    let mut set = Set::new();
+    assert!(set.insert(Key::Nested(Inner::Part(Part::A))));
+    set.insert(Key::Maybe(None));
+    assert!(set.contains(Key::Nested(Inner::Part(Part::A))));
+    assert!(!set.contains(Key::Nested(Inner::Part(Part::B))));
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn several_payload_variants() {
+    #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    enum Route {
+        A(Part),
+        B(bool),
+        C,
+    }
+
+    let mut map = Map::new();
+    map.insert(Route::C, 1);
+    map.insert(Route::B(true), 2);
+    map.insert(Route::B(false), 3);
+    map.insert(Route::A(Part::B), 4);
+    map.insert(Route::A(Part::A), 5);
+
+    assert!(map.iter().eq([
+        (Route::A(Part::A), &5),
+        (Route::A(Part::B), &4),
+        (Route::B(false), &3),
+        (Route::B(true), &2),
+        (Route::C, &1),
+    ]));
+}
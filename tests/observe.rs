@@ -0,0 +1,90 @@
+use fixed_map::map::Change;
+use fixed_map::observe::ObservableMap;
+use fixed_map::Key;
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Setting {
+    Volume,
+    Theme,
+    Language(bool),
+}
+
+#[derive(Debug, PartialEq)]
+enum Event {
+    Added(Setting, u32),
+    Removed(Setting, u32),
+    Changed(Setting, u32, u32),
+}
+
+#[test]
+fn observe_mutations() {
+    let mut events = Vec::new();
+
+    let observer = |change: Change<'_, Setting, u32>| {
+        events.push(match change {
+            Change::Added(key, value) => Event::Added(key, *value),
+            Change::Removed(key, value) => Event::Removed(key, *value),
+            Change::Changed(key, old, new) => Event::Changed(key, *old, *new),
+        });
+    };
+
+    {
+        let mut map = ObservableMap::new(observer);
+
+        map.insert(Setting::Volume, 1);
+        map.insert(Setting::Language(true), 2);
+        map.insert(Setting::Volume, 3);
+        assert!(map.modify(Setting::Language(true), |value| *value *= 10));
+        assert!(!map.modify(Setting::Theme, |value| *value *= 10));
+        assert_eq!(map.remove(Setting::Theme), None);
+        map.insert(Setting::Theme, 4);
+        map.retain(|key, _| key != Setting::Volume);
+
+        assert_eq!(map.get(Setting::Language(true)), Some(&20));
+        assert_eq!(map.len(), 2);
+
+        map.clear();
+        assert!(map.is_empty());
+    }
+
+    assert_eq!(
+        events,
+        [
+            Event::Added(Setting::Volume, 1),
+            Event::Added(Setting::Language(true), 2),
+            Event::Changed(Setting::Volume, 1, 3),
+            Event::Changed(Setting::Language(true), 2, 20),
+            Event::Added(Setting::Theme, 4),
+            Event::Removed(Setting::Volume, 3),
+            Event::Removed(Setting::Theme, 4),
+            Event::Removed(Setting::Language(true), 20),
+        ]
+    );
+}
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn clear_and_retain_keep_allocation() {
+    let map = (0u32..32).map(|n| (n, n)).collect::<fixed_map::Map<_, _>>();
+    let footprint = map.memory_footprint();
+
+    let mut removed = 0;
+    let mut map = ObservableMap::from_map(map, |change: Change<'_, u32, u32>| {
+        if let Change::Removed(..) = change {
+            removed += 1;
+        }
+    });
+
+    // Removals may leave markers behind which count against the capacity
+    // until the map is cleared, so only check that it wasn't shrunk.
+    map.retain(|key, _| key % 2 == 0);
+    assert_eq!(map.len(), 16);
+    assert!(map.memory_footprint() >= 32 * core::mem::size_of::<(u32, u32)>());
+
+    map.clear();
+    assert!(map.is_empty());
+    assert_eq!(map.memory_footprint(), footprint);
+
+    drop(map);
+    assert_eq!(removed, 32);
+}
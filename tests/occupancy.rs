@@ -0,0 +1,34 @@
+use fixed_map::{Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+}
+
+#[test]
+fn nested_options() {
+    let mut map = Map::new();
+    map.insert(Some(None), 1);
+    map.insert(Some(Some(Part::Two)), 2);
+    assert_eq!(map.occupancy(), (false, 2));
+
+    map.insert(None, 3);
+    assert_eq!(map.occupancy(), (true, 2));
+    assert_eq!(map.len(), 3);
+
+    map.remove(Some(None));
+    map.remove(Some(Some(Part::Two)));
+    assert_eq!(map.occupancy(), (true, 0));
+}
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn dynamic_some() {
+    let mut map = (0..10u32).map(|n| (Some(n), n)).collect::<Map<_, _>>();
+    assert_eq!(map.occupancy(), (false, 10));
+
+    map.retain(|key, _| key.is_none());
+    map.insert(None, 0);
+    assert_eq!(map.occupancy(), (true, 0));
+}
@@ -0,0 +1,55 @@
+use fixed_map::{Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+}
+
+#[test]
+fn nested_option_iteration() {
+    let mut map = Map::new();
+    map.insert(None, 1);
+    map.insert(Some(None), 2);
+    map.insert(Some(Some(Part::Two)), 3);
+    map.insert(Some(Some(Part::One)), 4);
+
+    let expected = [
+        (Some(Some(Part::One)), 4),
+        (Some(Some(Part::Two)), 3),
+        (Some(None), 2),
+        (None, 1),
+    ];
+
+    assert!(map.iter().map(|(k, v)| (k, *v)).eq(expected));
+    assert!(map
+        .iter()
+        .rev()
+        .map(|(k, v)| (k, *v))
+        .eq(expected.into_iter().rev()));
+    assert!(map.keys().eq(expected.map(|(k, _)| k)));
+    assert!(map.values().rev().copied().eq([1, 2, 3, 4]));
+
+    {
+        let mut it = map.iter();
+        assert_eq!(it.len(), 4);
+        assert_eq!(it.next_back(), Some((None, &1)));
+        assert_eq!(it.next(), Some((Some(Some(Part::One)), &4)));
+        assert_eq!(it.clone().count(), 2);
+        assert_eq!(it.len(), 2);
+    }
+
+    for value in map.values_mut() {
+        *value *= 10;
+    }
+
+    for (key, value) in map.iter_mut().rev() {
+        if key.is_none() {
+            *value += 1;
+        }
+    }
+
+    assert!(map
+        .into_iter()
+        .eq(expected.map(|(k, v)| (k, v * 10 + usize::from(k.is_none())))));
+}
@@ -0,0 +1,63 @@
+use fixed_map::map::{Entry, OccupiedEntry};
+use fixed_map::{Map, Set};
+
+#[test]
+fn map_len_matches_entries() {
+    let mut map = Map::new();
+
+    for key in [None, Some(0u8), Some(255), None, Some(0)] {
+        map.insert(key, ());
+        assert_eq!(map.len(), map.iter().count());
+    }
+
+    map.retain(|key, _| key != Some(0));
+    assert_eq!(map.len(), 2);
+
+    for key in [None, Some(255), None] {
+        map.remove(key);
+        assert_eq!(map.len(), map.iter().count());
+    }
+
+    assert!(map.is_empty());
+}
+
+#[test]
+fn map_len_matches_entries_through_entry() {
+    let mut map = Map::new();
+
+    *map.entry(None).or_insert(0) += 1;
+    *map.get_or_insert_with_key(Some(Some(true)), |_| 0) += 1;
+    map.entry(Some(None)).or_default();
+    assert_eq!(map.len(), map.iter().count());
+
+    if let Entry::Occupied(entry) = map.entry(Some(Some(true))) {
+        assert_eq!(entry.remove(), 1);
+    }
+
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.len(), map.iter().count());
+
+    map.clear();
+    assert!(map.is_empty());
+    assert_eq!(map.iter().count(), 0);
+}
+
+#[test]
+fn set_len_matches_values() {
+    let mut set = Set::new();
+
+    for value in [None, Some(true), Some(false), None] {
+        set.insert(value);
+        assert_eq!(set.len(), set.iter().count());
+    }
+
+    set.retain(|value| value != Some(true));
+    assert_eq!(set.len(), 2);
+
+    for value in [None, Some(false), Some(false)] {
+        set.remove(value);
+        assert_eq!(set.len(), set.iter().count());
+    }
+
+    assert!(set.is_empty());
+}
@@ -0,0 +1,36 @@
+use fixed_map::{Key, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+}
+
+#[test]
+fn only_none_is_not_empty() {
+    let mut set = Set::<Option<Part>>::new();
+    assert!(set.is_empty());
+
+    set.insert(None);
+    assert!(!set.is_empty());
+    assert_eq!(set.len(), 1);
+
+    set.remove(None);
+    assert!(set.is_empty());
+}
+
+#[test]
+fn retain_removes_only_none() {
+    let mut set = Set::from_array([None, Some(Part::One), Some(Part::Two)]);
+
+    let mut seen = Vec::new();
+
+    set.retain(|value| {
+        seen.push(value);
+        value.is_some()
+    });
+
+    assert_eq!(seen, [Some(Part::One), Some(Part::Two), None]);
+    assert!(set.iter().eq([Some(Part::One), Some(Part::Two)]));
+    assert!(!set.contains(None));
+}
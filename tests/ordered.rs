@@ -0,0 +1,84 @@
+#![cfg(feature = "alloc")]
+
+use fixed_map::{Key, Map, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Rank {
+    #[key(ordered)]
+    Score(u32),
+    Unranked,
+}
+
+#[test]
+fn ordered_map() {
+    let mut map = Map::new();
+
+    for score in [50, 10, 40, 20, 30] {
+        map.insert(Rank::Score(score), score * 2);
+    }
+
+    map.insert(Rank::Unranked, 0);
+
+    assert!(map.keys().eq([
+        Rank::Score(10),
+        Rank::Score(20),
+        Rank::Score(30),
+        Rank::Score(40),
+        Rank::Score(50),
+        Rank::Unranked,
+    ]));
+
+    assert!(map.values().copied().eq([20, 40, 60, 80, 100, 0]));
+    assert!(map.keys().rev().eq([
+        Rank::Unranked,
+        Rank::Score(50),
+        Rank::Score(40),
+        Rank::Score(30),
+        Rank::Score(20),
+        Rank::Score(10),
+    ]));
+
+    *map.entry(Rank::Score(20)).or_default() += 1;
+    assert_eq!(map.get(Rank::Score(20)), Some(&41));
+    assert_eq!(map.remove(Rank::Score(10)), Some(20));
+    assert_eq!(map.len(), 5);
+}
+
+#[test]
+fn ordered_set() {
+    let mut set = Set::new();
+    set.insert(Rank::Score(3));
+    set.insert(Rank::Unranked);
+    set.insert(Rank::Score(1));
+    set.insert(Rank::Score(2));
+
+    assert!(set.into_iter().eq([
+        Rank::Score(1),
+        Rank::Score(2),
+        Rank::Score(3),
+        Rank::Unranked,
+    ]));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Key)]
+#[key(storage = "btree")]
+enum Level {
+    High,
+    Low,
+}
+
+#[test]
+fn btree_storage() {
+    use fixed_map::key::FixedKey;
+
+    let mut set = Set::new();
+    set.insert(Level::Low);
+    set.insert(Level::High);
+
+    assert!(set.iter().eq([Level::High, Level::Low]));
+    assert_eq!(Level::COUNT, 2);
+
+    let map = Map::from_fn(|level| level == Level::High);
+    assert_eq!(map.get(Level::High), Some(&true));
+    assert_eq!(map.get(Level::Low), Some(&false));
+}
@@ -0,0 +1,43 @@
+#![cfg(feature = "proptest")]
+
+use fixed_map::proptest::{map_strategy, set_strategy};
+use fixed_map::Key;
+use proptest::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Key)]
+enum Part {
+    One,
+    Two,
+    Three,
+}
+
+fn part() -> impl Strategy<Value = Part> {
+    prop_oneof![Just(Part::One), Just(Part::Two), Just(Part::Three)]
+}
+
+proptest! {
+    #[test]
+    fn unit_keys(map in map_strategy(part(), any::<u32>())) {
+        prop_assert!(map.len() <= 3);
+        prop_assert_eq!(map.iter().count(), map.len());
+    }
+
+    #[cfg(feature = "hashbrown")]
+    #[test]
+    fn dynamic_keys(map in map_strategy(0..1000u32, any::<bool>())) {
+        for (key, _) in &map {
+            prop_assert!(key < 1000);
+        }
+    }
+
+    #[test]
+    fn composed(map in map_strategy(any::<Option<u8>>(), part()).prop_filter("non-empty", |m| !m.is_empty())) {
+        prop_assert!(!map.is_empty());
+    }
+
+    #[test]
+    fn sets(set in set_strategy(part())) {
+        prop_assert!(set.len() <= 3);
+        prop_assert!(set.iter().all(|p| set.contains(p)));
+    }
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "rayon")]
+
+use fixed_map::{Key, Map};
+use rayon::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Key)]
+enum Part {
+    One,
+    Two,
+    Three,
+    Four,
+}
+
+#[test]
+fn unit_variants() {
+    let mut map = Map::new();
+    map.insert(Part::Four, 4);
+    map.insert(Part::Two, 2);
+
+    let mut entries = map.par_iter().map(|(k, v)| (k, *v)).collect::<Vec<_>>();
+    entries.sort();
+    assert_eq!(entries, [(Part::Two, 2), (Part::Four, 4)]);
+
+    let keys = map.par_keys().collect::<Vec<_>>();
+    assert_eq!(keys, [Part::Two, Part::Four]);
+
+    let sum: i32 = map.par_values().sum();
+    assert_eq!(sum, 6);
+}
+
+#[test]
+fn bytes() {
+    let mut map = Map::new();
+
+    for b in (0..=u8::MAX).step_by(3) {
+        map.insert(b, u32::from(b));
+    }
+
+    let keys = map.par_keys().collect::<Vec<_>>();
+    assert!(keys.iter().copied().eq(map.keys()));
+    assert!(map.par_iter().all(|(k, v)| u32::from(k) == *v));
+
+    let sum: u32 = map.par_values().sum();
+    assert_eq!(sum, map.values().sum());
+}
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn hashbrown() {
+    let mut map = Map::new();
+
+    for n in 0..1000u32 {
+        map.insert(n, n * 2);
+    }
+
+    let mut keys = map.par_keys().collect::<Vec<_>>();
+    keys.sort_unstable();
+    assert!(keys.into_iter().eq(0..1000));
+    assert!(map.par_iter().all(|(k, v)| k * 2 == *v));
+    assert_eq!(map.par_values().count(), 1000);
+}
+
+#[test]
+fn set_unit_variants() {
+    let mut set = fixed_map::Set::new();
+    set.insert(Part::One);
+    set.insert(Part::Three);
+
+    let values = set.par_iter().collect::<Vec<_>>();
+    assert_eq!(values, [Part::One, Part::Three]);
+    assert_eq!(set.par_iter().filter(|&p| p > Part::Two).count(), 1);
+}
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn set_option() {
+    let mut set = fixed_map::Set::new();
+
+    for n in 0..100u32 {
+        set.insert(Some(n));
+    }
+
+    assert_eq!(set.par_iter().count(), 100);
+    set.insert(None);
+
+    let mut values = set.par_iter().collect::<Vec<_>>();
+    values.sort_unstable();
+    assert_eq!(values.len(), 101);
+    assert_eq!(values[0], None);
+    assert!(values[1..].iter().copied().eq((0..100).map(Some)));
+}
@@ -0,0 +1,49 @@
+use fixed_map::{Key, Map, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Key {
+    Simple,
+    Flag(bool),
+    Option(Option<bool>),
+}
+
+#[test]
+fn remove_all_counts_removed_keys() {
+    let mut map = Map::new();
+    map.insert(Key::Simple, 1);
+    map.insert(Key::Flag(true), 2);
+    map.insert(Key::Option(None), 3);
+
+    let stale = Set::from_array([Key::Flag(true), Key::Flag(false), Key::Option(None)]);
+    assert_eq!(map.remove_all(&stale), 2);
+    assert!(map.iter().eq([(Key::Simple, &1)]));
+
+    assert_eq!(map.remove_all(&stale), 0);
+    assert_eq!(map.remove_all(&Set::new()), 0);
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn remove_all_undoes_insert_many() {
+    let mut map = Map::new();
+    map.insert(Key::Simple, 0);
+
+    let added = map.insert_many([
+        (Key::Simple, 1),
+        (Key::Flag(false), 2),
+        (Key::Option(Some(true)), 3),
+    ]);
+
+    assert_eq!(map.remove_all(&added), 2);
+    assert!(map.iter().eq([(Key::Simple, &1)]));
+}
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn remove_all_hashbrown() {
+    let mut map: Map<u32, u32> = (0..10).map(|n| (n, n)).collect();
+    let stale: Set<u32> = (5..15).collect();
+
+    assert_eq!(map.remove_all(&stale), 5);
+    assert!(map.keys().all(|key| key < 5));
+}
@@ -0,0 +1,131 @@
+use core::mem::size_of;
+
+use fixed_map::key::FixedKey;
+use fixed_map::{Key, Map, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+#[key(storage = "dense")]
+#[repr(u8)]
+enum Register {
+    Status,
+    Control = 4,
+    Mode,
+    Data = 9,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+#[repr(u8)]
+enum Positional {
+    First = 10,
+    Second = 20,
+}
+
+#[test]
+fn indexed_by_discriminant() {
+    assert_eq!(
+        size_of::<Map<Register, u32>>(),
+        size_of::<[Option<u32>; 10]>()
+    );
+    assert_eq!(
+        size_of::<Map<Positional, u32>>(),
+        size_of::<[Option<u32>; 2]>()
+    );
+
+    let mut map = Map::new();
+    map.insert(Register::Data, 4);
+    map.insert(Register::Mode, 3);
+    map.insert(Register::Status, 1);
+
+    assert_eq!(map.get(Register::Mode), Some(&3));
+    assert_eq!(map.get(Register::Control), None);
+    assert_eq!(map.len(), 3);
+    assert!(map.iter().eq([
+        (Register::Status, &1),
+        (Register::Mode, &3),
+        (Register::Data, &4)
+    ]));
+    assert!(map.values().eq([&1, &3, &4]));
+    assert!(map.values_mut().rev().map(|v| *v).eq([4, 3, 1]));
+
+    map.retain(|key, _| key != Register::Mode);
+    assert!(map
+        .into_iter()
+        .eq([(Register::Status, 1), (Register::Data, 4)]));
+
+    assert_eq!(Register::COUNT, 4);
+    assert_eq!(Register::Mode.index(), 5);
+    assert_eq!(Register::Data.index(), 9);
+    assert_eq!(Register::from_index(4), Some(Register::Control));
+    assert_eq!(Register::from_index(3), None);
+    assert_eq!(Positional::Second.index(), 1);
+
+    let set: Set<_> = [Register::Data, Register::Control].into_iter().collect();
+    assert!(set.iter().eq([Register::Control, Register::Data]));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+#[key(storage = "dense")]
+#[repr(u8)]
+enum Unordered {
+    High = 200,
+    Low = 3,
+    Next,
+    Max = 255,
+}
+
+#[test]
+fn unordered_discriminants() {
+    assert_eq!(
+        size_of::<Map<Unordered, u8>>(),
+        size_of::<[Option<u8>; 256]>()
+    );
+
+    assert!(Unordered::keys().eq([
+        Unordered::High,
+        Unordered::Low,
+        Unordered::Next,
+        Unordered::Max
+    ]));
+
+    for key in Unordered::keys() {
+        assert_eq!(key.index(), key as u8 as usize);
+        assert_eq!(Unordered::from_index(key.index()), Some(key));
+    }
+
+    let map = Map::from_fn(|key: Unordered| key as u8);
+    assert!(map.keys().eq([
+        Unordered::Low,
+        Unordered::Next,
+        Unordered::High,
+        Unordered::Max
+    ]));
+    assert!(map.values().copied().eq([3, 4, 200, 255]));
+    assert!(map.iter().rev().map(|(key, _)| key).eq([
+        Unordered::Max,
+        Unordered::High,
+        Unordered::Next,
+        Unordered::Low
+    ]));
+
+    let mut set = Set::<Unordered>::full();
+    assert!(set.iter().eq(map.keys()));
+    set.remove(Unordered::Next);
+    assert!(set
+        .iter()
+        .eq([Unordered::Low, Unordered::High, Unordered::Max]));
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn par_iter_skips_unused_slots() {
+    use rayon::iter::ParallelIterator;
+
+    let mut map = Map::new();
+    map.insert(Register::Status, 1);
+    map.insert(Register::Data, 4);
+
+    let mut keys = map.par_keys().collect::<Vec<_>>();
+    keys.sort_by_key(|key| key.index());
+    assert_eq!(keys, [Register::Status, Register::Data]);
+    assert_eq!(map.par_values().sum::<u32>(), 5);
+}
@@ -0,0 +1,149 @@
+use fixed_map::map::{Entry, OccupiedEntry, VacantEntry};
+use fixed_map::{Key, Map, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Success {
+    Created,
+    Updated,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Failure {
+    NotFound,
+    Conflict,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Outcome {
+    Request(Result<Success, Failure>),
+    Timeout,
+}
+
+#[test]
+fn insert_get_remove() {
+    let mut map = Map::new();
+    assert_eq!(map.insert(Ok(Success::Created), 1), None);
+    assert_eq!(map.insert(Ok(Success::Created), 2), Some(1));
+    assert_eq!(map.insert(Err(Failure::Conflict), 3), None);
+    assert_eq!(map.len(), 2);
+
+    assert_eq!(map.get(Ok(Success::Created)), Some(&2));
+    assert_eq!(map.get(Ok(Success::Updated)), None);
+    assert_eq!(map.get(Err(Failure::Conflict)), Some(&3));
+    assert!(!map.contains_key(Err(Failure::NotFound)));
+
+    assert_eq!(map.remove(Err(Failure::Conflict)), Some(3));
+    assert_eq!(map.remove(Err(Failure::Conflict)), None);
+    assert_eq!(map.remove(Ok(Success::Created)), Some(2));
+    assert!(map.is_empty());
+}
+
+#[test]
+fn entry() {
+    let mut map = Map::new();
+    *map.entry(Err(Failure::NotFound)).or_default() += 1;
+    *map.entry(Err(Failure::NotFound)).or_default() += 1;
+    *map.entry(Ok(Success::Updated)).or_default() += 5;
+
+    assert_eq!(map.get(Err(Failure::NotFound)), Some(&2));
+    assert_eq!(map.get(Ok(Success::Updated)), Some(&5));
+
+    match map.entry(Err(Failure::NotFound)) {
+        Entry::Occupied(entry) => {
+            assert_eq!(entry.key(), Err(Failure::NotFound));
+            assert_eq!(entry.remove(), 2);
+        }
+        Entry::Vacant(..) => panic!("expected occupied entry"),
+    }
+
+    match map.entry(Ok(Success::Created)) {
+        Entry::Occupied(..) => panic!("expected vacant entry"),
+        Entry::Vacant(entry) => assert_eq!(entry.key(), Ok(Success::Created)),
+    }
+
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn ok_before_err() {
+    let mut map = Map::new();
+    map.insert(Err(Failure::Conflict), 4);
+    map.insert(Err(Failure::NotFound), 3);
+    map.insert(Ok(Success::Updated), 2);
+    map.insert(Ok(Success::Created), 1);
+
+    let expected = [
+        (Ok(Success::Created), 1),
+        (Ok(Success::Updated), 2),
+        (Err(Failure::NotFound), 3),
+        (Err(Failure::Conflict), 4),
+    ];
+
+    assert!(map.iter().map(|(k, v)| (k, *v)).eq(expected));
+    assert!(map
+        .iter()
+        .rev()
+        .map(|(k, v)| (k, *v))
+        .eq(expected.into_iter().rev()));
+    assert!(map.keys().eq(expected.map(|(k, _)| k)));
+    assert_eq!(map.iter().len(), 4);
+
+    for value in map.values_mut() {
+        *value *= 10;
+    }
+
+    assert!(map.values().copied().eq([10, 20, 30, 40]));
+    assert!(map.into_iter().eq(expected.map(|(k, v)| (k, v * 10))));
+}
+
+#[test]
+fn retain() {
+    let mut map = Map::new();
+    map.insert(Ok(Success::Created), 1);
+    map.insert(Err(Failure::NotFound), 2);
+    map.insert(Err(Failure::Conflict), 3);
+
+    map.retain(|key, _| key != Err(Failure::NotFound));
+
+    assert!(map
+        .keys()
+        .eq([Ok(Success::Created), Err(Failure::Conflict)]));
+}
+
+#[test]
+fn set() {
+    let mut set = Set::new();
+    assert!(set.insert(Err(Failure::NotFound)));
+    assert!(set.insert(Ok(Success::Updated)));
+    assert!(!set.insert(Ok(Success::Updated)));
+    assert_eq!(set.len(), 2);
+
+    assert!(set
+        .iter()
+        .eq([Ok(Success::Updated), Err(Failure::NotFound)]));
+    assert!(set
+        .iter()
+        .rev()
+        .eq([Err(Failure::NotFound), Ok(Success::Updated)]));
+
+    assert!(set.remove(Err(Failure::NotFound)));
+    assert!(!set.contains(Err(Failure::NotFound)));
+    assert!(set.into_iter().eq([Ok(Success::Updated)]));
+}
+
+#[test]
+fn nested_in_enum() {
+    let mut map = Map::new();
+    map.insert(Outcome::Timeout, 0);
+    map.insert(Outcome::Request(Err(Failure::Conflict)), 2);
+    map.insert(Outcome::Request(Ok(Success::Created)), 1);
+
+    assert_eq!(map.get(Outcome::Request(Ok(Success::Created))), Some(&1));
+    assert_eq!(map.get(Outcome::Request(Err(Failure::NotFound))), None);
+
+    assert!(map.keys().eq([
+        Outcome::Request(Ok(Success::Created)),
+        Outcome::Request(Err(Failure::Conflict)),
+        Outcome::Timeout,
+    ]));
+}
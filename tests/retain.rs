@@ -0,0 +1,216 @@
+use fixed_map::{Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Key {
+    Option(Option<Part>),
+    Singleton(()),
+}
+
+#[test]
+fn retain_option_none() {
+    let mut map = Map::new();
+    map.insert(Key::Option(None), 1);
+    map.insert(Key::Option(Some(Part::One)), 2);
+    map.insert(Key::Option(Some(Part::Two)), 3);
+
+    let mut seen = Vec::new();
+
+    map.retain(|k, _| {
+        seen.push(k);
+        k != Key::Option(None)
+    });
+
+    assert_eq!(
+        seen,
+        [
+            Key::Option(Some(Part::One)),
+            Key::Option(Some(Part::Two)),
+            Key::Option(None)
+        ]
+    );
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get(Key::Option(None)), None);
+    assert_eq!(map.get(Key::Option(Some(Part::One))), Some(&2));
+}
+
+#[test]
+fn retain_singleton() {
+    let mut map = Map::new();
+    map.insert(Key::Singleton(()), 1);
+
+    map.retain(|k, v| {
+        assert_eq!(k, Key::Singleton(()));
+        *v += 1;
+        true
+    });
+
+    assert_eq!(map.get(Key::Singleton(())), Some(&2));
+
+    map.retain(|_, _| false);
+    assert!(map.is_empty());
+
+    let mut map = Map::<(), u32>::new();
+    map.insert((), 1);
+    map.retain(|(), _| false);
+    assert!(map.is_empty());
+}
+
+#[test]
+fn filter_leaves_original() {
+    let mut map = Map::new();
+    map.insert(Key::Option(None), 1);
+    map.insert(Key::Option(Some(Part::Two)), 2);
+    map.insert(Key::Singleton(()), 3);
+
+    let odd = map.filter(|_, v| v % 2 == 1);
+
+    assert!(odd
+        .iter()
+        .eq([(Key::Option(None), &1), (Key::Singleton(()), &3)]));
+    assert_eq!(map.len(), 3);
+}
+
+#[test]
+fn retain_with_removed_hands_out_values() {
+    let mut map = Map::new();
+    map.insert(Key::Option(None), String::from("none"));
+    map.insert(Key::Option(Some(Part::One)), String::from("one"));
+    map.insert(Key::Singleton(()), String::from("singleton"));
+
+    let mut removed = Vec::new();
+
+    map.retain_with_removed(
+        |k, v| {
+            v.push('!');
+            k == Key::Option(Some(Part::One))
+        },
+        |k, v| removed.push((k, v)),
+    );
+
+    assert_eq!(map.len(), 1);
+    assert_eq!(
+        map.get(Key::Option(Some(Part::One))).map(String::as_str),
+        Some("one!")
+    );
+
+    removed.sort_by_key(|(_, v)| v.clone());
+    assert_eq!(
+        removed,
+        [
+            (Key::Option(None), String::from("none!")),
+            (Key::Singleton(()), String::from("singleton!")),
+        ]
+    );
+}
+
+#[test]
+fn retain_with_removed_in_place() {
+    fn check<K>(keys: [K; 4])
+    where
+        K: fixed_map::key::Key + core::fmt::Debug + PartialEq,
+    {
+        let mut map = Map::new();
+
+        for (n, key) in keys.iter().cloned().enumerate() {
+            map.insert(key, n);
+        }
+
+        let mut removed = Vec::new();
+        map.retain_with_removed(|_, v| *v % 2 == 0, |k, v| removed.push((k, v)));
+
+        assert_eq!(map.len(), 2);
+        assert!(map.values().all(|v| v % 2 == 0));
+        assert_eq!(removed.len(), 2);
+
+        for (k, v) in removed {
+            assert_eq!(keys[v], k);
+            assert!(!map.contains_key(k));
+        }
+    }
+
+    check([0u8, 42, 128, u8::MAX]);
+    check([(false, true), (true, false), (true, true), (false, false)]);
+    check([Ok(false), Err(true), Ok(true), Err(false)]);
+    check([
+        Key::Option(Some(Part::One)),
+        Key::Option(None),
+        Key::Option(Some(Part::Two)),
+        Key::Singleton(()),
+    ]);
+}
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn retain_with_removed_keeps_allocation() {
+    let mut map = (0u32..64).map(|n| (n, n)).collect::<Map<_, _>>();
+
+    let mut removed = 0;
+    map.retain_with_removed(|k, _| k % 4 == 0, |_, _| removed += 1);
+
+    assert_eq!(removed, 48);
+    assert_eq!(map.len(), 16);
+
+    // Removals may leave markers behind which count against the capacity
+    // until the table is rehashed, but it still has room for every entry it
+    // held before, so it wasn't rebuilt into a smaller table.
+    assert!(map.memory_footprint() >= 64 * core::mem::size_of::<(u32, u32)>());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn retain_with_removed_panic_keeps_unvisited() {
+    use std::panic::{self, AssertUnwindSafe};
+
+    let mut map = (0u8..10).map(|n| (n, n)).collect::<Map<_, _>>();
+    let mut removed = Vec::new();
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        map.retain_with_removed(
+            |k, _| {
+                assert!(k < 5, "stop");
+                k % 2 == 1
+            },
+            |k, _| removed.push(k),
+        );
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(removed, [0, 2, 4]);
+    assert!(map.keys().eq([1, 3, 5, 6, 7, 8, 9]));
+    assert_eq!(map.len(), 7);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn retain_with_removed_ordered() {
+    #[derive(Debug, Clone, Copy, PartialEq, Key)]
+    enum Score {
+        #[key(ordered)]
+        Points(u32),
+        Unranked,
+    }
+
+    let mut map = (0..8).map(|n| (Score::Points(n), n)).collect::<Map<_, _>>();
+    map.insert(Score::Unranked, 8);
+
+    let mut removed = Vec::new();
+    map.retain_with_removed(|_, v| *v % 3 == 0, |k, _| removed.push(k));
+
+    assert!(map
+        .keys()
+        .eq([Score::Points(0), Score::Points(3), Score::Points(6)]));
+    assert_eq!(
+        removed,
+        [1, 2, 4, 5, 7]
+            .map(Score::Points)
+            .into_iter()
+            .chain([Score::Unranked])
+            .collect::<Vec<_>>()
+    );
+}
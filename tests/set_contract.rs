@@ -0,0 +1,124 @@
+#![cfg(feature = "proptest")]
+
+use std::fmt;
+
+use fixed_map::{Key, Set};
+use proptest::collection::vec;
+use proptest::option::of;
+use proptest::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+    Three,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Composite {
+    Part(Option<Part>),
+    Flag(Option<bool>),
+    Unit,
+}
+
+fn part() -> impl Strategy<Value = Part> {
+    prop_oneof![Just(Part::One), Just(Part::Two), Just(Part::Three)]
+}
+
+fn composite() -> impl Strategy<Value = Composite> {
+    prop_oneof![
+        of(part()).prop_map(Composite::Part),
+        of(any::<bool>()).prop_map(Composite::Flag),
+        Just(Composite::Unit),
+    ]
+}
+
+/// Apply a sequence of inserts (`true`) and removes (`false`) to a set and a
+/// naive model of it, checking that the set agrees with the model after each
+/// operation.
+#[allow(clippy::len_zero)]
+fn check<T>(ops: Vec<(bool, T)>) -> Result<(), TestCaseError>
+where
+    T: fixed_map::key::Key + fmt::Debug + PartialEq,
+{
+    let mut set = Set::new();
+    let mut model = Vec::new();
+
+    for (insert, value) in ops {
+        if insert {
+            set.insert(value.clone());
+
+            if !model.contains(&value) {
+                model.push(value);
+            }
+        } else {
+            set.remove(value.clone());
+            model.retain(|v| *v != value);
+        }
+
+        prop_assert_eq!(set.is_empty(), set.len() == 0);
+        prop_assert_eq!(set.len(), set.iter().count());
+        prop_assert_eq!(set.len(), model.len());
+        prop_assert!(model.iter().all(|v| set.contains(v.clone())));
+        prop_assert!(set.iter().all(|v| model.contains(&v)));
+    }
+
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn unit(ops in vec((any::<bool>(), part()), 0..32)) {
+        check(ops)?;
+    }
+
+    #[test]
+    fn boolean(ops in vec((any::<bool>(), any::<bool>()), 0..32)) {
+        check(ops)?;
+    }
+
+    #[test]
+    fn option(ops in vec((any::<bool>(), of(part())), 0..32)) {
+        check(ops)?;
+    }
+
+    #[test]
+    fn option_bool(ops in vec((any::<bool>(), of(any::<bool>())), 0..32)) {
+        check(ops)?;
+    }
+
+    #[test]
+    fn nested_option(ops in vec((any::<bool>(), of(of(part()))), 0..32)) {
+        check(ops)?;
+    }
+
+    #[test]
+    fn nested_option_bool(ops in vec((any::<bool>(), of(of(any::<bool>()))), 0..32)) {
+        check(ops)?;
+    }
+
+    #[test]
+    fn composite_enum(ops in vec((any::<bool>(), composite()), 0..32)) {
+        check(ops)?;
+    }
+
+    #[test]
+    fn tuple(ops in vec((any::<bool>(), (part(), of(any::<bool>()))), 0..32)) {
+        check(ops)?;
+    }
+
+    #[test]
+    fn result(ops in vec((any::<bool>(), prop_oneof![part().prop_map(Ok), any::<bool>().prop_map(Err)]), 0..32)) {
+        check(ops)?;
+    }
+
+    #[test]
+    fn byte(ops in vec((any::<bool>(), any::<u8>()), 0..32)) {
+        check(ops)?;
+    }
+
+    #[test]
+    fn unit_type(ops in vec((any::<bool>(), Just(())), 0..32)) {
+        check(ops)?;
+    }
+}
@@ -0,0 +1,37 @@
+use fixed_map::{Key, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Key {
+    First,
+    Second(Option<bool>),
+    Third,
+}
+
+fn set() -> Set<Key> {
+    Set::from_array([Key::Third, Key::Second(None), Key::First])
+}
+
+#[test]
+fn by_value() {
+    let mut seen = Vec::new();
+
+    for value in set() {
+        seen.push(value);
+    }
+
+    assert_eq!(seen, [Key::First, Key::Second(None), Key::Third]);
+    assert_eq!(set().into_iter().collect::<Vec<_>>(), seen);
+}
+
+#[test]
+fn by_reference() {
+    let set = set();
+    let mut seen = Vec::new();
+
+    for value in &set {
+        seen.push(value);
+    }
+
+    assert_eq!(seen, set.iter().collect::<Vec<_>>());
+    assert_eq!(set.len(), 3);
+}
@@ -0,0 +1,67 @@
+use fixed_map::{Key, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Key {
+    Simple,
+    Option(Option<Part>),
+    Flag(bool),
+}
+
+const ALL: [Key; 6] = [
+    Key::Simple,
+    Key::Option(None),
+    Key::Option(Some(Part::One)),
+    Key::Option(Some(Part::Two)),
+    Key::Flag(false),
+    Key::Flag(true),
+];
+
+fn check(set: &Set<Key>, expected: impl Fn(Key) -> bool) {
+    for key in ALL {
+        assert_eq!(set.contains(key), expected(key), "{key:?}");
+    }
+
+    assert_eq!(set.len(), ALL.iter().filter(|&&key| expected(key)).count());
+}
+
+#[test]
+fn in_place_operations() {
+    let a = Set::from_array([Key::Simple, Key::Option(None), Key::Flag(true)]);
+    let b = Set::from_array([
+        Key::Option(None),
+        Key::Option(Some(Part::Two)),
+        Key::Flag(true),
+    ]);
+
+    let mut union = a;
+    union.union_with(&b);
+    check(&union, |key| a.contains(key) || b.contains(key));
+
+    let mut intersection = a;
+    intersection.intersect_with(&b);
+    check(&intersection, |key| a.contains(key) && b.contains(key));
+
+    let mut difference = a;
+    difference.subtract(&b);
+    check(&difference, |key| a.contains(key) && !b.contains(key));
+}
+
+#[test]
+fn with_empty() {
+    let a = Set::from_array([Key::Simple, Key::Flag(false)]);
+    let empty = Set::new();
+
+    let mut set = a;
+    set.union_with(&empty);
+    set.subtract(&empty);
+    assert_eq!(set, a);
+
+    set.intersect_with(&empty);
+    assert!(set.is_empty());
+}
@@ -0,0 +1,53 @@
+use fixed_map::{Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Key {
+    Simple,
+    Option(Option<Part>),
+    Flag(bool),
+}
+
+#[test]
+fn rollback_composite() {
+    let mut map = Map::new();
+    map.insert(Key::Simple, String::from("simple"));
+    map.insert(Key::Option(None), String::from("none"));
+
+    let snapshot = map.snapshot();
+    let expected = snapshot.clone().into_map();
+
+    map.insert(Key::Flag(true), String::from("flag"));
+    map.insert(Key::Option(Some(Part::Two)), String::from("two"));
+    map.remove(Key::Simple);
+
+    if let Some(value) = map.get_mut(Key::Option(None)) {
+        value.push('!');
+    }
+
+    map.restore(&snapshot);
+    assert_eq!(map, expected);
+
+    map.clear();
+    map.restore(&snapshot);
+    assert_eq!(map, expected);
+}
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn rollback_dynamic() {
+    let mut map = (0..16u32).map(|n| (n, n)).collect::<Map<_, _>>();
+    let snapshot = map.snapshot();
+
+    map.retain(|key, _| key % 2 == 0);
+    map.insert(100, 100);
+    assert_ne!(map, snapshot.clone().into_map());
+
+    map.restore(&snapshot);
+    assert_eq!(map, snapshot.into_map());
+}
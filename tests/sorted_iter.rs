@@ -0,0 +1,90 @@
+#![cfg(feature = "hashbrown")]
+
+use fixed_map::{Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Key)]
+enum Key {
+    Name(&'static str),
+    Id(u32),
+    Unit,
+}
+
+#[test]
+fn stable_across_insertion_order() {
+    let entries = [
+        (Key::Unit, 0),
+        (Key::Id(3), 1),
+        (Key::Name("b"), 2),
+        (Key::Id(1), 3),
+        (Key::Name("a"), 4),
+    ];
+
+    let forward = entries.into_iter().collect::<Map<_, _>>();
+    let backward = entries.into_iter().rev().collect::<Map<_, _>>();
+
+    let expected = [
+        (Key::Name("a"), &4),
+        (Key::Name("b"), &2),
+        (Key::Id(1), &3),
+        (Key::Id(3), &1),
+        (Key::Unit, &0),
+    ];
+
+    assert!(forward.sorted_iter().eq(expected));
+    assert!(backward.sorted_iter().eq(expected));
+    assert!(forward.sorted_iter().rev().eq(expected.into_iter().rev()));
+}
+
+#[test]
+fn ord_differs_from_declaration_order() {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Key)]
+    enum Reversed {
+        First,
+        Second,
+    }
+
+    impl PartialOrd for Reversed {
+        fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Reversed {
+        fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+            (*other as u8).cmp(&(*self as u8))
+        }
+    }
+
+    let map = Map::from_array([(Reversed::First, 1), (Reversed::Second, 2)]);
+
+    assert!(map.keys().eq([Reversed::First, Reversed::Second]));
+    assert!(map
+        .sorted_iter()
+        .eq([(Reversed::Second, &2), (Reversed::First, &1)]));
+}
+
+#[test]
+fn sorted_by_value_descending() {
+    let map = [
+        (Key::Name("a"), 2),
+        (Key::Id(7), 5),
+        (Key::Unit, 1),
+        (Key::Id(1), 5),
+    ]
+    .into_iter()
+    .collect::<Map<_, _>>();
+
+    let by_value = map
+        .iter_sorted_by(|&(ka, a), &(kb, b)| b.cmp(a).then(ka.cmp(&kb)))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        by_value,
+        [
+            (Key::Id(1), &5),
+            (Key::Id(7), &5),
+            (Key::Name("a"), &2),
+            (Key::Unit, &1),
+        ]
+    );
+}
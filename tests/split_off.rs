@@ -0,0 +1,51 @@
+use fixed_map::{Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Setting {
+    Theme,
+    Port(Option<bool>),
+    Volume,
+}
+
+#[test]
+fn split_off() {
+    let mut config = Map::new();
+    config.insert(Setting::Theme, String::from("dark"));
+    config.insert(Setting::Port(None), String::from("80"));
+    config.insert(Setting::Port(Some(true)), String::from("443"));
+    config.insert(Setting::Volume, String::from("10"));
+
+    let ports = config.split_off(|key| matches!(key, Setting::Port(..)));
+
+    assert!(ports.iter().eq([
+        (Setting::Port(Some(true)), &String::from("443")),
+        (Setting::Port(None), &String::from("80")),
+    ]));
+    assert!(config.keys().eq([Setting::Theme, Setting::Volume]));
+
+    let mut empty = config.split_off(|_| false);
+    assert!(empty.is_empty());
+    assert_eq!(config.len(), 2);
+
+    empty = config.split_off(|_| true);
+    assert_eq!(empty.len(), 2);
+    assert!(config.is_empty());
+}
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn split_off_hashbrown() {
+    let mut map: Map<u32, u32> = (0..10).map(|n| (n, n * 10)).collect();
+
+    let even = map.split_off(|key| key % 2 == 0);
+
+    assert_eq!(even.len(), 5);
+    assert_eq!(map.len(), 5);
+    assert_eq!(even.get(4), Some(&40));
+    assert_eq!(map.get(4), None);
+    assert_eq!(map.get(5), Some(&50));
+
+    // Removals may leave markers behind which count against the capacity,
+    // but the map still has room for every entry it held before the split.
+    assert!(map.memory_footprint() >= 10 * core::mem::size_of::<(u32, u32)>());
+}
@@ -0,0 +1,124 @@
+#![cfg(feature = "hashbrown")]
+
+use fixed_map::map::{Entry, OccupiedEntry, VacantEntry};
+use fixed_map::{Key, Map, Set};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Key)]
+enum Name {
+    First(String),
+    Second,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Key)]
+enum Ranked {
+    #[key(ordered)]
+    Named(String),
+    Anonymous,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Key)]
+#[key(storage = "btree")]
+enum Sorted {
+    Named(String),
+    Anonymous,
+}
+
+fn first(name: &str) -> Name {
+    Name::First(name.to_owned())
+}
+
+#[test]
+fn string_map() {
+    let mut map = Map::new();
+    assert_eq!(map.insert(first("a"), 1), None);
+    assert_eq!(map.insert(first("b"), 2), None);
+    assert_eq!(map.insert(Name::Second, 3), None);
+    assert_eq!(map.insert(first("a"), 4), Some(1));
+
+    assert_eq!(map.get(first("a")), Some(&4));
+    assert_eq!(map.get(first("c")), None);
+    assert_eq!(map.len(), 3);
+
+    let mut keys = map.keys().collect::<Vec<_>>();
+    keys.sort_by_key(|key| format!("{key:?}"));
+    assert_eq!(keys, [first("a"), first("b"), Name::Second]);
+
+    map.retain(|key, _| key != first("b"));
+    assert_eq!(map.remove(first("a")), Some(4));
+    assert!(map.into_iter().eq([(Name::Second, 3)]));
+}
+
+#[test]
+fn string_entry() {
+    let mut map = Map::new();
+
+    match map.entry(first("a")) {
+        Entry::Vacant(entry) => {
+            assert_eq!(entry.key(), first("a"));
+            entry.insert(1);
+        }
+        Entry::Occupied(..) => panic!("expected a vacant entry"),
+    }
+
+    match map.entry(first("a")) {
+        Entry::Occupied(entry) => {
+            assert_eq!(entry.key(), first("a"));
+            assert_eq!(entry.remove(), 1);
+        }
+        Entry::Vacant(..) => panic!("expected an occupied entry"),
+    }
+
+    *map.entry(Name::Second).or_default() += 1;
+    assert!(map.iter().eq([(Name::Second, &1)]));
+}
+
+#[test]
+fn string_set() {
+    let mut set = Set::new();
+    assert!(set.insert(first("a")));
+    assert!(!set.insert(first("a")));
+    assert!(set.insert(Name::Second));
+
+    assert!(set.contains(first("a")));
+    assert!(!set.contains(first("b")));
+
+    set.retain(|key| key == Name::Second);
+    assert!(set.iter().eq([Name::Second]));
+}
+
+#[test]
+fn string_tuple() {
+    let mut map = Map::new();
+    map.insert((String::from("a"), true), 1);
+    map.insert((String::from("a"), false), 2);
+
+    assert_eq!(map.get((String::from("a"), false)), Some(&2));
+    assert_eq!(map.remove((String::from("a"), true)), Some(1));
+    assert!(map.keys().eq([(String::from("a"), false)]));
+}
+
+#[test]
+fn string_ordered() {
+    let mut map = Map::new();
+    map.insert(Ranked::Anonymous, 0);
+    map.insert(Ranked::Named(String::from("b")), 2);
+    map.insert(Ranked::Named(String::from("a")), 1);
+
+    assert!(map.values().eq([&1, &2, &0]));
+
+    let mut map = Map::new();
+    map.insert(Sorted::Anonymous, 0);
+    map.insert(Sorted::Named(String::from("b")), 2);
+    map.insert(Sorted::Named(String::from("a")), 1);
+
+    assert!(map.values().eq([&1, &2, &0]));
+}
+
+#[test]
+fn string_borrowed() {
+    let mut map = Map::new();
+    map.insert(String::from("alpha"), 1);
+
+    assert_eq!(map.get_borrowed("alpha"), Some(&1));
+    assert_eq!(map.get_borrowed("beta"), None);
+}
@@ -0,0 +1,68 @@
+use fixed_map::{Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Key {
+    Unit,
+    Bool(bool),
+    Option(Option<u8>),
+}
+
+#[test]
+fn swap_composite() {
+    let mut map = Map::new();
+    map.insert(Key::Unit, String::from("unit"));
+    map.insert(Key::Option(None), String::from("none"));
+
+    map.swap(Key::Unit, Key::Option(None));
+    assert_eq!(map.get(Key::Unit).map(String::as_str), Some("none"));
+    assert_eq!(map.get(Key::Option(None)).map(String::as_str), Some("unit"));
+
+    map.swap(Key::Option(None), Key::Bool(true));
+    assert_eq!(map.get(Key::Option(None)), None);
+    assert_eq!(map.get(Key::Bool(true)).map(String::as_str), Some("unit"));
+
+    map.swap(Key::Bool(false), Key::Option(Some(1)));
+    assert_eq!(map.len(), 2);
+
+    map.swap(Key::Unit, Key::Unit);
+    assert_eq!(map.get(Key::Unit).map(String::as_str), Some("none"));
+}
+
+#[cfg(feature = "hashbrown")]
+#[test]
+fn swap_dynamic() {
+    let mut map = Map::new();
+    map.insert(1u32, 'a');
+    map.insert(2u32, 'b');
+
+    map.swap(1, 2);
+    assert_eq!(map.get(1), Some(&'b'));
+    assert_eq!(map.get(2), Some(&'a'));
+
+    map.swap(2, 3);
+    assert_eq!(map.get(2), None);
+    assert_eq!(map.get(3), Some(&'a'));
+}
+
+#[test]
+fn swap_each_side() {
+    let mut map = Map::new();
+    map.insert(Key::Bool(true), 1);
+
+    map.swap(Key::Bool(true), Key::Unit);
+    assert!(map.iter().eq([(Key::Unit, &1)]));
+
+    map.swap(Key::Bool(false), Key::Unit);
+    assert!(map.iter().eq([(Key::Bool(false), &1)]));
+
+    map.insert(Key::Option(Some(2)), 2);
+    map.swap(Key::Option(Some(2)), Key::Bool(false));
+    assert!(map
+        .iter()
+        .eq([(Key::Bool(false), &2), (Key::Option(Some(2)), &1)]));
+
+    map.swap(Key::Option(Some(2)), Key::Option(Some(2)));
+    assert!(map
+        .iter()
+        .eq([(Key::Bool(false), &2), (Key::Option(Some(2)), &1)]));
+}
@@ -0,0 +1,52 @@
+use fixed_map::{Key, Map};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Part {
+    One,
+    Two,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Key {
+    Simple,
+    Option(Option<Part>),
+    Flag(bool),
+}
+
+#[test]
+fn distinct_composite_keys() {
+    let entries = [
+        (Key::Option(None), 1),
+        (Key::Option(Some(Part::One)), 2),
+        (Key::Option(Some(Part::Two)), 3),
+        (Key::Flag(false), 4),
+        (Key::Flag(true), 5),
+        (Key::Simple, 6),
+    ];
+
+    let map = Map::try_from_iter(entries).unwrap();
+    assert_eq!(map, entries.into_iter().collect::<Map<_, _>>());
+}
+
+#[test]
+fn first_duplicate_is_reported() {
+    let mut rest = [
+        (Key::Flag(true), 1),
+        (Key::Option(None), 2),
+        (Key::Option(None), 3),
+        (Key::Flag(true), 4),
+    ]
+    .into_iter();
+
+    assert_eq!(
+        Map::try_from_iter(rest.by_ref()),
+        Err((Key::Option(None), 3))
+    );
+    assert!(rest.eq([(Key::Flag(true), 4)]));
+}
+
+#[test]
+fn empty() {
+    let map = Map::<Key, u32>::try_from_iter([]).unwrap();
+    assert!(map.is_empty());
+}
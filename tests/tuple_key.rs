@@ -0,0 +1,176 @@
+use fixed_map::map::{Entry, OccupiedEntry, VacantEntry};
+use fixed_map::{Key, Map, Set};
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum State {
+    Idle,
+    Running,
+    Stopped,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Event {
+    Start,
+    Stop,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Key)]
+enum Composite {
+    Pair((State, bool)),
+    Unit,
+}
+
+#[test]
+fn insert_get_remove() {
+    let mut map = Map::new();
+    assert_eq!(map.insert((State::Idle, Event::Start), 1), None);
+    assert_eq!(map.insert((State::Idle, Event::Start), 2), Some(1));
+    assert_eq!(map.insert((State::Running, Event::Stop), 3), None);
+    assert_eq!(map.len(), 2);
+
+    assert_eq!(map.get((State::Idle, Event::Start)), Some(&2));
+    assert_eq!(map.get((State::Idle, Event::Stop)), None);
+    assert_eq!(map.get((State::Stopped, Event::Start)), None);
+
+    assert_eq!(map.remove((State::Idle, Event::Start)), Some(2));
+    assert_eq!(map.remove((State::Idle, Event::Start)), None);
+    assert_eq!(map.len(), 1);
+    assert!(!map.is_empty());
+
+    assert_eq!(map.remove((State::Running, Event::Stop)), Some(3));
+    assert!(map.is_empty());
+}
+
+#[test]
+fn entry() {
+    let mut map = Map::new();
+
+    // No storage for the first element of the key yet.
+    *map.entry((State::Running, Event::Start)).or_default() += 1;
+    // Storage for the first element exists, but not for the second.
+    *map.entry((State::Running, Event::Stop)).or_default() += 2;
+    // Both exist.
+    *map.entry((State::Running, Event::Start)).or_default() += 10;
+
+    assert_eq!(map.get((State::Running, Event::Start)), Some(&11));
+    assert_eq!(map.get((State::Running, Event::Stop)), Some(&2));
+    assert_eq!(map.len(), 2);
+
+    match map.entry((State::Running, Event::Stop)) {
+        Entry::Occupied(entry) => {
+            assert_eq!(entry.key(), (State::Running, Event::Stop));
+            assert_eq!(entry.remove(), 2);
+        }
+        Entry::Vacant(..) => panic!("expected occupied entry"),
+    }
+
+    assert_eq!(map.len(), 1);
+
+    match map.entry((State::Idle, Event::Stop)) {
+        Entry::Occupied(..) => panic!("expected vacant entry"),
+        Entry::Vacant(entry) => assert_eq!(entry.key(), (State::Idle, Event::Stop)),
+    }
+}
+
+#[test]
+fn lexicographic_iteration() {
+    let mut map = Map::new();
+    map.insert((State::Stopped, Event::Start), 4);
+    map.insert((State::Running, Event::Stop), 3);
+    map.insert((State::Idle, Event::Stop), 2);
+    map.insert((State::Idle, Event::Start), 1);
+
+    let expected = [
+        ((State::Idle, Event::Start), 1),
+        ((State::Idle, Event::Stop), 2),
+        ((State::Running, Event::Stop), 3),
+        ((State::Stopped, Event::Start), 4),
+    ];
+
+    assert!(map.iter().map(|(k, v)| (k, *v)).eq(expected));
+    assert!(map
+        .iter()
+        .rev()
+        .map(|(k, v)| (k, *v))
+        .eq(expected.into_iter().rev()));
+    assert!(map.keys().eq(expected.map(|(k, _)| k)));
+    assert!(map.values().copied().eq([1, 2, 3, 4]));
+    assert_eq!(map.iter().len(), 4);
+
+    for value in map.values_mut() {
+        *value *= 10;
+    }
+
+    assert!(map.into_iter().eq(expected.map(|(k, v)| (k, v * 10))));
+}
+
+#[test]
+fn equality_ignores_emptied_storage() {
+    let mut a = Map::new();
+    a.insert((State::Idle, Event::Start), 1);
+    a.insert((State::Running, Event::Stop), 2);
+    a.remove((State::Running, Event::Stop));
+
+    let mut b = Map::new();
+    b.insert((State::Idle, Event::Start), 1);
+
+    assert_eq!(a, b);
+
+    b.insert((State::Idle, Event::Stop), 1);
+    assert_ne!(a, b);
+}
+
+#[test]
+fn retain() {
+    let mut map = Map::new();
+    map.insert((State::Idle, Event::Start), 1);
+    map.insert((State::Idle, Event::Stop), 2);
+    map.insert((State::Running, Event::Start), 3);
+
+    map.retain(|(_, event), _| event == Event::Start);
+
+    assert!(map
+        .keys()
+        .eq([(State::Idle, Event::Start), (State::Running, Event::Start)]));
+}
+
+#[test]
+fn set() {
+    let mut set = Set::new();
+    assert!(set.insert((State::Running, Event::Stop)));
+    assert!(!set.insert((State::Running, Event::Stop)));
+    assert!(set.insert((State::Idle, Event::Stop)));
+    assert!(set.insert((State::Idle, Event::Start)));
+    assert_eq!(set.len(), 3);
+
+    assert!(set.iter().eq([
+        (State::Idle, Event::Start),
+        (State::Idle, Event::Stop),
+        (State::Running, Event::Stop),
+    ]));
+
+    assert!(set.remove((State::Running, Event::Stop)));
+    assert!(!set.remove((State::Running, Event::Stop)));
+    assert!(!set.contains((State::Running, Event::Stop)));
+    assert_eq!(set.len(), 2);
+
+    set.retain(|(_, event)| event == Event::Stop);
+    assert!(set.into_iter().eq([(State::Idle, Event::Stop)]));
+}
+
+#[test]
+fn nested_in_enum() {
+    let mut map = Map::new();
+    map.insert(Composite::Unit, 0);
+    map.insert(Composite::Pair((State::Running, true)), 2);
+    map.insert(Composite::Pair((State::Idle, false)), 1);
+
+    assert_eq!(map.get(Composite::Pair((State::Idle, false))), Some(&1));
+    assert_eq!(map.get(Composite::Pair((State::Idle, true))), None);
+
+    assert!(map.keys().eq([
+        Composite::Pair((State::Idle, false)),
+        Composite::Pair((State::Running, true)),
+        Composite::Unit,
+    ]));
+}